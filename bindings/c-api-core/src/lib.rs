@@ -2,6 +2,7 @@
 //! Most notably for WebAssembly.
 
 use verilization_compiler::{lang, model, parser, load_all_models, VError, MemoryOutputHandler};
+use verilization_compiler::source::SourceFile;
 use lang::{GeneratorError, Language, LanguageOptions, LanguageOptionsBuilder, LanguageRegistry, LanguageHandler};
 
 use std::ffi::{c_void, OsString};
@@ -135,11 +136,10 @@ pub unsafe extern "C" fn verilization_parse(nfiles: usize, files: *const *const
 }
 
 unsafe fn verilization_parse_impl(files: &[*const APIString]) -> Result<model::Verilization, VError> {
-    let models = files.iter().map(|content| {
+    let models = files.iter().enumerate().map(|(index, content)| {
         let content = content.as_ref().expect("Pointer was null").to_str().expect("Invalid String");
-        let (_, model) = parser::parse_model(content)?;
-        let model = model()?;
-        Ok(model)
+        let file = SourceFile::new(format!("<input {}>", index + 1), String::from(content));
+        parser::parse_model(&file)
     });
 
     load_all_models(models)
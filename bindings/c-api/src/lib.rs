@@ -12,7 +12,8 @@ pub unsafe extern "C" fn verilization_generate(verilization: *const model::Veril
 	let registry = language_registry_new()
 		.add_language::<verilization_lang_typescript::TypeScriptLanguage>()
 		.add_language::<verilization_lang_java::JavaLanguage>()
-		.add_language::<verilization_lang_scala::ScalaLanguage>();
+		.add_language::<verilization_lang_scala::ScalaLanguage>()
+		.add_language::<verilization_lang_rust::RustLanguage>();
 
     verilization_generate_impl(verilization, language, noptions, options, result, &registry)
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use verilization_compiler::*;
 
 use std::env;
 use std::ffi::OsString;
+use std::path::PathBuf;
 use lang::{Language, LanguageOptions, LanguageOptionsBuilder, LanguageRegistry, LanguageHandler};
 
 
@@ -17,8 +18,645 @@ fn command_help() -> Result<i32, VError> {
 	Ok(0)
 }
 
-fn command_generate<Lang: Language>(input_files: Vec<OsString>, options: Lang::Options) -> Result<i32, VError> {
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+	Text,
+	Json,
+}
+
+fn command_check(input_files: Vec<OsString>, lockfile_path: PathBuf, format: ReportFormat) -> Result<i32, VError> {
+	let result = load_files(input_files)
+		.and_then(|model| check_lockfile(&model, &lockfile_path));
+
+	match format {
+		ReportFormat::Text => result.map(|_| 0),
+		ReportFormat::Json => {
+			let diagnostics = match &result {
+				Ok(_) => Vec::new(),
+				Err(err) => err.errors().iter().map(json_diagnostic).collect(),
+			};
+
+			let report = serde_json::json!({
+				"success": result.is_ok(),
+				"diagnostics": diagnostics,
+			});
+			println!("{}", report);
+
+			Ok(if result.is_ok() { 0 } else { 1 })
+		},
+	}
+}
+
+fn json_diagnostic(err: &VError) -> serde_json::Value {
+	let location = err.span().map(|span| {
+		let (line, column) = span.line_col();
+		let (end_line, end_column) = span.file().line_col(span.end());
+		serde_json::json!({
+			"file": span.file().name(),
+			"line": line,
+			"column": column,
+			"end_line": end_line,
+			"end_column": end_column,
+		})
+	});
+
+	serde_json::json!({
+		"severity": "error",
+		"code": err.code(),
+		"message": err.to_string(),
+		"location": location,
+	})
+}
+
+fn parse_check_args<Args>(mut args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut input_files = Vec::new();
+	let mut lockfile_path = PathBuf::from(lockfile::LOCKFILE_NAME);
+	let mut format = ReportFormat::Text;
+
+	while let Some(arg) = args.next() {
+		match arg.to_str().unwrap() {
+			"-i" => {
+				if let Some(filename) = args.next() {
+					input_files.push(filename)
+				}
+				else {
+					println!("Missing value for input file");
+					return Ok(1);
+				}
+			},
+
+			"--lockfile" => {
+				if let Some(path) = args.next() {
+					lockfile_path = PathBuf::from(path)
+				}
+				else {
+					println!("Missing value for lockfile");
+					return Ok(1);
+				}
+			},
+
+			"--format" => {
+				match args.next().as_ref().and_then(|value| value.to_str()) {
+					Some("text") => format = ReportFormat::Text,
+					Some("json") => format = ReportFormat::Json,
+					Some(value) => {
+						println!("Unknown format: {}", value);
+						return Ok(1);
+					},
+					None => {
+						println!("Missing value for format");
+						return Ok(1);
+					},
+				}
+			},
+
+			arg => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	command_check(input_files, lockfile_path, format)
+}
+
+fn command_fmt(input_files: Vec<OsString>, check: bool) -> Result<i32, VError> {
+	let mut exit_code = 0;
+	let mut errors = Vec::new();
+
+	for input_file in input_files {
+		let path = PathBuf::from(input_file);
+		let content = match std::fs::read_to_string(&path) {
+			Ok(content) => content,
+			Err(err) => {
+				errors.push(VError::FileReadError(path, err));
+				continue;
+			},
+		};
+
+		let file = source::SourceFile::new(path.to_string_lossy().into_owned(), content);
+		let formatted = match format::format_source(&file) {
+			Ok(formatted) => formatted,
+			Err(err) => {
+				errors.push(err);
+				continue;
+			},
+		};
+
+		if formatted != file.content() {
+			if check {
+				println!("{} is not formatted", path.display());
+				exit_code = 1;
+			}
+			else {
+				std::fs::write(&path, formatted)?;
+			}
+		}
+	}
+
+	if errors.is_empty() {
+		Ok(exit_code)
+	}
+	else {
+		Err(VError::from_errors(errors))
+	}
+}
+
+fn parse_fmt_args<Args>(mut args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut input_files = Vec::new();
+	let mut check = false;
+
+	while let Some(arg) = args.next() {
+		match arg.to_str().unwrap() {
+			"-i" => {
+				if let Some(filename) = args.next() {
+					input_files.push(filename)
+				}
+				else {
+					println!("Missing value for input file");
+					return Ok(1);
+				}
+			},
+
+			"--check" => check = true,
+
+			arg => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	command_fmt(input_files, check)
+}
+
+fn command_decode(input_files: Vec<OsString>, type_name: &str, version: &str, data_file: OsString, format: ReportFormat) -> Result<i32, VError> {
+	let t = match parser::parse_type(type_name) {
+		Some(t) => t,
+		None => {
+			println!("Invalid type: {}", type_name);
+			return Ok(1);
+		},
+	};
+
+	let version = match version.parse::<num_bigint::BigUint>() {
+		Ok(version) => version,
+		Err(_) => {
+			println!("Invalid version: {}", version);
+			return Ok(1);
+		},
+	};
+
+	let model = load_files(input_files)?;
+
+	let path = PathBuf::from(data_file);
+	let data = std::fs::read(&path).map_err(|err| VError::FileReadError(path, err))?;
+	let value = dynamic::decode_value(&model, &t, &version, &data)?;
+
+	match format {
+		ReportFormat::Text => println!("{}", value),
+		ReportFormat::Json => println!("{}", json_value(&value)),
+	}
+
+	Ok(0)
+}
+
+// Enum values are written as an object with a single property named after the case.
+// Options are written as null or the value of the some case.
+// Maps are written as an array of [key, value] pairs because keys need not be strings.
+fn json_value(value: &dynamic::DynamicValue) -> serde_json::Value {
+	match value {
+		// Numbers keep every digit because serde_json uses arbitrary precision.
+		dynamic::DynamicValue::Integer(n) => serde_json::Value::Number(n.to_string().parse().unwrap()),
+		dynamic::DynamicValue::String(s) => serde_json::Value::from(s.as_str()),
+		dynamic::DynamicValue::Boolean(b) => serde_json::Value::Bool(*b),
+		// JSON has no representation for NaN or infinity.
+		dynamic::DynamicValue::Float(x) => serde_json::Number::from_f64(*x).map_or(serde_json::Value::Null, serde_json::Value::Number),
+		dynamic::DynamicValue::Sequence(values) => serde_json::Value::Array(values.iter().map(json_value).collect()),
+		dynamic::DynamicValue::Map(entries) =>
+			serde_json::Value::Array(entries.iter().map(|(key, value)| serde_json::Value::Array(vec!(json_value(key), json_value(value)))).collect()),
+		dynamic::DynamicValue::Option(Some(value)) => json_value(value),
+		dynamic::DynamicValue::Option(None) => serde_json::Value::Null,
+		dynamic::DynamicValue::Struct(fields) =>
+			serde_json::Value::Object(fields.iter().map(|(name, value)| (name.clone(), json_value(value))).collect()),
+		dynamic::DynamicValue::Enum(name, value) => {
+			let mut case = serde_json::Map::new();
+			case.insert(name.clone(), json_value(value));
+			serde_json::Value::Object(case)
+		},
+	}
+}
+
+fn parse_decode_args<Args>(mut args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut input_files = Vec::new();
+	let mut type_name = None;
+	let mut version = None;
+	let mut data_file = None;
+	let mut format = ReportFormat::Text;
+
+	while let Some(arg) = args.next() {
+		match arg.to_str().unwrap() {
+			"-i" => {
+				if let Some(filename) = args.next() {
+					input_files.push(filename)
+				}
+				else {
+					println!("Missing value for input file");
+					return Ok(1);
+				}
+			},
+
+			"--type" => {
+				match args.next().and_then(|value| value.into_string().ok()) {
+					Some(value) => type_name = Some(value),
+					None => {
+						println!("Missing value for type");
+						return Ok(1);
+					},
+				}
+			},
+
+			"--version" => {
+				match args.next().and_then(|value| value.into_string().ok()) {
+					Some(value) => version = Some(value),
+					None => {
+						println!("Missing value for version");
+						return Ok(1);
+					},
+				}
+			},
+
+			"--format" => {
+				match args.next().as_ref().and_then(|value| value.to_str()) {
+					Some("text") => format = ReportFormat::Text,
+					Some("json") => format = ReportFormat::Json,
+					Some(value) => {
+						println!("Unknown format: {}", value);
+						return Ok(1);
+					},
+					None => {
+						println!("Missing value for format");
+						return Ok(1);
+					},
+				}
+			},
+
+			arg if arg.starts_with('-') => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+
+			arg if data_file.is_none() => data_file = Some(OsString::from(arg)),
+
+			arg => {
+				println!("Unexpected argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	let (type_name, version, data_file) = match (type_name, version, data_file) {
+		(Some(type_name), Some(version), Some(data_file)) => (type_name, version, data_file),
+		(None, _, _) => {
+			println!("Missing type");
+			return Ok(1);
+		},
+		(_, None, _) => {
+			println!("Missing version");
+			return Ok(1);
+		},
+		(_, _, None) => {
+			println!("Missing data file");
+			return Ok(1);
+		},
+	};
+
+	command_decode(input_files, &type_name, &version, data_file, format)
+}
+
+fn command_encode(input_files: Vec<OsString>, type_name: &str, version: &str, value_file: OsString, format: ReportFormat, output_file: OsString) -> Result<i32, VError> {
+	let t = match parser::parse_type(type_name) {
+		Some(t) => t,
+		None => {
+			println!("Invalid type: {}", type_name);
+			return Ok(1);
+		},
+	};
+
+	let version = match version.parse::<num_bigint::BigUint>() {
+		Ok(version) => version,
+		Err(_) => {
+			println!("Invalid version: {}", version);
+			return Ok(1);
+		},
+	};
+
+	let model = load_files(input_files)?;
+
+	let path = PathBuf::from(value_file);
+	let content = std::fs::read_to_string(&path).map_err(|err| VError::FileReadError(path.clone(), err))?;
+
+	let value = match format {
+		ReportFormat::Text => parser::parse_constant_value(&source::SourceFile::new(path.to_string_lossy().into_owned(), content))?,
+		ReportFormat::Json => {
+			let json = match serde_json::from_str(&content) {
+				Ok(json) => json,
+				Err(err) => {
+					println!("Invalid JSON in {}: {}", path.display(), err);
+					return Ok(1);
+				},
+			};
+
+			match json_constant_value(&json) {
+				Ok(value) => value,
+				Err(message) => {
+					println!("Invalid value in {}: {}", path.display(), message);
+					return Ok(1);
+				},
+			}
+		},
+	};
+
+	let data = dynamic::encode_value(&model, &t, &version, &value)?;
+	std::fs::write(output_file, data)?;
+
+	Ok(0)
+}
+
+// Objects become records and null becomes the none case. The encoder accepts these for enums and options.
+fn json_constant_value(json: &serde_json::Value) -> Result<model::ConstantValue, String> {
+	Ok(match json {
+		serde_json::Value::Null => model::ConstantValue::Case(String::from("none"), Vec::new()),
+		serde_json::Value::Bool(b) => model::ConstantValue::Boolean(*b),
+		serde_json::Value::Number(n) => match (n.to_string().parse(), n.as_f64()) {
+			(Ok(n), _) => model::ConstantValue::Integer(n),
+			(Err(_), Some(x)) if x.is_finite() => model::ConstantValue::Float(x),
+			(Err(_), _) => return Err(format!("Number {} is out of range", n)),
+		},
+		serde_json::Value::String(s) => model::ConstantValue::String(s.clone()),
+		serde_json::Value::Array(values) => model::ConstantValue::Sequence(values.iter().map(json_constant_value).collect::<Result<Vec<_>, _>>()?),
+		serde_json::Value::Object(fields) => {
+			let mut record = model::ConstantValueRecordBuilder::new();
+			for (name, value) in fields {
+				record.add_field(name.clone(), json_constant_value(value)?, None).map_err(|err| err.to_string())?;
+			}
+			model::ConstantValue::Record(record.build())
+		},
+	})
+}
+
+fn parse_encode_args<Args>(mut args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut input_files = Vec::new();
+	let mut type_name = None;
+	let mut version = None;
+	let mut value_file = None;
+	let mut output_file = None;
+	let mut format = ReportFormat::Text;
+
+	while let Some(arg) = args.next() {
+		match arg.to_str().unwrap() {
+			"-i" => {
+				if let Some(filename) = args.next() {
+					input_files.push(filename)
+				}
+				else {
+					println!("Missing value for input file");
+					return Ok(1);
+				}
+			},
+
+			"-o" => {
+				if let Some(filename) = args.next() {
+					output_file = Some(filename)
+				}
+				else {
+					println!("Missing value for output file");
+					return Ok(1);
+				}
+			},
+
+			"--type" => {
+				match args.next().and_then(|value| value.into_string().ok()) {
+					Some(value) => type_name = Some(value),
+					None => {
+						println!("Missing value for type");
+						return Ok(1);
+					},
+				}
+			},
+
+			"--version" => {
+				match args.next().and_then(|value| value.into_string().ok()) {
+					Some(value) => version = Some(value),
+					None => {
+						println!("Missing value for version");
+						return Ok(1);
+					},
+				}
+			},
+
+			"--format" => {
+				match args.next().as_ref().and_then(|value| value.to_str()) {
+					Some("text") => format = ReportFormat::Text,
+					Some("json") => format = ReportFormat::Json,
+					Some(value) => {
+						println!("Unknown format: {}", value);
+						return Ok(1);
+					},
+					None => {
+						println!("Missing value for format");
+						return Ok(1);
+					},
+				}
+			},
+
+			arg if arg.starts_with('-') => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+
+			arg if value_file.is_none() => value_file = Some(OsString::from(arg)),
+
+			arg => {
+				println!("Unexpected argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	let (type_name, version, value_file, output_file) = match (type_name, version, value_file, output_file) {
+		(Some(type_name), Some(version), Some(value_file), Some(output_file)) => (type_name, version, value_file, output_file),
+		(None, _, _, _) => {
+			println!("Missing type");
+			return Ok(1);
+		},
+		(_, None, _, _) => {
+			println!("Missing version");
+			return Ok(1);
+		},
+		(_, _, None, _) => {
+			println!("Missing value file");
+			return Ok(1);
+		},
+		(_, _, _, None) => {
+			println!("Missing output file");
+			return Ok(1);
+		},
+	};
+
+	command_encode(input_files, &type_name, &version, value_file, format, output_file)
+}
+
+// Directories are searched recursively for source files.
+fn find_source_files(path: PathBuf, files: &mut Vec<PathBuf>) -> Result<(), VError> {
+	if !path.is_dir() {
+		files.push(path);
+		return Ok(());
+	}
+
+	let mut entries = std::fs::read_dir(&path)
+		.and_then(|entries| entries.map(|entry| entry.map(|entry| entry.path())).collect::<Result<Vec<_>, _>>())
+		.map_err(|err| VError::FileReadError(path, err))?;
+	entries.sort();
+
+	for entry in entries {
+		if entry.is_dir() || entry.extension().is_some_and(|ext| ext == "verilization") {
+			find_source_files(entry, files)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn command_compat(old_path: OsString, new_path: OsString) -> Result<i32, VError> {
+	let mut old_files = Vec::new();
+	find_source_files(PathBuf::from(old_path), &mut old_files)?;
+	let old_model = load_files(old_files)?;
+
+	let mut new_files = Vec::new();
+	find_source_files(PathBuf::from(new_path), &mut new_files)?;
+	let new_model = load_files(new_files)?;
+
+	let changes = compat::check_compatibility(&old_model, &new_model);
+	for change in &changes {
+		println!("{}", change);
+		println!("  --> {}", change.span());
+		print!("{}", change.span().snippet());
+		println!();
+	}
+
+	if changes.is_empty() {
+		println!("No incompatible changes found");
+		Ok(0)
+	}
+	else {
+		println!("Found {} incompatible changes", changes.len());
+		Ok(1)
+	}
+}
+
+fn parse_compat_args<Args>(args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut paths = Vec::new();
+
+	for arg in args {
+		match arg.to_str().unwrap() {
+			arg if arg.starts_with('-') => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+
+			_ if paths.len() < 2 => paths.push(arg),
+
+			arg => {
+				println!("Unexpected argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	let mut paths = paths.into_iter();
+	match (paths.next(), paths.next()) {
+		(Some(old_path), Some(new_path)) => command_compat(old_path, new_path),
+		(None, _) => {
+			println!("Missing old schema");
+			Ok(1)
+		},
+		(_, None) => {
+			println!("Missing new schema");
+			Ok(1)
+		},
+	}
+}
+
+fn command_lock(input_files: Vec<OsString>, path: PathBuf, update: bool) -> Result<i32, VError> {
 	let model = load_files(input_files)?;
+	check_lockfile(&model, &path)?;
+
+	let mut lock = match std::fs::read_to_string(&path) {
+		Ok(content) => lockfile::Lockfile::parse(&content)?,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => lockfile::Lockfile::new(),
+		Err(err) => return Err(VError::FileReadError(path, err)),
+	};
+
+	let unlocked_versions = lock.unlocked_versions(&model);
+
+	if update {
+		lock.update(&model).map_err(|errors| VError::from_errors(errors.into_iter().map(VError::from).collect()))?;
+		std::fs::write(&path, lock.to_string())?;
+		println!("Locked {} new versions", unlocked_versions.len());
+		Ok(0)
+	}
+	else {
+		for (name, version) in &unlocked_versions {
+			println!("Version {} of type {} is not locked", version, name);
+		}
+
+		Ok(if unlocked_versions.is_empty() { 0 } else { 1 })
+	}
+}
+
+fn parse_lock_args<Args>(mut args: Args) -> Result<i32, VError> where Args : Iterator<Item = OsString> {
+	let mut input_files = Vec::new();
+	let mut lockfile_path = PathBuf::from(lockfile::LOCKFILE_NAME);
+	let mut update = false;
+
+	while let Some(arg) = args.next() {
+		match arg.to_str().unwrap() {
+			"-i" => {
+				if let Some(filename) = args.next() {
+					input_files.push(filename)
+				}
+				else {
+					println!("Missing value for input file");
+					return Ok(1);
+				}
+			},
+
+			"--lockfile" => {
+				if let Some(path) = args.next() {
+					lockfile_path = PathBuf::from(path)
+				}
+				else {
+					println!("Missing value for lockfile");
+					return Ok(1);
+				}
+			},
+
+			"--update" => update = true,
+
+			arg => {
+				println!("Unknown argument: {}", arg);
+				return Ok(1);
+			},
+		}
+	}
+
+	command_lock(input_files, lockfile_path, update)
+}
+
+fn command_generate<Lang: Language>(input_files: Vec<OsString>, lockfile_path: PathBuf, options: Lang::Options) -> Result<i32, VError> {
+	let model = load_files(input_files)?;
+	check_lockfile(&model, &lockfile_path)?;
 
 	Lang::generate(&model, options, &mut FileOutputHandler {})?;
 	Ok(0)
@@ -33,6 +671,7 @@ impl <Args: Iterator<Item = OsString>> LanguageHandler for ParseGenerateCommand<
 
 	fn run<Lang: Language>(&mut self) -> Self::Result {
 		let mut input_files = Vec::new();
+		let mut lockfile_path = PathBuf::from(lockfile::LOCKFILE_NAME);
 		let mut lang_options = <<Lang::Options as LanguageOptions>::Builder as LanguageOptionsBuilder>::empty();
 	
 	
@@ -47,6 +686,16 @@ impl <Args: Iterator<Item = OsString>> LanguageHandler for ParseGenerateCommand<
 						return Ok(1);
 					}
 				},
+
+				"--lockfile" => {
+					if let Some(path) = self.args.next() {
+						lockfile_path = PathBuf::from(path)
+					}
+					else {
+						println!("Missing value for lockfile");
+						return Ok(1);
+					}
+				},
 	
 				arg => {
 					if let Some(option) = arg.strip_prefix("-o:") {
@@ -68,7 +717,7 @@ impl <Args: Iterator<Item = OsString>> LanguageHandler for ParseGenerateCommand<
 	
 		let lang_options = Lang::Options::build(lang_options)?;
 	
-		command_generate::<Lang>(input_files, lang_options)
+		command_generate::<Lang>(input_files, lockfile_path, lang_options)
 	}
 }
 
@@ -77,6 +726,12 @@ fn parse_args<Args, Registry: LanguageRegistry>(mut args: Args, registry: &Regis
 		match arg.to_str().unwrap() {
 			"version" | "--version" | "-v" => return command_version(),
 			"help" | "--help" | "-h" => return command_help(),
+			"check" => return parse_check_args(args),
+			"fmt" => return parse_fmt_args(args),
+			"decode" => return parse_decode_args(args),
+			"encode" => return parse_encode_args(args),
+			"compat" => return parse_compat_args(args),
+			"lock" => return parse_lock_args(args),
 			"generate" => {
 				let lang = match args.next() {
 					Some(lang) => lang,
@@ -109,6 +764,26 @@ fn parse_args<Args, Registry: LanguageRegistry>(mut args: Args, registry: &Regis
 }
 
 
+fn print_error_report(err: &VError) {
+	let errors = err.errors();
+	for err in errors {
+		match err.code() {
+			Some(code) => eprintln!("error[{}]: {}", code, err),
+			None => eprintln!("error: {}", err),
+		}
+
+		if let Some(span) = err.span() {
+			eprintln!("  --> {}", span);
+			eprint!("{}", span.snippet());
+		}
+		eprintln!();
+	}
+
+	if errors.len() > 1 {
+		eprintln!("error: found {} errors", errors.len());
+	}
+}
+
 pub fn main_impl<Registry: LanguageRegistry>(registry: &Registry) {
 	let mut args = env::args_os();
 	args.next();
@@ -116,7 +791,7 @@ pub fn main_impl<Registry: LanguageRegistry>(registry: &Registry) {
 	match parse_args(args, registry) {
 		Ok(exit_code) => std::process::exit(exit_code),
 		Err(err) => {
-			println!("{:?}", err);
+			print_error_report(&err);
 			std::process::exit(1)
 		},
 	}
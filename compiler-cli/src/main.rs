@@ -6,7 +6,8 @@ fn main() {
 	let registry = language_registry_new()
 		.add_language::<verilization_lang_typescript::TypeScriptLanguage>()
 		.add_language::<verilization_lang_java::JavaLanguage>()
-		.add_language::<verilization_lang_scala::ScalaLanguage>();
+		.add_language::<verilization_lang_scala::ScalaLanguage>()
+		.add_language::<verilization_lang_rust::RustLanguage>();
 
 	main_impl(&registry)
 }
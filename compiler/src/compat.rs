@@ -0,0 +1,280 @@
+//! Compares two revisions of a schema to find changes that make existing data unreadable.
+//!
+//! Serialized data does not describe its own structure, so every version of a type in the old schema must be encoded
+//! the same way by the new schema. Adding versions after the latest version and adding types are safe.
+
+use crate::model::{self, QualifiedName, Span};
+use num_bigint::{BigInt, BigUint};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A change between two revisions of a schema that breaks data written with the old revision.
+#[derive(Debug)]
+pub enum Incompatibility {
+	TypeRemoved(QualifiedName, Span),
+	TypeKindChanged(QualifiedName, Span),
+	TypeParametersChanged(QualifiedName, Span),
+	VersionRemoved(QualifiedName, BigUint, Span),
+	FieldAdded(QualifiedName, BigUint, String, Span),
+	FieldRemoved(QualifiedName, BigUint, String, Span),
+	FieldTypeChanged(QualifiedName, BigUint, String, Box<(model::Type, model::Type)>, Span),
+	FieldsReordered(QualifiedName, BigUint, Span),
+	CaseAdded(QualifiedName, BigUint, String, Span),
+	CaseRemoved(QualifiedName, BigUint, String, Span),
+	CaseTypeChanged(QualifiedName, BigUint, String, Box<(model::Type, model::Type)>, Span),
+	CasesReordered(QualifiedName, BigUint, Span),
+	ExternLiteralsChanged(QualifiedName, Span),
+}
+
+impl Incompatibility {
+	/// Gets the location of the change.
+	///
+	/// This is in the new schema, except for removed types which are located in the old schema.
+	pub fn span(&self) -> &Span {
+		match self {
+			Incompatibility::TypeRemoved(_, span) => span,
+			Incompatibility::TypeKindChanged(_, span) => span,
+			Incompatibility::TypeParametersChanged(_, span) => span,
+			Incompatibility::VersionRemoved(_, _, span) => span,
+			Incompatibility::FieldAdded(_, _, _, span) => span,
+			Incompatibility::FieldRemoved(_, _, _, span) => span,
+			Incompatibility::FieldTypeChanged(_, _, _, _, span) => span,
+			Incompatibility::FieldsReordered(_, _, span) => span,
+			Incompatibility::CaseAdded(_, _, _, span) => span,
+			Incompatibility::CaseRemoved(_, _, _, span) => span,
+			Incompatibility::CaseTypeChanged(_, _, _, _, span) => span,
+			Incompatibility::CasesReordered(_, _, span) => span,
+			Incompatibility::ExternLiteralsChanged(_, span) => span,
+		}
+	}
+}
+
+impl fmt::Display for Incompatibility {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Incompatibility::TypeRemoved(name, _) => write!(f, "Type {} was removed.", name),
+			Incompatibility::TypeKindChanged(name, _) => write!(f, "Type {} was changed to a different kind of type.", name),
+			Incompatibility::TypeParametersChanged(name, _) => write!(f, "Type parameters of {} were changed.", name),
+			Incompatibility::VersionRemoved(name, version, _) => write!(f, "Version {} of type {} was removed.", version, name),
+			Incompatibility::FieldAdded(name, version, field, _) => write!(f, "Field {} was added to version {} of type {}.", field, version, name),
+			Incompatibility::FieldRemoved(name, version, field, _) => write!(f, "Field {} was removed from version {} of type {}.", field, version, name),
+			Incompatibility::FieldTypeChanged(name, version, field, types, _) => {
+				let (old_type, new_type) = &**types;
+				write!(f, "Field {} of type {} version {} was changed from {} to {}.", field, name, version, old_type, new_type)
+			},
+			Incompatibility::FieldsReordered(name, version, _) => write!(f, "Fields of type {} version {} were reordered.", name, version),
+			Incompatibility::CaseAdded(name, version, case, _) => write!(f, "Case {} was added to version {} of type {}.", case, version, name),
+			Incompatibility::CaseRemoved(name, version, case, _) => write!(f, "Case {} was removed from version {} of type {}.", case, version, name),
+			Incompatibility::CaseTypeChanged(name, version, case, types, _) => {
+				let (old_type, new_type) = &**types;
+				write!(f, "Case {} of type {} version {} was changed from {} to {}.", case, name, version, old_type, new_type)
+			},
+			Incompatibility::CasesReordered(name, version, _) => write!(f, "Cases of type {} version {} were reordered.", name, version),
+			Incompatibility::ExternLiteralsChanged(name, _) => write!(f, "Literals of extern type {} were changed.", name),
+		}
+	}
+}
+
+// Resolves the names in a type so that types from both schemas can be compared.
+// Type parameters are left unqualified.
+fn resolve_type(scope: &model::Scope, t: &model::Type) -> model::Type {
+	let name = match scope.lookup(t.name.clone()) {
+		model::ScopeLookup::NamedType(name) => name,
+		model::ScopeLookup::TypeParameter(name) => QualifiedName { package: model::PackageName::new(), name },
+	};
+
+	model::Type {
+		name,
+		args: t.args.iter().map(|arg| resolve_type_arg(scope, arg)).collect(),
+	}
+}
+
+fn resolve_type_arg(scope: &model::Scope, arg: &model::TypeArgument) -> model::TypeArgument {
+	match arg {
+		model::TypeArgument::Type(t) => model::TypeArgument::Type(resolve_type(scope, t)),
+		model::TypeArgument::Integer(n) => model::TypeArgument::Integer(n.clone()),
+	}
+}
+
+fn resolve_fields<'a>(scope: &model::Scope, fields: &'a [(String, model::FieldInfo)]) -> Vec<(&'a String, model::Type)> {
+	fields.iter().map(|(name, field)| (name, resolve_type(scope, &field.field_type))).collect()
+}
+
+#[derive(PartialEq, Eq)]
+enum ResolvedLiteral {
+	Integer(model::ExternLiteralIntBound, Option<BigInt>, model::ExternLiteralIntBound, Option<BigInt>),
+	String,
+	Boolean,
+	Float,
+	Sequence(model::Type, Option<model::TypeArgument>),
+	Map(model::Type, model::Type),
+	Case(String, Vec<model::Type>),
+	Record(Vec<(String, model::Type)>),
+}
+
+fn resolve_literals(t: model::Named<model::ExternTypeDefinitionData>) -> Vec<ResolvedLiteral> {
+	let scope = t.scope();
+	t.literals().iter().map(|literal| match literal {
+		model::ExternLiteralSpecifier::Integer(lower_type, lower, upper_type, upper) => ResolvedLiteral::Integer(*lower_type, lower.clone(), *upper_type, upper.clone()),
+		model::ExternLiteralSpecifier::String => ResolvedLiteral::String,
+		model::ExternLiteralSpecifier::Boolean => ResolvedLiteral::Boolean,
+		model::ExternLiteralSpecifier::Float => ResolvedLiteral::Float,
+		model::ExternLiteralSpecifier::Sequence(element_type, length) => ResolvedLiteral::Sequence(resolve_type(&scope, element_type), length.as_ref().map(|length| resolve_type_arg(&scope, length))),
+		model::ExternLiteralSpecifier::Map(key_type, value_type) => ResolvedLiteral::Map(resolve_type(&scope, key_type), resolve_type(&scope, value_type)),
+		model::ExternLiteralSpecifier::Case(name, params) => ResolvedLiteral::Case(name.clone(), params.iter().map(|param| resolve_type(&scope, param)).collect()),
+		model::ExternLiteralSpecifier::Record(fields) => ResolvedLiteral::Record(
+			resolve_fields(&scope, fields).into_iter().map(|(name, field_type)| (name.clone(), field_type)).collect()
+		),
+	}).collect()
+}
+
+struct VersionComparison<'a> {
+	name: &'a QualifiedName,
+	version: &'a BigUint,
+	is_enum: bool,
+	span: &'a Span,
+}
+
+impl <'a> VersionComparison<'a> {
+	fn compare_fields(&self, changes: &mut Vec<Incompatibility>, old_fields: Vec<(&String, model::Type)>, new_fields: Vec<(&String, model::Type)>) {
+		let name = || self.name.clone();
+		let version = || self.version.clone();
+		let span = || self.span.clone();
+
+		for (field, old_type) in &old_fields {
+			match new_fields.iter().find(|(new_field, _)| new_field == field) {
+				Some((_, new_type)) if new_type != old_type => {
+					let types = Box::new((old_type.clone(), new_type.clone()));
+					changes.push(
+						if self.is_enum { Incompatibility::CaseTypeChanged(name(), version(), field.to_string(), types, span()) }
+						else { Incompatibility::FieldTypeChanged(name(), version(), field.to_string(), types, span()) }
+					);
+				},
+				Some(_) => (),
+				None => changes.push(
+					if self.is_enum { Incompatibility::CaseRemoved(name(), version(), field.to_string(), span()) }
+					else { Incompatibility::FieldRemoved(name(), version(), field.to_string(), span()) }
+				),
+			}
+		}
+
+		for (field, _) in &new_fields {
+			if !old_fields.iter().any(|(old_field, _)| old_field == field) {
+				changes.push(
+					if self.is_enum { Incompatibility::CaseAdded(name(), version(), field.to_string(), span()) }
+					else { Incompatibility::FieldAdded(name(), version(), field.to_string(), span()) }
+				);
+			}
+		}
+
+		// Fields that are in both versions must stay in the same order.
+		let old_order: Vec<&String> = old_fields.iter().map(|(field, _)| *field).filter(|field| new_fields.iter().any(|(new_field, _)| new_field == field)).collect();
+		let new_order: Vec<&String> = new_fields.iter().map(|(field, _)| *field).filter(|field| old_fields.iter().any(|(old_field, _)| old_field == field)).collect();
+		if old_order != new_order {
+			changes.push(
+				if self.is_enum { Incompatibility::CasesReordered(name(), version(), span()) }
+				else { Incompatibility::FieldsReordered(name(), version(), span()) }
+			);
+		}
+	}
+}
+
+fn compare_versioned_types(changes: &mut Vec<Incompatibility>, old_type: model::Named<model::VersionedTypeDefinitionData>, new_type: model::Named<model::VersionedTypeDefinitionData>, is_enum: bool) {
+	let old_scope = old_type.scope();
+	let new_scope = new_type.scope();
+	let new_versions: HashMap<BigUint, model::TypeVersionInfo<&model::TypeVersionDefinition>> = new_type.versions()
+		.map(|ver| (ver.version.clone(), ver))
+		.collect();
+
+	for old_ver in old_type.versions() {
+		let new_ver = match new_versions.get(&old_ver.version) {
+			Some(new_ver) => new_ver,
+			None => {
+				// Later versions are missing too, so only the first is reported.
+				changes.push(Incompatibility::VersionRemoved(old_type.name().clone(), old_ver.version, new_type.span().clone()));
+				break;
+			},
+		};
+
+		// Versions that are copied from an earlier version in both schemas were already compared.
+		if !old_ver.explicit_version && !new_ver.explicit_version {
+			continue;
+		}
+
+		let comparison = VersionComparison {
+			name: old_type.name(),
+			version: &old_ver.version,
+			is_enum,
+			span: new_ver.ver_type.span(),
+		};
+
+		comparison.compare_fields(
+			changes,
+			resolve_fields(&old_scope, old_ver.ver_type.fields()),
+			resolve_fields(&new_scope, new_ver.ver_type.fields()),
+		);
+	}
+}
+
+fn compare_types(changes: &mut Vec<Incompatibility>, old_type: model::NamedTypeDefinition, new_type: model::NamedTypeDefinition) {
+	let name = old_type.name();
+
+	match (old_type, new_type) {
+		// Interfaces are not serialized.
+		(model::NamedTypeDefinition::InterfaceType(_), _) => return,
+		(model::NamedTypeDefinition::StructType(_), model::NamedTypeDefinition::StructType(_)) |
+		(model::NamedTypeDefinition::EnumType(_), model::NamedTypeDefinition::EnumType(_)) |
+		(model::NamedTypeDefinition::ExternType(_), model::NamedTypeDefinition::ExternType(_)) => (),
+		_ => {
+			changes.push(Incompatibility::TypeKindChanged(name.clone(), new_type.span().clone()));
+			return;
+		},
+	}
+
+	// Field types cannot be compared when the parameters that they refer to have changed.
+	if old_type.type_params() != new_type.type_params() || old_type.type_params().iter().any(|param| old_type.is_const_param(param) != new_type.is_const_param(param)) {
+		changes.push(Incompatibility::TypeParametersChanged(name.clone(), new_type.span().clone()));
+		return;
+	}
+
+	match (old_type, new_type) {
+		(model::NamedTypeDefinition::StructType(old_type), model::NamedTypeDefinition::StructType(new_type)) =>
+			compare_versioned_types(changes, old_type, new_type, false),
+
+		(model::NamedTypeDefinition::EnumType(old_type), model::NamedTypeDefinition::EnumType(new_type)) =>
+			compare_versioned_types(changes, old_type, new_type, true),
+
+		(model::NamedTypeDefinition::ExternType(old_type), model::NamedTypeDefinition::ExternType(new_type)) => {
+			let old_literals = resolve_literals(old_type);
+			let new_literals = resolve_literals(new_type);
+			if old_literals.len() != new_literals.len() || !old_literals.iter().all(|literal| new_literals.contains(literal)) {
+				changes.push(Incompatibility::ExternLiteralsChanged(name.clone(), new_type.span().clone()));
+			}
+		},
+
+		_ => (),
+	}
+}
+
+/// Finds the changes between two revisions of a schema that would prevent reading data written with the old revision.
+///
+/// Changes are reported in order of the type names.
+pub fn check_compatibility(old: &model::Verilization, new: &model::Verilization) -> Vec<Incompatibility> {
+	let mut old_types: Vec<model::NamedTypeDefinition> = old.types().collect();
+	old_types.sort_by(|a, b| a.name().cmp(b.name()));
+
+	let mut changes = Vec::new();
+	for old_type in old_types {
+		match new.get_type(old_type.name()) {
+			Some(new_type) => compare_types(&mut changes, old_type, new_type),
+			None if matches!(old_type, model::NamedTypeDefinition::InterfaceType(_)) => (),
+			None => changes.push(Incompatibility::TypeRemoved(old_type.name().clone(), old_type.span().clone())),
+		}
+	}
+
+	changes
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,204 @@
+use crate::compat::check_compatibility;
+use crate::model;
+use crate::parser::parse_model;
+use crate::source::SourceFile;
+
+const EXTERNS: &str = "
+version 1;
+package std;
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+extern string {
+    literal {
+        string;
+    }
+}
+extern list<A> {
+    literal {
+        sequence A;
+    }
+}
+";
+
+fn load(source: &str) -> model::Verilization {
+    let mut model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(source))).unwrap();
+    model.merge(parse_model(&SourceFile::new(String::from("std.verilization"), String::from(EXTERNS))).unwrap()).unwrap();
+    model
+}
+
+fn run_compat_test(old: &str, new: &str) -> Vec<String> {
+    check_compatibility(&load(old), &load(new)).iter().map(|change| change.to_string()).collect()
+}
+
+#[test]
+fn safe_changes() {
+    let old = "
+version 2;
+package a;
+import std.nat;
+struct Point {
+    version 1 {
+        x: nat;
+        y: nat;
+    }
+}
+";
+
+    let new = "
+version 3;
+package a;
+import std.nat as Number;
+struct Point {
+    version 1 {
+        x: Number;
+        y: std.nat;
+    }
+    version 3 {
+        x: Number;
+        y: Number;
+        z: Number;
+    }
+}
+struct Line {
+    version 3 {
+        start: Point;
+        end: Point;
+    }
+}
+";
+
+    assert_eq!(run_compat_test(old, new), Vec::<String>::new());
+}
+
+#[test]
+fn field_changes() {
+    let old = "
+version 1;
+package a;
+struct Point {
+    version 1 {
+        x: std.nat;
+        y: std.nat;
+        z: std.nat;
+        name: std.string;
+    }
+}
+";
+
+    let new = "
+version 1;
+package a;
+struct Point {
+    version 1 {
+        y: std.nat;
+        x: std.nat;
+        name: std.list(std.string);
+        w: std.nat;
+    }
+}
+";
+
+    assert_eq!(run_compat_test(old, new), vec!(
+        "Field z was removed from version 1 of type a.Point.",
+        "Field name of type a.Point version 1 was changed from std.string to std.list(std.string).",
+        "Field w was added to version 1 of type a.Point.",
+        "Fields of type a.Point version 1 were reordered.",
+    ));
+}
+
+#[test]
+fn enum_cases() {
+    let old = "
+version 2;
+package a;
+enum Shape {
+    version 1 {
+        circle: std.nat;
+        square: std.nat;
+    }
+}
+";
+
+    let new = "
+version 2;
+package a;
+enum Shape {
+    version 1 {
+        circle: std.nat;
+        square: std.nat;
+    }
+    version 2 {
+        square: std.nat;
+        circle: std.nat;
+        triangle: std.nat;
+    }
+}
+";
+
+    assert_eq!(run_compat_test(old, new), vec!(
+        "Case triangle was added to version 2 of type a.Shape.",
+        "Cases of type a.Shape version 2 were reordered.",
+    ));
+}
+
+#[test]
+fn type_changes() {
+    let old = "
+version 2;
+package a;
+struct A {
+    version 1 {}
+}
+struct B<T> {
+    version 1 {
+        value: T;
+    }
+}
+struct C {
+    version 1 {}
+}
+struct D {
+    version 1 {}
+}
+extern E {
+    literal {
+        integer [0, 10];
+    }
+}
+interface F {
+    version 1 {}
+}
+";
+
+    let new = "
+version 2;
+package a;
+enum A {
+    version 1 {}
+}
+struct B<U> {
+    version 1 {
+        value: U;
+    }
+}
+final struct D {
+    version 1 {}
+}
+extern E {
+    literal {
+        integer [0, 100];
+    }
+}
+";
+
+    assert_eq!(run_compat_test(old, new), vec!(
+        "Type a.A was changed to a different kind of type.",
+        "Type parameters of a.B were changed.",
+        "Type a.C was removed.",
+        "Version 2 of type a.D was removed.",
+        "Literals of extern type a.E were changed.",
+    ));
+}
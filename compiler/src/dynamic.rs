@@ -0,0 +1,715 @@
+//! Interprets a schema to read and write values without generated code.
+
+use crate::model;
+use crate::lang::GeneratorError;
+use crate::lang::generator::{build_qualified_type, LangField, LangLiteral, LangType, VersionedTypeKind};
+use num_bigint::{BigUint, BigInt};
+use num_traits::ToPrimitive;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use verilization_runtime::{types, Codec, FormatReader, FormatWriter, InvalidDataError, VerilizationCodec, read_discriminator, write_discriminator};
+
+/// A value of a type that is only known at runtime.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DynamicValue {
+	Integer(BigInt),
+	String(String),
+	Boolean(bool),
+	Float(f64),
+	Sequence(Vec<DynamicValue>),
+	Map(Vec<(DynamicValue, DynamicValue)>),
+	Option(Option<Box<DynamicValue>>),
+	Struct(Vec<(String, DynamicValue)>),
+	Enum(String, Box<DynamicValue>),
+}
+
+impl DynamicValue {
+	fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+		match self {
+			DynamicValue::Integer(n) => write!(f, "{}", n),
+			DynamicValue::String(s) => {
+				write!(f, "\"")?;
+				for ch in s.chars() {
+					match ch {
+						'\\' => write!(f, "\\\\")?,
+						'"' => write!(f, "\\\"")?,
+						'\n' => write!(f, "\\n")?,
+						'\r' => write!(f, "\\r")?,
+						_ => write!(f, "{}", ch)?,
+					}
+				}
+				write!(f, "\"")
+			},
+			DynamicValue::Boolean(b) => write!(f, "{}", b),
+			DynamicValue::Float(x) => write!(f, "{:?}", x),
+			DynamicValue::Sequence(values) => {
+				write!(f, "[")?;
+				for (index, value) in values.iter().enumerate() {
+					if index > 0 {
+						write!(f, ", ")?;
+					}
+					value.fmt_indented(f, indent)?;
+				}
+				write!(f, "]")
+			},
+			DynamicValue::Map(entries) => {
+				write!(f, "[")?;
+				for (index, (key, value)) in entries.iter().enumerate() {
+					if index > 0 {
+						write!(f, ", ")?;
+					}
+					key.fmt_indented(f, indent)?;
+					write!(f, " => ")?;
+					value.fmt_indented(f, indent)?;
+				}
+				write!(f, "]")
+			},
+			DynamicValue::Option(Some(value)) => {
+				write!(f, "some(")?;
+				value.fmt_indented(f, indent)?;
+				write!(f, ")")
+			},
+			DynamicValue::Option(None) => write!(f, "none()"),
+			DynamicValue::Struct(fields) if fields.is_empty() => write!(f, "{{}}"),
+			DynamicValue::Struct(fields) => {
+				writeln!(f, "{{")?;
+				for (name, value) in fields {
+					write!(f, "{:width$}{} = ", "", name, width = (indent + 1) * 4)?;
+					value.fmt_indented(f, indent + 1)?;
+					writeln!(f, ";")?;
+				}
+				write!(f, "{:width$}}}", "", width = indent * 4)
+			},
+			DynamicValue::Enum(name, value) => {
+				write!(f, "{}(", name)?;
+				value.fmt_indented(f, indent)?;
+				write!(f, ")")
+			},
+		}
+	}
+}
+
+/// Values are written using the same syntax as constants.
+impl fmt::Display for DynamicValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.fmt_indented(f, 0)
+	}
+}
+
+/// An error that occurs while reading or writing a dynamic value.
+#[derive(Debug)]
+pub enum DynamicError {
+	UnexpectedEndOfData,
+	TrailingData(usize),
+	InvalidString,
+	InvalidBoolean(u8),
+	DuplicateKey,
+	InvalidData(String),
+	InvalidCase(model::QualifiedName, BigUint),
+	UnsupportedExternType(model::QualifiedName),
+	UnsupportedType(String),
+	MissingField(model::QualifiedName, BigUint, String),
+	IntegerOutOfRange(model::QualifiedName, BigInt),
+	ConstantNotDefined(model::QualifiedName, BigUint),
+	GeneratorError(GeneratorError),
+}
+
+impl DynamicError {
+	/// Gets a stable code that identifies the kind of error.
+	pub fn code(&self) -> &'static str {
+		match self {
+			DynamicError::UnexpectedEndOfData => "D0001",
+			DynamicError::TrailingData(_) => "D0002",
+			DynamicError::InvalidString => "D0003",
+			DynamicError::InvalidCase(..) => "D0004",
+			DynamicError::UnsupportedExternType(_) => "D0005",
+			DynamicError::UnsupportedType(_) => "D0006",
+			DynamicError::MissingField(..) => "D0007",
+			DynamicError::IntegerOutOfRange(..) => "D0008",
+			DynamicError::ConstantNotDefined(..) => "D0009",
+			DynamicError::InvalidBoolean(_) => "D0010",
+			DynamicError::DuplicateKey => "D0011",
+			DynamicError::InvalidData(_) => "D0012",
+			DynamicError::GeneratorError(err) => err.code(),
+		}
+	}
+}
+
+impl fmt::Display for DynamicError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			DynamicError::UnexpectedEndOfData => write!(f, "Unexpected end of data."),
+			DynamicError::TrailingData(count) => write!(f, "Found {} bytes after the end of the value.", count),
+			DynamicError::InvalidString => write!(f, "String is not valid UTF-8."),
+			DynamicError::InvalidBoolean(b) => write!(f, "Byte {} is not a valid boolean.", b),
+			DynamicError::DuplicateKey => write!(f, "A map or set contains the same key more than once."),
+			DynamicError::InvalidData(message) => write!(f, "{}.", message),
+			DynamicError::InvalidCase(name, version) => write!(f, "Invalid case for version {} of type {}.", version, name),
+			DynamicError::UnsupportedExternType(name) => write!(f, "Extern type {} is not supported.", name),
+			DynamicError::UnsupportedType(t) => write!(f, "Values of {} are not supported.", t),
+			DynamicError::MissingField(name, version, field_name) => write!(f, "Value for version {} of type {} is missing field {}.", version, name, field_name),
+			DynamicError::IntegerOutOfRange(name, n) => write!(f, "Integer {} is out of range for type {}.", n, name),
+			DynamicError::ConstantNotDefined(name, version) => write!(f, "Constant {} is not defined in version {}.", name, version),
+			DynamicError::GeneratorError(err) => err.fmt(f),
+		}
+	}
+}
+
+impl std::error::Error for DynamicError {}
+
+impl InvalidDataError for DynamicError {
+	fn invalid_data(message: &str) -> Self {
+		DynamicError::InvalidData(String::from(message))
+	}
+}
+
+impl From<GeneratorError> for DynamicError {
+	fn from(err: GeneratorError) -> Self {
+		DynamicError::GeneratorError(err)
+	}
+}
+
+
+struct SliceReader<'a> {
+	data: &'a [u8],
+}
+
+impl <'a> SliceReader<'a> {
+	fn take(&mut self, count: usize) -> Result<&'a [u8], DynamicError> {
+		if count > self.data.len() {
+			return Err(DynamicError::UnexpectedEndOfData);
+		}
+
+		let (bytes, rest) = self.data.split_at(count);
+		self.data = rest;
+		Ok(bytes)
+	}
+
+	fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DynamicError> {
+		let mut bytes = [0; N];
+		bytes.copy_from_slice(self.take(N)?);
+		Ok(bytes)
+	}
+
+	// Lengths are checked against the remaining data so that a corrupt length does not cause a huge allocation.
+	fn read_length(&mut self) -> Result<usize, DynamicError> {
+		BigUint::read_verilization(self)?
+			.to_usize()
+			.filter(|len| *len <= self.data.len())
+			.ok_or(DynamicError::UnexpectedEndOfData)
+	}
+}
+
+impl <'a> FormatReader for SliceReader<'a> {
+	type Error = DynamicError;
+
+	fn read_u8(&mut self) -> Result<u8, Self::Error> {
+		Ok(u8::from_le_bytes(self.take_array()?))
+	}
+
+	fn read_u16(&mut self) -> Result<u16, Self::Error> {
+		Ok(u16::from_le_bytes(self.take_array()?))
+	}
+
+	fn read_u32(&mut self) -> Result<u32, Self::Error> {
+		Ok(u32::from_le_bytes(self.take_array()?))
+	}
+
+	fn read_u64(&mut self) -> Result<u64, Self::Error> {
+		Ok(u64::from_le_bytes(self.take_array()?))
+	}
+
+	fn read_bytes(&mut self, count: usize) -> Result<Vec<u8>, Self::Error> {
+		Ok(self.take(count)?.to_vec())
+	}
+}
+
+
+fn read_integer<C: Codec>(reader: &mut SliceReader) -> Result<DynamicValue, DynamicError> where C::Value : Into<BigInt> {
+	Ok(DynamicValue::Integer(C::read_verilization(reader)?.into()))
+}
+
+fn read_value(reader: &mut SliceReader, t: &LangType) -> Result<DynamicValue, DynamicError> {
+	match t {
+		LangType::Versioned(VersionedTypeKind::Struct, _, _, _, fields) => {
+			let mut values = Vec::new();
+			for field in fields.build()? {
+				let value = read_value(reader, &field.field_type)?;
+				values.push((field.name.clone(), value));
+			}
+			Ok(DynamicValue::Struct(values))
+		},
+
+		LangType::Versioned(VersionedTypeKind::Enum, name, version, _, fields) => {
+			let mut fields = fields.build()?;
+			let index = read_discriminator(reader)?
+				.and_then(|tag| tag.to_usize())
+				.filter(|index| *index < fields.len())
+				.ok_or_else(|| DynamicError::InvalidCase((*name).clone(), version.clone()))?;
+
+			let field = fields.swap_remove(index);
+			let value = read_value(reader, &field.field_type)?;
+			Ok(DynamicValue::Enum(field.name.clone(), Box::new(value)))
+		},
+
+		LangType::Extern(name, args, _) if name.package.package.is_empty() => match (name.name.as_str(), args.as_slice()) {
+			("nat", []) => read_integer::<types::nat::Codec>(reader),
+			("int", []) => read_integer::<types::int::Codec>(reader),
+			("u8", []) => read_integer::<types::u8::Codec>(reader),
+			("i8", []) => read_integer::<types::i8::Codec>(reader),
+			("u16", []) => read_integer::<types::u16::Codec>(reader),
+			("i16", []) => read_integer::<types::i16::Codec>(reader),
+			("u32", []) => read_integer::<types::u32::Codec>(reader),
+			("i32", []) => read_integer::<types::i32::Codec>(reader),
+			("u64", []) => read_integer::<types::u64::Codec>(reader),
+			("i64", []) => read_integer::<types::i64::Codec>(reader),
+			("u24", []) => read_integer::<types::u24::Codec>(reader),
+			("i24", []) => read_integer::<types::i24::Codec>(reader),
+			("u128", []) => read_integer::<types::u128::Codec>(reader),
+			("i128", []) => read_integer::<types::i128::Codec>(reader),
+			("u16be", []) => read_integer::<types::u16be::Codec>(reader),
+			("i16be", []) => read_integer::<types::i16be::Codec>(reader),
+			("u24be", []) => read_integer::<types::u24be::Codec>(reader),
+			("i24be", []) => read_integer::<types::i24be::Codec>(reader),
+			("u32be", []) => read_integer::<types::u32be::Codec>(reader),
+			("i32be", []) => read_integer::<types::i32be::Codec>(reader),
+			("u64be", []) => read_integer::<types::u64be::Codec>(reader),
+			("i64be", []) => read_integer::<types::i64be::Codec>(reader),
+			("u128be", []) => read_integer::<types::u128be::Codec>(reader),
+			("i128be", []) => read_integer::<types::i128be::Codec>(reader),
+			("string", []) => {
+				let len = reader.read_length()?;
+				let bytes = reader.read_bytes(len)?;
+				let s = String::from_utf8(bytes).map_err(|_| DynamicError::InvalidString)?;
+				Ok(DynamicValue::String(s))
+			},
+			("bool", []) => match reader.read_u8()? {
+				0 => Ok(DynamicValue::Boolean(false)),
+				1 => Ok(DynamicValue::Boolean(true)),
+				b => Err(DynamicError::InvalidBoolean(b)),
+			},
+			("f32", []) => Ok(DynamicValue::Float(f64::from(reader.read_f32()?))),
+			("f64", []) => Ok(DynamicValue::Float(reader.read_f64()?)),
+			("bytes", []) => {
+				let len = reader.read_length()?;
+				let bytes = reader.read_bytes(len)?;
+				Ok(DynamicValue::Sequence(bytes.into_iter().map(|b| DynamicValue::Integer(BigInt::from(b))).collect()))
+			},
+			("list", [elem_type]) => {
+				let len = reader.read_length()?;
+				let mut values = Vec::new();
+				for _ in 0..len {
+					values.push(read_value(reader, elem_type)?);
+				}
+				Ok(DynamicValue::Sequence(values))
+			},
+			("array", [elem_type, LangType::Integer(len)]) => {
+				let len = len.to_usize().ok_or(DynamicError::UnexpectedEndOfData)?;
+				let mut values = Vec::new();
+				for _ in 0..len {
+					values.push(read_value(reader, elem_type)?);
+				}
+				Ok(DynamicValue::Sequence(values))
+			},
+			("map", [key_type, value_type]) => {
+				let len = reader.read_length()?;
+				let mut entries: Vec<(DynamicValue, DynamicValue)> = Vec::new();
+				for _ in 0..len {
+					let key = read_value(reader, key_type)?;
+					if entries.iter().any(|(prev_key, _)| *prev_key == key) {
+						return Err(DynamicError::DuplicateKey);
+					}
+
+					let value = read_value(reader, value_type)?;
+					entries.push((key, value));
+				}
+				Ok(DynamicValue::Map(entries))
+			},
+			("set", [elem_type]) => {
+				let len = reader.read_length()?;
+				let mut values = Vec::new();
+				for _ in 0..len {
+					let value = read_value(reader, elem_type)?;
+					if values.contains(&value) {
+						return Err(DynamicError::DuplicateKey);
+					}
+
+					values.push(value);
+				}
+				Ok(DynamicValue::Sequence(values))
+			},
+			("option", [elem_type]) => {
+				if reader.read_u8()? != 0 {
+					Ok(DynamicValue::Option(Some(Box::new(read_value(reader, elem_type)?))))
+				}
+				else {
+					Ok(DynamicValue::Option(None))
+				}
+			},
+			_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+		},
+
+		LangType::Extern(name, _, _) => Err(DynamicError::UnsupportedExternType((*name).clone())),
+		LangType::Interface(name, _, _, _) => Err(DynamicError::UnsupportedType(format!("interface {}", name))),
+		LangType::TypeParameter(name) => Err(DynamicError::GeneratorError(GeneratorError::CouldNotResolveTypeParameter(name.clone()))),
+		_ => Err(DynamicError::UnsupportedType(String::from("this type"))),
+	}
+}
+
+/// Decodes the binary representation of a value.
+///
+/// The type must use fully qualified names. All of the data must be used by the value.
+pub fn decode_value(model: &model::Verilization, t: &model::Type, version: &BigUint, data: &[u8]) -> Result<DynamicValue, DynamicError> {
+	let t = build_qualified_type(model, version, t)?;
+	let mut reader = SliceReader { data };
+	let value = read_value(&mut reader, &t)?;
+
+	if reader.data.is_empty() {
+		Ok(value)
+	}
+	else {
+		Err(DynamicError::TrailingData(reader.data.len()))
+	}
+}
+
+
+struct ValueWriter<'model> {
+	model: &'model model::Verilization,
+	version: &'model BigUint,
+	data: Vec<u8>,
+}
+
+impl <'model> FormatWriter for ValueWriter<'model> {
+	type Error = DynamicError;
+
+	fn write_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+		self.write_bytes(&value.to_le_bytes())
+	}
+
+	fn write_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+		self.write_bytes(&value.to_le_bytes())
+	}
+
+	fn write_u32(&mut self, value: u32) -> Result<(), Self::Error> {
+		self.write_bytes(&value.to_le_bytes())
+	}
+
+	fn write_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+		self.write_bytes(&value.to_le_bytes())
+	}
+
+	fn write_bytes(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+		self.data.extend_from_slice(data);
+		Ok(())
+	}
+}
+
+fn integer_in_range(n: &BigInt, lower_type: &model::ExternLiteralIntBound, lower: &Option<BigInt>, upper_type: &model::ExternLiteralIntBound, upper: &Option<BigInt>) -> bool {
+	let above_lower = match (lower_type, lower) {
+		(_, None) => true,
+		(model::ExternLiteralIntBound::Inclusive, Some(x)) => n >= x,
+		(model::ExternLiteralIntBound::Exclusive, Some(x)) => n > x,
+	};
+
+	let below_upper = match (upper_type, upper) {
+		(_, None) => true,
+		(model::ExternLiteralIntBound::Inclusive, Some(x)) => n <= x,
+		(model::ExternLiteralIntBound::Exclusive, Some(x)) => n < x,
+	};
+
+	above_lower && below_upper
+}
+
+impl <'model> ValueWriter<'model> {
+	fn write_integer<C: Codec>(&mut self, name: &model::QualifiedName, n: &BigInt) -> Result<(), DynamicError> where C::Value : for<'a> TryFrom<&'a BigInt> {
+		let value = C::Value::try_from(n).map_err(|_| DynamicError::IntegerOutOfRange(name.clone(), n.clone()))?;
+		C::write_verilization(&value, self)
+	}
+
+	fn write_value(&mut self, t: &LangType, value: &model::ConstantValue, scope: &model::Scope) -> Result<(), DynamicError> {
+		if let model::ConstantValue::Constant(constant_name) = value {
+			let constant_name = scope.lookup_constant(constant_name.clone());
+			let model = self.model;
+			let constant = model.get_constant(&constant_name)
+				.and_then(|constant| Some((constant.versioned(self.version)?, constant.scope())));
+
+			return match constant {
+				Some((ver_constant, constant_scope)) => self.write_value(t, ver_constant.value, &constant_scope),
+				None => Err(DynamicError::ConstantNotDefined(constant_name, self.version.clone())),
+			};
+		}
+
+		match t {
+			LangType::Versioned(kind, name, version, _, fields) => {
+				let fields = fields.build()?;
+				match (kind, value) {
+					(VersionedTypeKind::Struct, model::ConstantValue::Record(record)) => {
+						let field_values = record.field_values();
+						if let Some(field_name) = field_values.keys().filter(|field_name| !fields.iter().any(|field| field.name == *field_name)).min() {
+							return Err(DynamicError::GeneratorError(GeneratorError::CouldNotFindRecordField((*name).clone(), Some(version.clone()), field_name.clone())));
+						}
+
+						for field in fields {
+							let field_value = field_values.get(field.name)
+								.ok_or_else(|| DynamicError::MissingField((*name).clone(), version.clone(), field.name.clone()))?;
+							self.write_value(&field.field_type, field_value, scope)?;
+						}
+						Ok(())
+					},
+
+					(VersionedTypeKind::Enum, model::ConstantValue::Case(case_name, args)) => {
+						match args.as_slice() {
+							[arg] => self.write_case(name, version, fields, case_name, arg, scope),
+							_ => Err(DynamicError::GeneratorError(GeneratorError::IncorrectCaseArity((*name).clone(), case_name.clone()))),
+						}
+					},
+
+					// Allows enum values to be written as a record with a single field, which is how JSON represents them.
+					(VersionedTypeKind::Enum, model::ConstantValue::Record(record)) if record.field_values().len() == 1 => {
+						let (case_name, arg) = record.field_values().iter().next().unwrap();
+						self.write_case(name, version, fields, case_name, arg, scope)
+					},
+
+					(VersionedTypeKind::Struct, model::ConstantValue::Case(case_name, _)) =>
+						Err(DynamicError::GeneratorError(GeneratorError::TypeDoesNotHaveCase((*name).clone(), Some(version.clone()), case_name.clone()))),
+
+					(VersionedTypeKind::Enum, model::ConstantValue::Record(_)) => Err(DynamicError::GeneratorError(GeneratorError::RecordLiteralNotForStruct)),
+					(_, value) => Err(Self::invalid_literal(name, value)),
+				}
+			},
+
+			LangType::Extern(name, args, literals) if name.package.package.is_empty() => match (name.name.as_str(), args.as_slice(), value) {
+				("option", [elem_type], model::ConstantValue::Case(case_name, case_args)) if case_name == "some" || case_name == "none" => {
+					match (case_name.as_str(), case_args.as_slice()) {
+						("some", [arg]) => {
+							self.write_u8(1)?;
+							self.write_value(elem_type, arg, scope)
+						},
+						("none", []) => self.write_u8(0),
+						_ => Err(DynamicError::GeneratorError(GeneratorError::IncorrectCaseArity((*name).clone(), case_name.clone()))),
+					}
+				},
+
+				// Any other value is the value of a some case, since JSON does not wrap it.
+				("option", [elem_type], value) => {
+					self.write_u8(1)?;
+					self.write_value(elem_type, value, scope)
+				},
+
+				("map", [key_type, value_type], model::ConstantValue::Map(entries)) =>
+					self.write_map(key_type, value_type, entries.iter().map(|(key, value)| (key, value)).collect(), scope),
+
+				// JSON has no map syntax, so entries may also be written as a sequence of pairs.
+				("map", [key_type, value_type], model::ConstantValue::Sequence(values)) => {
+					let entries = values.iter()
+						.map(|entry| match entry {
+							model::ConstantValue::Sequence(pair) if pair.len() == 2 => Ok((&pair[0], &pair[1])),
+							_ => Err(Self::invalid_literal(name, value)),
+						})
+						.collect::<Result<Vec<_>, _>>()?;
+					self.write_map(key_type, value_type, entries, scope)
+				},
+
+				("set", [elem_type], model::ConstantValue::Sequence(values)) => {
+					let mut seen = HashSet::new();
+					BigUint::from(values.len()).write_verilization(self)?;
+					for elem in values {
+						self.write_key(elem_type, elem, scope, &mut seen)?;
+					}
+					Ok(())
+				},
+
+				(type_name, args, value) => {
+					let literals = literals.clone().build()?;
+					match value {
+						model::ConstantValue::Integer(n) => {
+							let is_integer_type = literals.iter().any(|literal| matches!(literal, LangLiteral::Integer(..)));
+							if !is_integer_type {
+								return Err(Self::invalid_literal(name, value));
+							}
+
+							let in_range = literals.iter().any(|literal| match literal {
+								LangLiteral::Integer(lower_type, lower, upper_type, upper) => integer_in_range(n, lower_type, lower, upper_type, upper),
+								_ => false,
+							});
+							if !in_range {
+								return Err(DynamicError::IntegerOutOfRange((*name).clone(), n.clone()));
+							}
+
+							match (type_name, args) {
+								("nat", []) => self.write_integer::<types::nat::Codec>(name, n),
+								("int", []) => n.write_verilization(self),
+								("u8", []) => self.write_integer::<types::u8::Codec>(name, n),
+								("i8", []) => self.write_integer::<types::i8::Codec>(name, n),
+								("u16", []) => self.write_integer::<types::u16::Codec>(name, n),
+								("i16", []) => self.write_integer::<types::i16::Codec>(name, n),
+								("u32", []) => self.write_integer::<types::u32::Codec>(name, n),
+								("i32", []) => self.write_integer::<types::i32::Codec>(name, n),
+								("u64", []) => self.write_integer::<types::u64::Codec>(name, n),
+								("i64", []) => self.write_integer::<types::i64::Codec>(name, n),
+								("u24", []) => self.write_integer::<types::u24::Codec>(name, n),
+								("i24", []) => self.write_integer::<types::i24::Codec>(name, n),
+								("u128", []) => self.write_integer::<types::u128::Codec>(name, n),
+								("i128", []) => self.write_integer::<types::i128::Codec>(name, n),
+								("u16be", []) => self.write_integer::<types::u16be::Codec>(name, n),
+								("i16be", []) => self.write_integer::<types::i16be::Codec>(name, n),
+								("u24be", []) => self.write_integer::<types::u24be::Codec>(name, n),
+								("i24be", []) => self.write_integer::<types::i24be::Codec>(name, n),
+								("u32be", []) => self.write_integer::<types::u32be::Codec>(name, n),
+								("i32be", []) => self.write_integer::<types::i32be::Codec>(name, n),
+								("u64be", []) => self.write_integer::<types::u64be::Codec>(name, n),
+								("i64be", []) => self.write_integer::<types::i64be::Codec>(name, n),
+								("u128be", []) => self.write_integer::<types::u128be::Codec>(name, n),
+								("i128be", []) => self.write_integer::<types::i128be::Codec>(name, n),
+								_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+							}
+						},
+
+						model::ConstantValue::String(s) => {
+							if !literals.iter().any(|literal| matches!(literal, LangLiteral::String)) {
+								return Err(Self::invalid_literal(name, value));
+							}
+
+							match (type_name, args) {
+								("string", []) => s.write_verilization(self),
+								_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+							}
+						},
+
+						model::ConstantValue::Boolean(b) => {
+							if !literals.iter().any(|literal| matches!(literal, LangLiteral::Boolean)) {
+								return Err(Self::invalid_literal(name, value));
+							}
+
+							match (type_name, args) {
+								("bool", []) => self.write_u8(u8::from(*b)),
+								_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+							}
+						},
+
+						model::ConstantValue::Float(x) => {
+							if !literals.iter().any(|literal| matches!(literal, LangLiteral::Float)) {
+								return Err(Self::invalid_literal(name, value));
+							}
+
+							match (type_name, args) {
+								("f32", []) => self.write_f32(*x as f32),
+								("f64", []) => self.write_f64(*x),
+								_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+							}
+						},
+
+						model::ConstantValue::Sequence(values) => {
+							let (elem_type, length) = literals.iter().find_map(|literal| match literal {
+								LangLiteral::Sequence(elem_type, length) => Some((elem_type, length)),
+								_ => None,
+							}).ok_or_else(|| Self::invalid_literal(name, value))?;
+
+							if length.as_ref().is_some_and(|length| *length != BigUint::from(values.len())) {
+								return Err(DynamicError::GeneratorError(GeneratorError::SequenceLengthMismatch((*name).clone())));
+							}
+
+							match (type_name, args) {
+								("list", [_]) | ("bytes", []) => {
+									BigUint::from(values.len()).write_verilization(self)?;
+									for elem in values {
+										self.write_value(elem_type, elem, scope)?;
+									}
+									Ok(())
+								},
+								// The length is known from the type, so only the elements are written.
+								("array", [_, LangType::Integer(_)]) => {
+									for elem in values {
+										self.write_value(elem_type, elem, scope)?;
+									}
+									Ok(())
+								},
+								_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+							}
+						},
+
+						model::ConstantValue::Case(case_name, _) if !literals.iter().any(|literal| matches!(literal, LangLiteral::Case(name2, _) if name2 == case_name)) =>
+							Err(Self::invalid_literal(name, value)),
+
+						model::ConstantValue::Record(_) if !literals.iter().any(|literal| matches!(literal, LangLiteral::Record(_))) =>
+							Err(Self::invalid_literal(name, value)),
+
+						model::ConstantValue::Map(_) if !literals.iter().any(|literal| matches!(literal, LangLiteral::Map(..))) =>
+							Err(Self::invalid_literal(name, value)),
+
+						_ => Err(DynamicError::UnsupportedExternType((*name).clone())),
+					}
+				},
+			},
+
+			LangType::Extern(name, _, _) => Err(DynamicError::UnsupportedExternType((*name).clone())),
+			LangType::Interface(name, _, _, _) => Err(DynamicError::UnsupportedType(format!("interface {}", name))),
+			LangType::TypeParameter(name) => Err(DynamicError::GeneratorError(GeneratorError::CouldNotResolveTypeParameter(name.clone()))),
+			_ => Err(DynamicError::UnsupportedType(String::from("this type"))),
+		}
+	}
+
+	fn write_map(&mut self, key_type: &LangType, value_type: &LangType, entries: Vec<(&model::ConstantValue, &model::ConstantValue)>, scope: &model::Scope) -> Result<(), DynamicError> {
+		let mut seen = HashSet::new();
+		BigUint::from(entries.len()).write_verilization(self)?;
+		for (key, value) in entries {
+			self.write_key(key_type, key, scope, &mut seen)?;
+			self.write_value(value_type, value, scope)?;
+		}
+		Ok(())
+	}
+
+	// Keys are compared using their encoded form.
+	fn write_key(&mut self, t: &LangType, key: &model::ConstantValue, scope: &model::Scope, seen: &mut HashSet<Vec<u8>>) -> Result<(), DynamicError> {
+		let start = self.data.len();
+		self.write_value(t, key, scope)?;
+		if !seen.insert(self.data[start..].to_vec()) {
+			return Err(DynamicError::DuplicateKey);
+		}
+		Ok(())
+	}
+
+	fn write_case(&mut self, name: &model::QualifiedName, version: &BigUint, fields: Vec<LangField>, case_name: &str, arg: &model::ConstantValue, scope: &model::Scope) -> Result<(), DynamicError> {
+		let (index, field) = fields.iter().enumerate().find(|(_, field)| field.name == case_name)
+			.ok_or_else(|| DynamicError::GeneratorError(GeneratorError::TypeDoesNotHaveCase(name.clone(), Some(version.clone()), case_name.to_string())))?;
+
+		write_discriminator(self, index as u64)?;
+		self.write_value(&field.field_type, arg, scope)
+	}
+
+	// Gets the error for a kind of literal that the type does not have.
+	fn invalid_literal(name: &model::QualifiedName, value: &model::ConstantValue) -> DynamicError {
+		DynamicError::GeneratorError(match value {
+			model::ConstantValue::Integer(_) => GeneratorError::InvalidTypeForIntValue,
+			model::ConstantValue::String(_) => GeneratorError::InvalidTypeForString,
+			model::ConstantValue::Boolean(_) => GeneratorError::InvalidTypeForBoolean,
+			model::ConstantValue::Float(_) => GeneratorError::InvalidTypeForFloat,
+			model::ConstantValue::Sequence(_) => GeneratorError::TypeCannotBeSequence(name.clone()),
+			model::ConstantValue::Map(_) => GeneratorError::InvalidTypeForMap,
+			model::ConstantValue::Case(case_name, _) => GeneratorError::TypeDoesNotHaveCase(name.clone(), None, case_name.clone()),
+			model::ConstantValue::Record(_) => GeneratorError::ExternTypeDoesNotHaveRecordLiteral(name.clone()),
+			model::ConstantValue::Constant(_) => GeneratorError::TypeMismatch,
+		})
+	}
+}
+
+/// Encodes a value into its binary representation.
+///
+/// The value is checked against the type in the same way as constants.
+/// To accept JSON, an enum value may also be a record with a single field and an option value may omit `some`.
+/// Names of constants in the value must be fully qualified.
+pub fn encode_value(model: &model::Verilization, t: &model::Type, version: &BigUint, value: &model::ConstantValue) -> Result<Vec<u8>, DynamicError> {
+	let lang_type = build_qualified_type(model, version, t)?;
+	let mut writer = ValueWriter {
+		model,
+		version,
+		data: Vec::new(),
+	};
+	writer.write_value(&lang_type, value, &model::Scope::empty(model))?;
+	Ok(writer.data)
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,296 @@
+use crate::dynamic::{decode_value, encode_value, DynamicError, DynamicValue, SliceReader};
+use crate::model;
+use crate::lang::GeneratorError;
+use crate::parser::{parse_model, parse_type, parse_constant_value};
+use crate::source::SourceFile;
+use num_bigint::{BigUint, BigInt};
+use verilization_runtime::{types, Codec, VerilizationCodec};
+
+const SCHEMA: &str = "
+version 2;
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+extern int {
+    literal {
+        integer (,);
+    }
+}
+extern u8 {
+    literal {
+        integer [0, 255];
+    }
+}
+extern i16 {
+    literal {
+        integer [-32768, 32767];
+    }
+}
+extern u32 {
+    literal {
+        integer [0, 4294967295];
+    }
+}
+extern u16be {
+    literal {
+        integer [0, 65535];
+    }
+}
+extern i24 {
+    literal {
+        integer [-8388608, 8388607];
+    }
+}
+extern i24be {
+    literal {
+        integer [-8388608, 8388607];
+    }
+}
+extern u128be {
+    literal {
+        integer [0, 340282366920938463463374607431768211455];
+    }
+}
+extern string {
+    literal {
+        string;
+    }
+}
+extern bool {
+    literal {
+        boolean;
+    }
+}
+extern f32 {
+    literal {
+        float;
+    }
+}
+extern f64 {
+    literal {
+        float;
+    }
+}
+extern bytes {
+    literal {
+        sequence u8;
+    }
+}
+extern list<A> {
+    literal {
+        sequence A;
+    }
+}
+extern option<A> {
+    literal {
+        case some(A);
+        case none();
+    }
+}
+extern map<K, V> {
+    literal {
+        map K V;
+    }
+}
+extern set<A> {
+    literal {
+        sequence A;
+    }
+}
+extern array<A, const N> {
+    literal {
+        sequence A [N];
+    }
+}
+extern other {}
+
+struct Point {
+    version 1 {
+        x: u32;
+        y: i16;
+    }
+}
+
+enum Shape {
+    version 1 {
+        point: Point;
+        name: string;
+    }
+    version 2 {
+        point: Point;
+        name: string;
+        tags: list(option(int));
+    }
+}
+
+struct Wrapper<T> {
+    version 1 {
+        value: T;
+        size: nat;
+    }
+}
+
+struct Bad {
+    version 1 {
+        value: other;
+    }
+}
+
+const origin: Point {
+    version 1 = { x = 0; y = 0; };
+}
+";
+
+fn run_decode(type_expr: &str, version: u32, data: &[u8]) -> Result<DynamicValue, DynamicError> {
+    let model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(SCHEMA))).unwrap();
+    let t = parse_type(type_expr).unwrap();
+    decode_value(&model, &t, &BigUint::from(version), data)
+}
+
+fn run_encode(type_expr: &str, version: u32, value: &str) -> Result<Vec<u8>, DynamicError> {
+    let model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(SCHEMA))).unwrap();
+    let t = parse_type(type_expr).unwrap();
+    let value = parse_constant_value(&SourceFile::new(String::from("value.txt"), String::from(value))).unwrap();
+    encode_value(&model, &t, &BigUint::from(version), &value)
+}
+
+fn int(n: i64) -> DynamicValue {
+    DynamicValue::Integer(BigInt::from(n))
+}
+
+#[test]
+fn decode_struct() {
+    let value = run_decode("Point", 1, &[0x04, 0x03, 0x02, 0x01, 0xFE, 0xFF]).unwrap();
+    assert_eq!(value, DynamicValue::Struct(vec!(
+        (String::from("x"), int(0x01020304)),
+        (String::from("y"), int(-2)),
+    )));
+    assert_eq!(run_decode("bool", 1, &[0x01]).unwrap(), DynamicValue::Boolean(true));
+    assert_eq!(run_decode("f32", 1, &[0x00, 0x00, 0xC0, 0x3F]).unwrap(), DynamicValue::Float(1.5));
+    assert_eq!(run_decode("bytes", 1, &[0x02, 0x00, 0xFF]).unwrap(), DynamicValue::Sequence(vec!(int(0), int(255))));
+    assert_eq!(run_decode("map(u8, bool)", 1, &[0x02, 0x05, 0x01, 0x06, 0x00]).unwrap(), DynamicValue::Map(vec!(
+        (int(5), DynamicValue::Boolean(true)),
+        (int(6), DynamicValue::Boolean(false)),
+    )));
+    assert_eq!(run_decode("set(u8)", 1, &[0x02, 0x05, 0x06]).unwrap(), DynamicValue::Sequence(vec!(int(5), int(6))));
+    assert_eq!(run_decode("array(u8, 3)", 1, &[0x05, 0x06, 0x07]).unwrap(), DynamicValue::Sequence(vec!(int(5), int(6), int(7))));
+    assert_eq!(run_decode("u16be", 1, &[0x01, 0x02]).unwrap(), int(0x0102));
+    assert_eq!(run_decode("i24", 1, &[0xFE, 0xFF, 0xFF]).unwrap(), int(-2));
+    assert_eq!(run_decode("i24be", 1, &[0xFF, 0xFF, 0xFE]).unwrap(), int(-2));
+    assert_eq!(run_decode("u128be", 1, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05]).unwrap(), DynamicValue::Integer((BigInt::from(1) << 64) + 5));
+}
+
+#[test]
+fn decode_enum_versions() {
+    let value = run_decode("Shape", 1, &[0x01, 0x02, b'h', b'i']).unwrap();
+    assert_eq!(value, DynamicValue::Enum(String::from("name"), Box::new(DynamicValue::String(String::from("hi")))));
+
+    let value = run_decode("Shape", 2, &[0x02, 0x02, 0x01, 0x41, 0x00]).unwrap();
+    assert_eq!(value, DynamicValue::Enum(String::from("tags"), Box::new(DynamicValue::Sequence(vec!(
+        DynamicValue::Option(Some(Box::new(int(-2)))),
+        DynamicValue::Option(None),
+    )))));
+
+    assert!(matches!(run_decode("Shape", 1, &[0x02]), Err(DynamicError::InvalidCase(..))));
+}
+
+#[test]
+fn decode_type_arguments() {
+    let value = run_decode("Wrapper(u8)", 1, &[0x07, 0x80, 0x01]).unwrap();
+    assert_eq!(value, DynamicValue::Struct(vec!(
+        (String::from("value"), int(7)),
+        (String::from("size"), int(128)),
+    )));
+
+    assert_eq!(value.to_string(), "{\n    value = 7;\n    size = 128;\n}");
+}
+
+#[test]
+fn decode_errors() {
+    assert!(matches!(run_decode("Point", 1, &[0x00, 0x00, 0x00]), Err(DynamicError::UnexpectedEndOfData)));
+    assert!(matches!(run_decode("u8", 1, &[0x00, 0x00]), Err(DynamicError::TrailingData(1))));
+    assert!(matches!(run_decode("string", 1, &[0x7F, b'a']), Err(DynamicError::UnexpectedEndOfData)));
+    assert!(matches!(run_decode("string", 1, &[0x01, 0xFF]), Err(DynamicError::InvalidString)));
+    assert!(matches!(run_decode("bool", 1, &[0x02]), Err(DynamicError::InvalidBoolean(2))));
+    assert!(matches!(run_decode("map(u8, bool)", 1, &[0x02, 0x05, 0x01, 0x05, 0x00]), Err(DynamicError::DuplicateKey)));
+    assert!(matches!(run_decode("set(u8)", 1, &[0x02, 0x05, 0x05]), Err(DynamicError::DuplicateKey)));
+    assert!(matches!(run_decode("array(u8, 3)", 1, &[0x05, 0x06]), Err(DynamicError::UnexpectedEndOfData)));
+    assert!(matches!(run_decode("Bad", 1, &[]), Err(DynamicError::UnsupportedExternType(model::QualifiedName { .. }))));
+    assert!(matches!(run_decode("Missing", 1, &[]), Err(DynamicError::GeneratorError(_))));
+}
+
+#[test]
+fn runtime_codec_errors() {
+    let mut reader = SliceReader { data: &[0x02] };
+    assert!(matches!(bool::read_verilization(&mut reader), Err(DynamicError::InvalidData(message)) if message == "Invalid value for bool: 2"));
+
+    let mut reader = SliceReader { data: &[0x02, 0x05, 0x01, 0x05, 0x00] };
+    assert!(matches!(types::map::Codec::<types::u8::Codec, types::bool::Codec>::read_verilization(&mut reader), Err(DynamicError::InvalidData(message)) if message == "Duplicate key in map"));
+
+    let mut reader = SliceReader { data: &[0x02, 0x05, 0x05] };
+    assert!(matches!(types::set::Codec::<types::u8::Codec>::read_verilization(&mut reader), Err(DynamicError::InvalidData(message)) if message == "Duplicate element in set"));
+
+    let mut reader = SliceReader { data: &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F] };
+    assert!(matches!(types::map::Codec::<types::u8::Codec, types::bool::Codec>::read_verilization(&mut reader), Err(DynamicError::InvalidData(message)) if message == "Length is too large"));
+
+    let mut reader = SliceReader { data: &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F] };
+    assert!(matches!(types::set::Codec::<types::u8::Codec>::read_verilization(&mut reader), Err(DynamicError::UnexpectedEndOfData)));
+}
+
+#[test]
+fn encode_values() {
+    assert_eq!(run_encode("Point", 1, "{ y = -2; x = 16909060; }").unwrap(), vec!(0x04, 0x03, 0x02, 0x01, 0xFE, 0xFF));
+    assert_eq!(run_encode("Shape", 2, "tags([some(-2), none(), 5])").unwrap(), vec!(0x02, 0x03, 0x01, 0x41, 0x00, 0x01, 0x05));
+    assert_eq!(run_encode("Shape", 1, "{ point = origin; }").unwrap(), vec!(0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00));
+    assert_eq!(run_encode("Wrapper(string)", 1, "{ value = \"a\\n\"; size = 128; }").unwrap(), vec!(0x02, b'a', b'\n', 0x80, 0x01));
+    assert_eq!(run_encode("list(bool)", 1, "[true, false]").unwrap(), vec!(0x02, 0x01, 0x00));
+    assert_eq!(run_encode("f64", 1, "-2.0").unwrap(), vec!(0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0));
+    assert_eq!(run_encode("bytes", 1, "[1, 2, 255]").unwrap(), vec!(0x03, 0x01, 0x02, 0xFF));
+    assert_eq!(run_encode("map(string, u8)", 1, "[\"a\" => 1, \"b\" => 2]").unwrap(), vec!(0x02, 0x01, b'a', 0x01, 0x01, b'b', 0x02));
+    assert_eq!(run_encode("map(string, u8)", 1, "[]").unwrap(), vec!(0x00));
+    assert_eq!(run_encode("set(u8)", 1, "[3, 4]").unwrap(), vec!(0x02, 0x03, 0x04));
+    assert_eq!(run_encode("array(u8, 2)", 1, "[3, 4]").unwrap(), vec!(0x03, 0x04));
+    assert_eq!(run_encode("u16be", 1, "258").unwrap(), vec!(0x01, 0x02));
+    assert_eq!(run_encode("i24", 1, "-2").unwrap(), vec!(0xFE, 0xFF, 0xFF));
+    assert_eq!(run_encode("i24be", 1, "-2").unwrap(), vec!(0xFF, 0xFF, 0xFE));
+    assert_eq!(run_encode("u128be", 1, "340282366920938463463374607431768211455").unwrap(), vec!(0xFF; 16));
+}
+
+#[test]
+fn encode_round_trip() {
+    let data = run_encode("Shape", 2, "tags([some(1000000), none()])").unwrap();
+    let value = run_decode("Shape", 2, &data).unwrap();
+    assert_eq!(run_encode("Shape", 2, &value.to_string()).unwrap(), data);
+}
+
+#[test]
+fn int_round_trip() {
+    assert_eq!(run_decode("int", 1, &[0x40]).unwrap(), int(-1));
+    assert_eq!(run_decode("int", 1, &[0xFF, 0x40]).unwrap(), int(-128));
+
+    for n in [0, 1, -1, 63, -63, 64, -64, -65, 1000000, -1000000, i64::MIN] {
+        let data = run_encode("int", 1, &n.to_string()).unwrap();
+        assert_eq!(run_decode("int", 1, &data).unwrap(), int(n));
+    }
+}
+
+#[test]
+fn encode_errors() {
+    assert!(matches!(run_encode("Point", 1, "{ x = 1; }"), Err(DynamicError::MissingField(_, _, field)) if field == "y"));
+    assert!(matches!(run_encode("Point", 1, "{ x = 1; y = 2; z = 3; }"), Err(DynamicError::GeneratorError(GeneratorError::CouldNotFindRecordField(..)))));
+    assert!(matches!(run_encode("Point", 1, "{ x = -1; y = 2; }"), Err(DynamicError::IntegerOutOfRange(..))));
+    assert!(matches!(run_encode("Point", 1, "{ x = \"a\"; y = 2; }"), Err(DynamicError::GeneratorError(GeneratorError::InvalidTypeForString))));
+    assert!(matches!(run_encode("Point", 1, "{ x = true; y = 2; }"), Err(DynamicError::GeneratorError(GeneratorError::InvalidTypeForBoolean))));
+    assert!(matches!(run_encode("Point", 1, "{ x = 1.0; y = 2; }"), Err(DynamicError::GeneratorError(GeneratorError::InvalidTypeForFloat))));
+    assert!(matches!(run_encode("f32", 1, "1"), Err(DynamicError::GeneratorError(GeneratorError::InvalidTypeForIntValue))));
+    assert!(matches!(run_encode("bytes", 1, "[256]"), Err(DynamicError::IntegerOutOfRange(..))));
+    assert!(matches!(run_encode("i24", 1, "8388608"), Err(DynamicError::IntegerOutOfRange(..))));
+    assert!(matches!(run_encode("map(u8, u8)", 1, "[1 => 2, 1 => 3]"), Err(DynamicError::DuplicateKey)));
+    assert!(matches!(run_encode("set(u8)", 1, "[1, 1]"), Err(DynamicError::DuplicateKey)));
+    assert!(matches!(run_encode("array(u8, 2)", 1, "[1, 2, 3]"), Err(DynamicError::GeneratorError(GeneratorError::SequenceLengthMismatch(_)))));
+    assert!(matches!(run_encode("Point", 1, "{ x = [1 => 2]; y = 2; }"), Err(DynamicError::GeneratorError(GeneratorError::InvalidTypeForMap))));
+    assert!(matches!(run_encode("Shape", 1, "tags([])"), Err(DynamicError::GeneratorError(GeneratorError::TypeDoesNotHaveCase(..)))));
+    assert!(matches!(run_encode("Shape", 1, "missing"), Err(DynamicError::ConstantNotDefined(..))));
+}
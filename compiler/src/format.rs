@@ -0,0 +1,468 @@
+//! Formats source files in a canonical style.
+
+use crate::{parser, VError};
+use crate::source::SourceFile;
+use std::ops::Range;
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+const INDENT: &str = "    ";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum TokenKind {
+	Word,
+	Str,
+	Punct(char),
+	Arrow,
+	LineComment,
+	BlockComment,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Token<'a> {
+	pub(crate) kind: TokenKind,
+	pub(crate) text: &'a str,
+	pub(crate) offset: usize,
+	newlines_before: usize,
+}
+
+impl <'a> Token<'a> {
+	pub(crate) fn is_comment(&self) -> bool {
+		matches!(self.kind, TokenKind::LineComment | TokenKind::BlockComment)
+	}
+
+	pub(crate) fn is_punct(&self, ch: char) -> bool {
+		self.kind == TokenKind::Punct(ch)
+	}
+
+	pub(crate) fn is_word(&self, word: &str) -> bool {
+		self.kind == TokenKind::Word && self.text == word
+	}
+}
+
+fn string_literal_len(input: &str) -> usize {
+	let mut chars = input.char_indices().skip(1);
+	while let Some((_, ch)) = chars.next() {
+		match ch {
+			'\\' => { chars.next(); },
+			'"' => break,
+			_ => (),
+		}
+	}
+
+	chars.next().map_or(input.len(), |(index, _)| index)
+}
+
+// When the file is known to parse, every character is part of a token.
+pub(crate) fn tokenize(input: &str) -> Vec<Token<'_>> {
+	let mut tokens = Vec::new();
+	let mut rest = input;
+	let mut newlines = 0;
+
+	while let Some(ch) = rest.chars().next() {
+		if ch.is_whitespace() {
+			if ch == '\n' {
+				newlines += 1;
+			}
+			rest = &rest[ch.len_utf8()..];
+			continue;
+		}
+
+		let (kind, len) =
+			if rest.starts_with("//") {
+				(TokenKind::LineComment, rest.find(['\r', '\n']).unwrap_or(rest.len()))
+			}
+			else if rest.starts_with("/*") {
+				(TokenKind::BlockComment, rest.find("*/").map_or(rest.len(), |index| index + 2))
+			}
+			else if ch == '"' {
+				(TokenKind::Str, string_literal_len(rest))
+			}
+			else if rest.starts_with("=>") {
+				(TokenKind::Arrow, 2)
+			}
+			else if ch.is_ascii_alphanumeric() {
+				(TokenKind::Word, rest.find(|ch: char| !ch.is_ascii_alphanumeric()).unwrap_or(rest.len()))
+			}
+			else {
+				(TokenKind::Punct(ch), ch.len_utf8())
+			};
+
+		tokens.push(Token {
+			kind,
+			text: &rest[..len],
+			offset: input.len() - rest.len(),
+			newlines_before: newlines,
+		});
+		newlines = 0;
+		rest = &rest[len..];
+	}
+
+	tokens
+}
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ItemKind {
+	Header,
+	Import,
+	Definition,
+}
+
+impl ItemKind {
+	// Top level items are separated by blank lines, except for items of the same kind.
+	// The version and package directives are kept together.
+	fn of(token: &Token) -> ItemKind {
+		match token.text {
+			"version" | "package" => ItemKind::Header,
+			"import" => ItemKind::Import,
+			_ => ItemKind::Definition,
+		}
+	}
+}
+
+// Splits the top level of the file into items.
+// Each item includes the comments before it and any comments on the same line after it.
+fn top_level_items(tokens: &[Token]) -> Vec<Range<usize>> {
+	let mut items = Vec::new();
+	let mut depth = 0;
+	let mut start = 0;
+	let mut index = 0;
+
+	while index < tokens.len() {
+		let token = &tokens[index];
+		index += 1;
+
+		if token.is_punct('{') {
+			depth += 1;
+		}
+		else if token.is_punct('}') {
+			depth -= 1;
+		}
+		else if depth > 0 || !token.is_punct(';') {
+			continue;
+		}
+
+		if depth == 0 {
+			while index < tokens.len() && tokens[index].is_comment() && tokens[index].newlines_before == 0 {
+				index += 1;
+			}
+
+			items.push(start..index);
+			start = index;
+		}
+	}
+
+	if start < tokens.len() {
+		items.push(start..tokens.len());
+	}
+
+	items
+}
+
+// Imports are sorted by name, keeping the comments attached to each import.
+fn sort_imports(tokens: &mut Vec<Token>) {
+	let first_word = |item: &Range<usize>| tokens[item.clone()].iter().find(|token| !token.is_comment()).copied();
+	let is_import = |item: &Range<usize>| first_word(item).is_some_and(|token| token.is_word("import"));
+
+	let items = top_level_items(tokens);
+	let first = match items.iter().position(is_import) {
+		Some(first) => first,
+		None => return,
+	};
+	let count = items[first..].iter().take_while(|item| is_import(item)).count();
+	let imports = &items[first..first + count];
+
+	let key = |item: &Range<usize>| tokens[item.clone()].iter()
+		.filter(|token| !token.is_comment())
+		.map(|token| token.text)
+		.collect::<String>();
+
+	let mut sorted = imports.to_vec();
+	sorted.sort_by_cached_key(key);
+
+	let first_newlines = tokens[imports[0].start].newlines_before;
+	let mut sorted_tokens = Vec::new();
+	for (index, item) in sorted.into_iter().enumerate() {
+		let start = sorted_tokens.len();
+		sorted_tokens.extend_from_slice(&tokens[item]);
+		sorted_tokens[start].newlines_before = if index == 0 { first_newlines } else { 1 };
+	}
+
+	let range = imports[0].start..imports[count - 1].end;
+	tokens.splice(range, sorted_tokens);
+}
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Group {
+	Block,
+	LiteralBlock,
+	InlineRecord,
+	Paren,
+	Sequence,
+	Bound,
+	Angle,
+}
+
+impl Group {
+	fn is_block(self) -> bool {
+		matches!(self, Group::Block | Group::LiteralBlock)
+	}
+}
+
+struct Formatter<'a> {
+	tokens: &'a [Token<'a>],
+	out: String,
+	groups: Vec<Group>,
+	line_start: bool,
+	pending_newline: bool,
+	force_space: bool,
+	prev: Option<Token<'a>>,
+	prev2: Option<Token<'a>>,
+	current_item: Option<ItemKind>,
+	top_gap: Option<ItemKind>,
+}
+
+impl <'a> Formatter<'a> {
+	fn new(tokens: &'a [Token<'a>]) -> Self {
+		Formatter {
+			tokens,
+			out: String::new(),
+			groups: Vec::new(),
+			line_start: true,
+			pending_newline: false,
+			force_space: false,
+			prev: None,
+			prev2: None,
+			current_item: None,
+			top_gap: None,
+		}
+	}
+
+	fn format(mut self) -> String {
+		for index in 0..self.tokens.len() {
+			let token = self.tokens[index];
+			if token.is_comment() {
+				self.comment(index, token);
+			}
+			else {
+				self.token(index, token);
+			}
+		}
+
+		let len = self.out.trim_end().len();
+		self.out.truncate(len);
+		self.out.push('\n');
+		self.out
+	}
+
+	fn next_token(&self, index: usize) -> Option<&Token<'a>> {
+		self.tokens[index + 1..].iter().find(|token| !token.is_comment())
+	}
+
+	fn write(&mut self, text: &str) {
+		if self.line_start {
+			let depth = self.groups.iter().filter(|group| group.is_block()).count();
+			for _ in 0..depth {
+				self.out.push_str(INDENT);
+			}
+			self.line_start = false;
+		}
+
+		self.out.push_str(text);
+	}
+
+	fn line_break(&mut self, newlines_before: usize, next_item: Option<ItemKind>, is_close: bool) {
+		self.pending_newline = false;
+		self.force_space = false;
+
+		let source_blank = newlines_before >= 2;
+		let blank =
+			if !self.groups.is_empty() {
+				source_blank && !is_close && !self.prev.is_some_and(|prev| prev.is_punct('{'))
+			}
+			else if let Some(last_item) = self.top_gap {
+				last_item == ItemKind::Definition || next_item != Some(last_item)
+			}
+			else {
+				source_blank
+			};
+
+		self.out.push('\n');
+		if blank {
+			self.out.push('\n');
+		}
+		self.line_start = true;
+	}
+
+	fn opens_bound(&self, token: &Token) -> bool {
+		(token.is_punct('(') || token.is_punct('[')) &&
+			self.groups.last() == Some(&Group::LiteralBlock) &&
+			self.prev.is_some_and(|prev| prev.is_word("integer")) &&
+			!self.prev2.is_some_and(|prev2| prev2.is_word("case"))
+	}
+
+	fn needs_space(&self, token: &Token) -> bool {
+		let prev = match self.prev {
+			Some(prev) => prev,
+			None => return false,
+		};
+
+		if (token.is_punct(')') || token.is_punct(']')) && prev.is_punct(',') {
+			return !self.prev2.is_some_and(|prev2| prev2.is_punct('(') || prev2.is_punct('['));
+		}
+
+		match (prev.kind, token.kind) {
+			(_, TokenKind::Punct(';' | ':' | '.' | ',' | ')' | ']' | '<' | '>')) => false,
+			(TokenKind::Punct('.' | '(' | '[' | '<' | '-'), _) => false,
+			(TokenKind::Punct('{'), TokenKind::Punct('}')) => false,
+			(_, TokenKind::Punct('(')) => self.opens_bound(token),
+			_ => true,
+		}
+	}
+
+	// A record value is written on one line unless it contains comments.
+	fn record_value_has_comments(&self, index: usize) -> bool {
+		let mut depth = 0;
+		for token in &self.tokens[index..] {
+			if token.is_comment() {
+				return true;
+			}
+			else if token.is_punct('{') {
+				depth += 1;
+			}
+			else if token.is_punct('}') {
+				depth -= 1;
+				if depth == 0 {
+					break;
+				}
+			}
+		}
+
+		false
+	}
+
+	fn open_group(&self, index: usize, token: &Token) -> Option<Group> {
+		let prev = self.prev;
+		let prev_is = |ch: char| prev.is_some_and(|prev| prev.is_punct(ch));
+
+		Some(match token.kind {
+			TokenKind::Punct('{') if (prev_is('=') || prev_is(',') || prev_is('[') || prev_is('(')) && !self.record_value_has_comments(index) => Group::InlineRecord,
+			TokenKind::Punct('{') if prev.is_some_and(|prev| prev.is_word("literal")) => Group::LiteralBlock,
+			TokenKind::Punct('{') => Group::Block,
+			TokenKind::Punct('(' | '[') if self.opens_bound(token) => Group::Bound,
+			TokenKind::Punct('(') => Group::Paren,
+			TokenKind::Punct('[') => Group::Sequence,
+			TokenKind::Punct('<') => Group::Angle,
+			_ => return None,
+		})
+	}
+
+	fn token(&mut self, index: usize, token: Token<'a>) {
+		// Signs are only kept when they change the value.
+		if token.is_punct('+') {
+			return;
+		}
+
+		// Trailing commas in sequences are removed.
+		if token.is_punct(',') && self.groups.last() == Some(&Group::Sequence) && self.next_token(index).is_some_and(|next| next.is_punct(']')) {
+			return;
+		}
+
+		let is_close = token.is_punct('}') || token.is_punct(')') || token.is_punct(']') || token.is_punct('>');
+		let closed_group = if is_close { self.groups.pop() } else { None };
+		let closes_block = closed_group.is_some_and(Group::is_block);
+
+		if token.is_punct(';') {
+			self.pending_newline = false;
+		}
+		else if closes_block {
+			self.pending_newline = true;
+		}
+
+		let next_item = Some(ItemKind::of(&token));
+		if self.pending_newline && !self.out.is_empty() {
+			self.line_break(token.newlines_before, next_item, is_close);
+		}
+		else if self.force_space || self.needs_space(&token) {
+			self.out.push(' ');
+		}
+		self.force_space = false;
+
+		if self.groups.is_empty() && self.current_item.is_none() {
+			self.current_item = next_item;
+			self.top_gap = None;
+		}
+
+		let opened_group = self.open_group(index, &token);
+
+		self.write(token.text);
+
+		if let Some(group) = opened_group {
+			self.groups.push(group);
+		}
+
+		let in_block = self.groups.last().is_none_or(|group| group.is_block());
+		if (token.is_punct(';') && in_block) || opened_group.is_some_and(Group::is_block) || closes_block {
+			self.pending_newline = true;
+		}
+
+		if self.groups.is_empty() && (token.is_punct(';') || closes_block) {
+			self.top_gap = self.current_item.take();
+		}
+
+		self.prev2 = self.prev;
+		self.prev = Some(token);
+	}
+
+	fn comment(&mut self, index: usize, token: Token<'a>) {
+		let trailing = token.newlines_before == 0 && !self.out.is_empty();
+
+		if trailing {
+			self.out.push(' ');
+		}
+		else {
+			if !self.out.is_empty() {
+				let next_item = self.next_token(index).map(ItemKind::of);
+				self.line_break(token.newlines_before, next_item, false);
+			}
+			self.top_gap = None;
+		}
+
+		self.write(token.text);
+
+		let next_on_new_line = self.tokens.get(index + 1).is_none_or(|next| next.newlines_before > 0);
+		if token.kind == TokenKind::LineComment || (!trailing && next_on_new_line) {
+			self.pending_newline = true;
+		}
+		else {
+			self.force_space = true;
+		}
+	}
+}
+
+
+/// Formats a source file in the canonical style.
+///
+/// Comments are kept, imports are sorted, and each field, method and version is placed on its own line.
+/// Files with syntax errors cannot be formatted.
+pub fn format_source(file: &Arc<SourceFile>) -> Result<String, VError> {
+	if let Err(err) = parser::parse_model(file) {
+		let errors: Vec<VError> = err.into_errors()
+			.into_iter()
+			.filter(|err| matches!(err, VError::ParseError(_) | VError::ParseIncompleteError))
+			.collect();
+
+		if !errors.is_empty() {
+			return Err(VError::from_errors(errors));
+		}
+	}
+
+	let mut tokens = tokenize(file.content());
+	sort_imports(&mut tokens);
+
+	Ok(Formatter::new(&tokens).format())
+}
@@ -0,0 +1,249 @@
+use crate::format::format_source;
+use crate::source::SourceFile;
+
+fn run_format_test(file_data: &str) -> String {
+    let formatted = format_source(&SourceFile::new(String::from("test.verilization"), String::from(file_data))).unwrap();
+    let reformatted = format_source(&SourceFile::new(String::from("test.verilization"), formatted.clone())).unwrap();
+    assert_eq!(formatted, reformatted);
+    formatted
+}
+
+#[test]
+fn canonical_layout() {
+    let formatted = run_format_test(
+"version   4; package a.b;
+import x.Y; import c.D as E;
+final struct Box<T>{version 1{value:T;   other : list( T ) ;}
+
+
+    version 2 { value: T; }}
+enum E { version 1 { a: u32; } }
+"
+    );
+
+    assert_eq!(formatted,
+"version 4;
+package a.b;
+
+import c.D as E;
+import x.Y;
+
+final struct Box<T> {
+    version 1 {
+        value: T;
+        other: list(T);
+    }
+
+    version 2 {
+        value: T;
+    }
+}
+
+enum E {
+    version 1 {
+        a: u32;
+    }
+}
+"
+    );
+}
+
+#[test]
+fn normalized_literals() {
+    let formatted = run_format_test(
+"version 1;
+extern X {
+    literal {
+        integer [ +0 , ) ;
+        integer (,);
+        integer [-128,127];
+        case  Pair ( u8 , u8 ) ;
+        record { a: u8; };
+        sequence u8;
+        string;
+    }
+}
+const c: X {
+    version 1 = [ 1 , -2 , 3 , ];
+}
+const d: S {
+    version 1 = {a=1;b=\"x\";};
+    version 2 = Pair( 1, 2 );
+}
+"
+    );
+
+    assert_eq!(formatted,
+"version 1;
+
+extern X {
+    literal {
+        integer [0, );
+        integer (,);
+        integer [-128, 127];
+        case Pair(u8, u8);
+        record {
+            a: u8;
+        };
+        sequence u8;
+        string;
+    }
+}
+
+const c: X {
+    version 1 = [1, -2, 3];
+}
+
+const d: S {
+    version 1 = { a = 1; b = \"x\"; };
+    version 2 = Pair(1, 2);
+}
+"
+    );
+}
+
+#[test]
+fn comments_are_kept() {
+    let formatted = run_format_test(
+"// File header
+version 1;
+import b.B; // trailing
+// About A
+import a.A;
+
+/* block
+   comment */
+struct S { // open
+    version 1 {
+        // inside version
+        x: /* type */ u32; /* after field */
+
+        y: u32;
+    }
+}
+// At the end
+"
+    );
+
+    assert_eq!(formatted,
+"// File header
+version 1;
+
+// About A
+import a.A;
+import b.B; // trailing
+
+/* block
+   comment */
+struct S { // open
+    version 1 {
+        // inside version
+        x: /* type */ u32; /* after field */
+
+        y: u32;
+    }
+}
+
+// At the end
+"
+    );
+}
+
+#[test]
+fn interface_methods() {
+    let formatted = run_format_test(
+"version 1;
+interface I { version 1 { f<A,B>( a : A , b:B ) : list(A); } }
+"
+    );
+
+    assert_eq!(formatted,
+"version 1;
+
+interface I {
+    version 1 {
+        f<A, B>(a: A, b: B): list(A);
+    }
+}
+"
+    );
+}
+
+#[test]
+fn syntax_error_is_reported() {
+    assert!(format_source(&SourceFile::new(String::from("test.verilization"), String::from("version 1;\nstruct {"))).is_err());
+}
+
+#[test]
+fn newer_syntax() {
+    let formatted = run_format_test(
+"version 2; package a;
+extern array<A,const N> { literal { sequence A [ N ]; } }
+extern map<K,V> { literal { map K V; } }
+extern bool { literal { boolean; } }
+extern f64 { literal { float; } }
+struct S {
+    version 1 { x: u32; y: u32; m: map(string, u8) = [\"a\"=>1, \"b\" => 2,]; }
+    version 2 { z: u32 renamed from x; removed y; m: map(string,u8)=[\"a\"=>1]; b: bool = true; f: f64 = -1.5; a: array( u8 , 4 ) = [1,2,3,4]; }
+}
+format F { root S; magic [ 86 , 76 ]; version u16; }
+const c: map(string, bool) { version 1 = [ \"x\" => false ]; }
+"
+    );
+
+    assert_eq!(formatted,
+"version 2;
+package a;
+
+extern array<A, const N> {
+    literal {
+        sequence A [N];
+    }
+}
+
+extern map<K, V> {
+    literal {
+        map K V;
+    }
+}
+
+extern bool {
+    literal {
+        boolean;
+    }
+}
+
+extern f64 {
+    literal {
+        float;
+    }
+}
+
+struct S {
+    version 1 {
+        x: u32;
+        y: u32;
+        m: map(string, u8) = [\"a\" => 1, \"b\" => 2];
+    }
+    version 2 {
+        z: u32 renamed from x;
+        removed y;
+        m: map(string, u8) = [\"a\" => 1];
+        b: bool = true;
+        f: f64 = -1.5;
+        a: array(u8, 4) = [1, 2, 3, 4];
+    }
+}
+
+format F {
+    root S;
+    magic [86, 76];
+    version u16;
+}
+
+const c: map(string, bool) {
+    version 1 = [\"x\" => false];
+}
+"
+    );
+}
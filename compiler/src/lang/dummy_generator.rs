@@ -3,6 +3,7 @@ use model::Named;
 use super::generator::*;
 use super::GeneratorError;
 use std::marker::PhantomData;
+use num_bigint::BigUint;
 
 struct DummyConstGenerator<'model, Lang> {
     constant: Named<'model, model::Constant>,
@@ -41,3 +42,57 @@ impl <'model, Lang: GeneratorNameMapping> ConstGenerator<'model> for DummyConstG
     }
 
 }
+
+/// A generator for languages that do not generate code for file formats.
+pub struct DummyFormatGenerator<'model, Lang> {
+    format: Named<'model, model::FileFormat>,
+    model: &'model model::Verilization,
+    scope: model::Scope<'model>,
+    dummy_lang: PhantomData<Lang>,
+}
+
+impl <'model, Lang> DummyFormatGenerator<'model, Lang> {
+    pub fn new(model: &'model model::Verilization, format: Named<'model, model::FileFormat>) -> Self {
+        DummyFormatGenerator {
+            format,
+            model,
+            scope: format.scope(),
+            dummy_lang: PhantomData {},
+        }
+    }
+}
+
+impl <'model, Lang: GeneratorNameMapping> Generator<'model> for DummyFormatGenerator<'model, Lang> {
+    type Lang = Lang;
+
+	fn model(&self) -> &'model model::Verilization {
+        self.model
+    }
+
+	fn scope(&self) -> &model::Scope<'model> {
+        &self.scope
+    }
+}
+
+impl <'model, Lang: GeneratorNameMapping> FormatGenerator<'model> for DummyFormatGenerator<'model, Lang> {
+	fn format(&self) -> Named<'model, model::FileFormat> {
+        self.format
+    }
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+	fn write_read_file(&mut self, _t: LangType<'model>, _read_value: LangExpr<'model>) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+	fn write_write_file(&mut self, _t: LangType<'model>, _version: &BigUint, _codec: LangExpr<'model>) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+}
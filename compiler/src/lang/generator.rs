@@ -21,6 +21,7 @@ pub enum LangType<'model> {
 	Extern(&'model model::QualifiedName, Vec<LangType<'model>>, LangExternTypeLiterals<'model>),
 	Interface(&'model model::QualifiedName, BigUint, Vec<LangType<'model>>, LangInterfaceMethods<'model>),
 	TypeParameter(String),
+	Integer(BigUint),
 	Converter(Box<LangType<'model>>, Box<LangType<'model>>),
 	Codec(Box<LangType<'model>>),
 	RemoteObjectId,
@@ -43,6 +44,9 @@ impl <'model> LangType<'model> {
 			(LangType::TypeParameter(name1), LangType::TypeParameter(name2)) =>
 				name1 == name2,
 
+			(LangType::Integer(n1), LangType::Integer(n2)) =>
+				n1 == n2,
+
 			(LangType::Converter(from1, to1), LangType::Converter(from2, to2)) =>
 				from1.is_same_type(from2) && to1.is_same_type(to2),
 
@@ -81,7 +85,7 @@ impl <'model> LangType<'model> {
 				}
 			},
 
-			LangType::TypeParameter(_) => true,
+			LangType::TypeParameter(_) | LangType::Integer(_) => true,
 
 			LangType::RemoteObjectId | LangType::RemoteConnection | LangType::Converter(..) | LangType::Codec(_) => false,
 		}
@@ -105,7 +109,10 @@ impl <'model> Clone for LangField<'model> {
 pub enum LangLiteral<'model> {
 	Integer(model::ExternLiteralIntBound, Option<BigInt>, model::ExternLiteralIntBound, Option<BigInt>),
 	String,
-	Sequence(LangType<'model>),
+	Boolean,
+	Float,
+	Sequence(LangType<'model>, Option<BigUint>),
+	Map(LangType<'model>, LangType<'model>),
 	Case(String, Vec<LangType<'model>>),
 	Record(Vec<LangField<'model>>),
 }
@@ -197,7 +204,22 @@ impl <'model> LangExternTypeLiterals<'model> {
 			let lang_literal = match literal {
 				model::ExternLiteralSpecifier::Integer(lower_type, lower, upper_type, upper) => LangLiteral::Integer(*lower_type, lower.clone(), *upper_type, upper.clone()),
 				model::ExternLiteralSpecifier::String => LangLiteral::String,
-				model::ExternLiteralSpecifier::Sequence(t) => LangLiteral::Sequence(build_type_impl(self.model, &BigUint::one(), t, &scope, &self.type_args)?),
+				model::ExternLiteralSpecifier::Boolean => LangLiteral::Boolean,
+				model::ExternLiteralSpecifier::Float => LangLiteral::Float,
+				model::ExternLiteralSpecifier::Sequence(t, length) => LangLiteral::Sequence(
+					build_type_impl(self.model, &BigUint::one(), t, &scope, &self.type_args)?,
+					match length {
+						Some(length) => match build_type_arg_impl(self.model, &BigUint::one(), length, &scope, &self.type_args)? {
+							LangType::Integer(n) => Some(n),
+							_ => return Err(GeneratorError::InvalidTypeInExternLiteral),
+						},
+						None => None,
+					},
+				),
+				model::ExternLiteralSpecifier::Map(key_type, value_type) => LangLiteral::Map(
+					build_type_impl(self.model, &BigUint::one(), key_type, &scope, &self.type_args)?,
+					build_type_impl(self.model, &BigUint::one(), value_type, &scope, &self.type_args)?,
+				),
 				model::ExternLiteralSpecifier::Case(name, params) =>
 					LangLiteral::Case(name.clone(), params.iter().map(|param| build_type_impl(self.model, &BigUint::one(), param, &scope, &self.type_args)).collect::<Result<Vec<_>, _>>()?),
 				model::ExternLiteralSpecifier::Record(fields) => {
@@ -287,14 +309,19 @@ impl <'model> LangInterfaceMethods<'model> {
 #[derive(Debug)]
 pub enum Operation {
 	FromPreviousVersion(BigUint),
+	ToPreviousVersion(BigUint),
 	FinalTypeConverter,
 	TypeCodec,
 	FromInteger,
 	FromString,
+	FromBoolean,
+	FromFloat,
 	FromSequence,
+	FromMap,
 	FromCase(String),
 	FromRecord(Vec<String>),
 	CreateRemoteWrapper,
+	ReadAnyVersion,
 }
 
 #[derive(Debug)]
@@ -309,6 +336,9 @@ pub enum LangExpr<'model> {
 	Identifier(String),
 	IntegerLiteral(BigInt),
 	StringLiteral(String),
+	BooleanLiteral(bool),
+	FloatLiteral(f64),
+	MapEntry(Box<LangExpr<'model>>, Box<LangExpr<'model>>),
 	InvokeConverter {
 		converter: Box<LangExpr<'model>>,
 		value: Box<LangExpr<'model>>,
@@ -326,8 +356,8 @@ pub enum LangExpr<'model> {
 	InvokeOperation(Operation, OperationTarget<'model>, Vec<LangType<'model>>, Vec<LangExpr<'model>>),
 	InvokeUserConverter {
 		name: &'model model::QualifiedName,
-		prev_ver: BigUint,
-		version: BigUint,
+		from_ver: BigUint,
+		to_ver: BigUint,
 		type_args: Vec<LangType<'model>>,
 		args: Vec<LangExpr<'model>>,
 	},
@@ -378,6 +408,10 @@ pub enum LangExprStmt<'model> {
 		id: LangExpr<'model>,
 		methods: Vec<LangInterfaceMethod<'model>>,
 	},
+	ReadAnyVersion {
+		t: LangType<'model>,
+		read: Box<LangStmt<'model>>,
+	},
 }
 
 pub enum LangStmt<'model> {
@@ -418,7 +452,7 @@ fn requires_conversion<'model, G: Generator<'model>>(gen: &G, t: &model::Type, p
 						Some(last_ver) => last_ver > prev_ver,
 						None => true
 					}) ||
-					t.args.iter().any(|arg| requires_conversion(gen, arg, prev_ver))
+					t.args.iter().filter_map(model::TypeArgument::as_type).any(|arg| requires_conversion(gen, arg, prev_ver))
 			},
 
 			Some(model::NamedTypeDefinition::ExternType(_)) => false,
@@ -430,6 +464,89 @@ fn requires_conversion<'model, G: Generator<'model>>(gen: &G, t: &model::Type, p
 	}
 }
 
+// Conversions between adjacent versions of a type.
+// Downgrades convert from version to prev_ver using the ToPreviousVersion operations.
+fn build_conversion_impl<'model, G: Generator<'model>>(gen: &G, prev_ver: &BigUint, version: &BigUint, t: &model::Type, param: ConvertParam<'model>, is_downgrade: bool) -> Result<LangExpr<'model>, GeneratorError> {
+	let (from_ver, to_ver) = if is_downgrade { (version, prev_ver) } else { (prev_ver, version) };
+
+	if !requires_conversion(gen, t, prev_ver) {
+		return Ok(match param {
+			ConvertParam::ConverterObject => LangExpr::IdentityConverter(gen.build_type(to_ver, t)?),
+			ConvertParam::Expression(expr) => expr,
+		})
+	}
+
+	let converter = match gen.scope().lookup(t.name.clone()) {
+		model::ScopeLookup::NamedType(name) => {
+
+			let mut op_type_args = Vec::new();
+			let mut op_args = Vec::new();
+
+			for arg in &t.args {
+				let arg = match arg {
+					model::TypeArgument::Type(arg) => arg,
+					model::TypeArgument::Integer(n) => {
+						op_type_args.push(LangType::Integer(n.clone()));
+						op_type_args.push(LangType::Integer(n.clone()));
+						continue;
+					},
+				};
+
+				op_type_args.push(gen.build_type(from_ver, arg)?);
+				op_type_args.push(gen.build_type(to_ver, arg)?);
+				op_args.push(build_conversion_impl(gen, prev_ver, version, arg, ConvertParam::ConverterObject, is_downgrade)?);
+			}
+
+
+			let named_type_def = gen.model().get_type(&name).ok_or_else(|| GeneratorError::CouldNotFind(name.clone()))?;
+			let operation;
+			let target;
+			match named_type_def {
+				model::NamedTypeDefinition::StructType(type_def) | model::NamedTypeDefinition::EnumType(type_def) => {
+					let ver_type = type_def.versioned(version).ok_or_else(|| GeneratorError::CouldNotFindVersion(name.clone(), version.clone()))?;
+
+					operation =
+						if ver_type.version < *version {
+							Operation::FinalTypeConverter
+						}
+						else if is_downgrade {
+							Operation::ToPreviousVersion(prev_ver.clone())
+						}
+						else {
+							Operation::FromPreviousVersion(prev_ver.clone())
+						};
+
+					target = OperationTarget::VersionedType(named_type_def.name(), ver_type.version.clone());
+				},
+
+				model::NamedTypeDefinition::ExternType(_) => {
+					operation = Operation::FinalTypeConverter;
+					target = OperationTarget::ExternType(named_type_def.name());
+				},
+
+				model::NamedTypeDefinition::InterfaceType(_) => panic!("Cannot convert interface type to another version."),
+			};
+
+			LangExpr::InvokeOperation(
+				operation,
+				target,
+				op_type_args,
+				op_args
+			)
+		},
+		model::ScopeLookup::TypeParameter(name) => LangExpr::Identifier(G::Lang::convert_conv_param_name(&name)),
+	};
+
+				
+	Ok(match param {
+		ConvertParam::ConverterObject => converter,
+		ConvertParam::Expression(expr) => LangExpr::InvokeConverter {
+			converter: Box::new(converter),
+			value: Box::new(expr),
+		},
+	})
+}
+
 pub trait GeneratorNameMapping {
 	fn convert_prev_type_param(param: &str) -> String;
 	fn convert_current_type_param(param: &str) -> String;
@@ -441,6 +558,7 @@ pub trait GeneratorNameMapping {
 
 	fn format_writer_name() -> &'static str;
 	fn format_reader_name() -> &'static str;
+	fn version_param_name() -> &'static str;
 	fn connection_name() -> &'static str;
 	fn object_id_name() -> &'static str;
 	
@@ -448,9 +566,16 @@ pub trait GeneratorNameMapping {
 }
 
 
+fn build_type_arg_impl<'model>(model: &'model model::Verilization, version: &BigUint, arg: &model::TypeArgument, scope: &model::Scope<'model>, type_args: &HashMap<String, LangType<'model>>) -> Result<LangType<'model>, GeneratorError> {
+	match arg {
+		model::TypeArgument::Type(t) => build_type_impl(model, version, t, scope, type_args),
+		model::TypeArgument::Integer(n) => Ok(LangType::Integer(n.clone())),
+	}
+}
+
 fn build_type_impl<'model>(model: &'model model::Verilization, version: &BigUint, t: &model::Type, scope: &model::Scope<'model>, type_args: &HashMap<String, LangType<'model>>) -> Result<LangType<'model>, GeneratorError> {
 	let lang_args = t.args.iter()
-		.map(|arg| build_type_impl(model, version, arg, scope, type_args))
+		.map(|arg| build_type_arg_impl(model, version, arg, scope, type_args))
 		.collect::<Result<Vec<_>, _>>()?;
 
 	Ok(match scope.lookup(t.name.clone()) {
@@ -537,6 +662,11 @@ fn build_type_impl<'model>(model: &'model model::Verilization, version: &BigUint
 	})
 }
 
+/// Builds a type where every name is fully qualified.
+pub fn build_qualified_type<'model>(model: &'model model::Verilization, version: &BigUint, t: &model::Type) -> Result<LangType<'model>, GeneratorError> {
+	build_type_impl(model, version, t, &model::Scope::empty(model), &HashMap::new())
+}
+
 fn is_valid_integer_for_type(n: &BigInt, literals: LangExternTypeLiterals) -> Result<bool, GeneratorError> {
 	Ok(literals.build()?.iter().any(|literal| match literal {
 		LangLiteral::Integer(lower_bound, lower_value, upper_bound, upper_value) =>
@@ -615,75 +745,19 @@ pub trait Generator<'model> : Sized {
 
 			LangType::TypeParameter(name) => LangExpr::Identifier(Self::Lang::codec_codec_param_name(&name)),
 
+			LangType::Integer(n) => LangExpr::IntegerLiteral(BigInt::from(n)),
+
 			LangType::RemoteObjectId | LangType::RemoteConnection | LangType::Codec(_) | LangType::Converter(_, _) => return Err(GeneratorError::InvalidTypeForCodec),
 		})
 	}
 
 	fn build_conversion(&self, prev_ver: &BigUint, version: &BigUint, t: &model::Type, param: ConvertParam<'model>) -> Result<LangExpr<'model>, GeneratorError> {
-		if !requires_conversion(self, t, prev_ver) {
-			return Ok(match param {
-				ConvertParam::ConverterObject => LangExpr::IdentityConverter(self.build_type(version, t)?),
-				ConvertParam::Expression(expr) => expr,
-			})
-		}
-
-		let converter = match self.scope().lookup(t.name.clone()) {
-			model::ScopeLookup::NamedType(name) => {
-
-				let mut op_type_args = Vec::new();
-				let mut op_args = Vec::new();
-
-				for arg in &t.args {
-					op_type_args.push(self.build_type(prev_ver, arg)?);
-					op_type_args.push(self.build_type(version, arg)?);
-					op_args.push(self.build_conversion(prev_ver, version, arg, ConvertParam::ConverterObject)?);
-				}
-
-
-				let named_type_def = self.model().get_type(&name).ok_or_else(|| GeneratorError::CouldNotFind(name.clone()))?;
-				let operation;
-				let target;
-				match named_type_def {
-					model::NamedTypeDefinition::StructType(type_def) | model::NamedTypeDefinition::EnumType(type_def) => {
-						let ver_type = type_def.versioned(version).ok_or_else(|| GeneratorError::CouldNotFindVersion(name.clone(), version.clone()))?;
-
-						operation =
-							if ver_type.version < *version {
-								Operation::FinalTypeConverter
-							}
-							else {
-								Operation::FromPreviousVersion(prev_ver.clone())
-							};
-
-						target = OperationTarget::VersionedType(named_type_def.name(), ver_type.version.clone());
-					},
-
-					model::NamedTypeDefinition::ExternType(_) => {
-						operation = Operation::FinalTypeConverter;
-						target = OperationTarget::ExternType(named_type_def.name());
-					},
-
-					model::NamedTypeDefinition::InterfaceType(_) => panic!("Cannot convert interface type to newer version."),
-				};
-
-				LangExpr::InvokeOperation(
-					operation,
-					target,
-					op_type_args,
-					op_args
-				)
-			},
-			model::ScopeLookup::TypeParameter(name) => LangExpr::Identifier(Self::Lang::convert_conv_param_name(&name)),
-		};
+		build_conversion_impl(self, prev_ver, version, t, param, false)
+	}
 
-					
-		Ok(match param {
-			ConvertParam::ConverterObject => converter,
-			ConvertParam::Expression(expr) => LangExpr::InvokeConverter {
-				converter: Box::new(converter),
-				value: Box::new(expr),
-			},
-		})
+	// Converts a value of version to the previous version.
+	fn build_downgrade(&self, prev_ver: &BigUint, version: &BigUint, t: &model::Type, param: ConvertParam<'model>) -> Result<LangExpr<'model>, GeneratorError> {
+		build_conversion_impl(self, prev_ver, version, t, param, true)
 	}
 
 	fn build_value(&self, version: &BigUint, t: LangType<'model>, value: model::ConstantValue) -> Result<LangExpr<'model>, GeneratorError> {
@@ -714,18 +788,53 @@ pub trait Generator<'model> : Sized {
 					_ => return Err(GeneratorError::InvalidTypeForString),
 				},
 
+			model::ConstantValue::Boolean(b) =>
+				match t {
+					LangType::Extern(name, args, literals) =>
+						if !literals.build()?.iter().any(|literal| matches!(literal, LangLiteral::Boolean)) {
+							return Err(GeneratorError::InvalidTypeForBoolean)
+						}
+						else {
+							LangExpr::InvokeOperation(Operation::FromBoolean, OperationTarget::ExternType(name), args, vec!(LangExpr::BooleanLiteral(b)))
+						},
+
+					_ => return Err(GeneratorError::InvalidTypeForBoolean),
+				},
+
+			model::ConstantValue::Float(f) =>
+				match t {
+					LangType::Extern(name, args, literals) =>
+						if !literals.build()?.iter().any(|literal| matches!(literal, LangLiteral::Float)) {
+							return Err(GeneratorError::InvalidTypeForFloat)
+						}
+						else {
+							LangExpr::InvokeOperation(Operation::FromFloat, OperationTarget::ExternType(name), args, vec!(LangExpr::FloatLiteral(f)))
+						},
+
+					_ => return Err(GeneratorError::InvalidTypeForFloat),
+				},
+
 			model::ConstantValue::Sequence(seq) => match t {
 				LangType::Extern(type_name, type_args, literals) => {
 					let literals = literals.build()?;
 
-					let element_type = literals
+					// An empty sequence is also used for an empty map.
+					if seq.is_empty() && !literals.iter().any(|literal| matches!(literal, LangLiteral::Sequence(..))) && literals.iter().any(|literal| matches!(literal, LangLiteral::Map(..))) {
+						return Ok(LangExpr::InvokeOperation(Operation::FromMap, OperationTarget::ExternType(type_name), type_args, Vec::new()));
+					}
+
+					let (element_type, length) = literals
 						.into_iter()
 						.find_map(|literal| match literal {
-							LangLiteral::Sequence(element_type) => Some(element_type),
+							LangLiteral::Sequence(element_type, length) => Some((element_type, length)),
 							_ => None,
 						})
 						.ok_or_else(|| GeneratorError::TypeCannotBeSequence(type_name.clone()))?;
 
+					if length.is_some_and(|length| length != BigUint::from(seq.len())) {
+						return Err(GeneratorError::SequenceLengthMismatch(type_name.clone()));
+					}
+
 					let args = seq.into_iter()
 						.map(|elem| self.build_value(version, element_type.clone(), elem))
 						.collect::<Result<Vec<_>, _>>()?;
@@ -739,11 +848,38 @@ pub trait Generator<'model> : Sized {
 				},
 				LangType::Versioned(_, type_name, ..) => return Err(GeneratorError::TypeCannotBeSequence(type_name.clone())),
 				LangType::RemoteObjectId | LangType::RemoteConnection |
-				LangType::Interface(..) | LangType::TypeParameter(_) |
+				LangType::Interface(..) | LangType::TypeParameter(_) | LangType::Integer(_) |
 				LangType::Codec(_) | LangType::Converter(_, _) =>
 					return Err(GeneratorError::InvalidTypeForConstant),
 			},
 			
+			model::ConstantValue::Map(entries) => match t {
+				LangType::Extern(type_name, type_args, literals) => {
+					let (key_type, value_type) = literals.build()?
+						.into_iter()
+						.find_map(|literal| match literal {
+							LangLiteral::Map(key_type, value_type) => Some((key_type, value_type)),
+							_ => None,
+						})
+						.ok_or(GeneratorError::InvalidTypeForMap)?;
+
+					let args = entries.into_iter()
+						.map(|(key, value)| Ok(LangExpr::MapEntry(
+							Box::new(self.build_value(version, key_type.clone(), key)?),
+							Box::new(self.build_value(version, value_type.clone(), value)?),
+						)))
+						.collect::<Result<Vec<_>, GeneratorError>>()?;
+
+					LangExpr::InvokeOperation(
+						Operation::FromMap,
+						OperationTarget::ExternType(type_name),
+						type_args,
+						args,
+					)
+				},
+				_ => return Err(GeneratorError::InvalidTypeForMap),
+			},
+
 			model::ConstantValue::Case(case_name, mut args) => match t {
 				LangType::Versioned(VersionedTypeKind::Enum, type_name, type_version, type_args, fields) if args.len() == 1 => {
 					let field = fields.build()?.into_iter().find(|field| *field.name == case_name).ok_or_else(|| GeneratorError::TypeDoesNotHaveCase(type_name.clone(), Some(type_version.clone()), case_name.clone()))?;
@@ -776,7 +912,7 @@ pub trait Generator<'model> : Sized {
 
 				LangType::Versioned(VersionedTypeKind::Struct, ..) => return Err(GeneratorError::RecordLiteralNotForStruct),
 				LangType::RemoteObjectId | LangType::RemoteConnection |
-				LangType::Interface(..) | LangType::TypeParameter(_) |
+				LangType::Interface(..) | LangType::TypeParameter(_) | LangType::Integer(_) |
 				LangType::Codec(_) | LangType::Converter(_, _) =>
 					return Err(GeneratorError::InvalidTypeForConstant),
 			},
@@ -825,29 +961,24 @@ pub trait Generator<'model> : Sized {
 
 				LangType::Versioned(VersionedTypeKind::Enum, ..) => return Err(GeneratorError::InvalidTypeForConstant),
 				LangType::RemoteObjectId | LangType::RemoteConnection |
-				LangType::Interface(..) | LangType::TypeParameter(_) |
+				LangType::Interface(..) | LangType::TypeParameter(_) | LangType::Integer(_) |
 				LangType::Codec(_) | LangType::Converter(_, _) => return Err(GeneratorError::InvalidTypeForConstant),
 			},
 			model::ConstantValue::Constant(name) => {
-				match self.scope().lookup(name) {
-					model::ScopeLookup::NamedType(name) => {
-						let constant = self.model().get_constant(&name).ok_or_else(|| GeneratorError::CouldNotFind(name.clone()))?;
-
-						if !constant.has_version(version) {
-							return Err(GeneratorError::CouldNotFindVersion(name, version.clone()))
-						}
-						
-						let const_type = self.build_type(version, constant.value_type())?;
-
-						if !t.is_same_type(&const_type) {
-							return Err(GeneratorError::TypeMismatch)
-						}
+				let name = self.scope().lookup_constant(name);
+				let constant = self.model().get_constant(&name).ok_or_else(|| GeneratorError::CouldNotFind(name.clone()))?;
 
-						LangExpr::ConstantValue(constant.name(), version.clone())
-					},
-					model::ScopeLookup::TypeParameter(name) => return Err(GeneratorError::CouldNotFind(model::QualifiedName { package: model::PackageName::new(), name: name, }))
+				if !constant.has_version(version) {
+					return Err(GeneratorError::CouldNotFindVersion(name, version.clone()))
 				}
 				
+				let const_type = self.build_type(version, constant.value_type())?;
+
+				if !t.is_same_type(&const_type) {
+					return Err(GeneratorError::TypeMismatch)
+				}
+
+				LangExpr::ConstantValue(constant.name(), version.clone())
 			},
 		})
 	}
@@ -892,6 +1023,49 @@ pub trait ConstGenerator<'model> : Generator<'model> {
 
 }
 
+pub trait FormatGenerator<'model> : Generator<'model> {
+	fn format(&self) -> Named<'model, model::FileFormat>;
+
+	fn write_header(&mut self) -> Result<(), GeneratorError>;
+	fn write_read_file(&mut self, t: LangType<'model>, read_value: LangExpr<'model>) -> Result<(), GeneratorError>;
+	fn write_write_file(&mut self, t: LangType<'model>, version: &BigUint, codec: LangExpr<'model>) -> Result<(), GeneratorError>;
+	fn write_footer(&mut self) -> Result<(), GeneratorError>;
+
+
+	fn generate(&mut self) -> Result<(), GeneratorError> {
+		let root_type = self.format().root_type();
+		let root_name = match self.scope().lookup(root_type.name.clone()) {
+			model::ScopeLookup::NamedType(name) => name,
+			model::ScopeLookup::TypeParameter(_) => return Err(GeneratorError::CouldNotGenerateType),
+		};
+
+		let type_def = match self.model().get_type(&root_name).ok_or_else(|| GeneratorError::CouldNotFind(root_name.clone()))? {
+			model::NamedTypeDefinition::StructType(type_def) | model::NamedTypeDefinition::EnumType(type_def) => type_def,
+			_ => return Err(GeneratorError::CouldNotGenerateType),
+		};
+
+		// Files are always written with the latest version.
+		let version = type_def.versions().last().ok_or(GeneratorError::CouldNotGenerateType)?.version;
+		let t = self.build_type(&version, root_type)?;
+
+		let read_value = LangExpr::InvokeOperation(
+			Operation::ReadAnyVersion,
+			OperationTarget::VersionedType(type_def.name(), version.clone()),
+			vec!(),
+			vec!(
+				LangExpr::Identifier(Self::Lang::version_param_name().to_string()),
+				LangExpr::Identifier(Self::Lang::format_reader_name().to_string()),
+			),
+		);
+		let codec = self.build_codec(t.clone())?;
+
+		self.write_header()?;
+		self.write_read_file(t.clone(), read_value)?;
+		self.write_write_file(t, &version, codec)?;
+		self.write_footer()
+	}
+}
+
 pub trait TypeGenerator<'model> : Generator<'model> {
 	type TypeDefinition : 'model + model::GeneratableType<'model>;
 	fn type_def(&self) -> Named<'model, Self::TypeDefinition>;
@@ -901,6 +1075,11 @@ pub trait TypeGenerator<'model> : Generator<'model> {
 	fn write_operation(&mut self, operation: OperationInfo<'model>) -> Result<(), GeneratorError>;
 	fn write_version_footer(&mut self) -> Result<(), GeneratorError>;
 	fn write_footer(&mut self) -> Result<(), GeneratorError>;
+
+	/// Whether conversions to the previous version of each type are generated.
+	fn generate_downgrades(&self) -> bool {
+		false
+	}
 }
 
 pub trait TypeGeneratorOperations<'model, TypeDef : 'model + model::GeneratableType<'model>> : TypeGenerator<'model, TypeDefinition = TypeDef> {
@@ -942,14 +1121,15 @@ impl <'model, TypeDef : 'model + model::GeneratableType<'model>, TImpl : TypeGen
 	}
 }
 
-fn build_type_params_as_args<'model, TypeDef : model::GeneratableType<'model>>(type_def: Named<'model, TypeDef>) -> Vec<model::Type> {
+fn build_type_params_as_args<'model, TypeDef : model::GeneratableType<'model>>(type_def: Named<'model, TypeDef>) -> Vec<model::TypeArgument> {
 	type_def.type_params().iter()
-		.map(|param| model::Type { name: model::QualifiedName::from_parts(&[], &param), args: vec!() })
+		.map(|param| model::TypeArgument::Type(model::Type { name: model::QualifiedName::from_parts(&[], &param), args: vec!() }))
 		.collect::<Vec<_>>()
 }
 
 pub struct VersionedTypeGeneratorOperationsState {
 	is_final_last_version: bool,
+	is_last_version: bool,
 	type_kind: VersionedTypeKind,
 }
 
@@ -961,6 +1141,7 @@ impl <'model, TImpl : TypeGenerator<'model, TypeDefinition = model::VersionedTyp
 
 		Ok(VersionedTypeGeneratorOperationsState {
 			is_final_last_version: t.is_final_in_version(version, self.model()),
+			is_last_version: self.type_def().versions().last().is_some_and(|last_ver| last_ver.version == *version),
 			type_kind: match &t {
 				LangType::Versioned(kind, ..) => *kind,
 				_ => return Err(GeneratorError::CouldNotGenerateType),
@@ -973,12 +1154,18 @@ impl <'model, TImpl : TypeGenerator<'model, TypeDefinition = model::VersionedTyp
 
 		// Converter for latest version of final type with type parameters
 		if state.is_final_last_version && !self.type_def().type_params().is_empty() {
-			self.write_operation(build_converter_operation_common(self, Operation::FinalTypeConverter, state.type_kind, &ver_type, version)?)?;
+			self.write_operation(build_converter_operation_common(self, Operation::FinalTypeConverter, state.type_kind, &ver_type, version, ver_type.ver_type)?)?;
 		}
 		
 		// Conversion from previous version
 		if let Some(prev_ver) = prev_ver { // Skip when there is no prevous version.
-			self.write_operation(build_converter_operation_common(self, Operation::FromPreviousVersion(prev_ver.clone()), state.type_kind, &ver_type, &prev_ver)?)?;
+			let prev_ver_type = self.type_def().versioned(prev_ver).ok_or_else(|| GeneratorError::CouldNotFindVersion(self.type_def().name().clone(), prev_ver.clone()))?;
+			self.write_operation(build_converter_operation_common(self, Operation::FromPreviousVersion(prev_ver.clone()), state.type_kind, &ver_type, &prev_ver, prev_ver_type.ver_type)?)?;
+
+			// Conversion to previous version
+			if self.generate_downgrades() {
+				self.write_operation(build_converter_operation_common(self, Operation::ToPreviousVersion(prev_ver.clone()), state.type_kind, ver_type, prev_ver, prev_ver_type.ver_type)?)?;
+			}
 		}
 
 		// Codec
@@ -1012,6 +1199,11 @@ impl <'model, TImpl : TypeGenerator<'model, TypeDefinition = model::VersionedTyp
 			self.write_operation(op)?;
 		}
 
+		// Read any version and convert to the latest version
+		if state.is_last_version {
+			self.write_operation(build_read_any_version_operation(self, version)?)?;
+		}
+
 		Ok(())
 	}
 }
@@ -1072,22 +1264,77 @@ impl <'model, TImpl : TypeGenerator<'model, TypeDefinition = model::InterfaceTyp
 }
 
 
-fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_kind: VersionedTypeKind, ver_type: &model::TypeVersionInfo<&'model model::TypeVersionDefinition>, prev_ver: &BigUint) -> Result<OperationInfo<'model>, GeneratorError> where
+fn find_field_type<'a>(fields: &'a [(String, model::FieldInfo)], name: &str) -> Option<&'a model::Type> {
+	fields.iter().find(|(field_name, _)| field_name == name).map(|(_, field)| &field.field_type)
+}
+
+// Types are compared after resolving their names, so that a type may be written differently in each version.
+fn is_same_type(scope: &model::Scope, a: &model::Type, b: &model::Type) -> bool {
+	scope.lookup(a.name.clone()) == scope.lookup(b.name.clone()) &&
+		a.args.len() == b.args.len() &&
+		a.args.iter().zip(b.args.iter()).all(|(a, b)| match (a, b) {
+			(model::TypeArgument::Type(a), model::TypeArgument::Type(b)) => is_same_type(scope, a, b),
+			(model::TypeArgument::Integer(a), model::TypeArgument::Integer(b)) => a == b,
+			_ => false,
+		})
+}
+
+// A new version can be converted without user code when each of its fields is either copied from a field
+// of the previous version with the same type, possibly under a new name, or is new.
+// New fields of a struct need a default value, while new cases of an enum are never created by the conversion.
+// Every field of the previous version must be copied or marked as removed.
+fn has_automatic_conversion(scope: &model::Scope, type_kind: VersionedTypeKind, ver_type: &model::TypeVersionDefinition, prev_ver_type: &model::TypeVersionDefinition) -> bool {
+	let mut copied_fields = Vec::new();
+	for (field_name, field) in ver_type.fields() {
+		let prev_field_name = field.renamed_from.as_ref().unwrap_or(field_name);
+		match find_field_type(prev_ver_type.fields(), prev_field_name) {
+			Some(prev_field_type) if is_same_type(scope, prev_field_type, &field.field_type) => copied_fields.push(prev_field_name),
+			Some(_) => return false,
+			None if matches!(type_kind, VersionedTypeKind::Enum) || field.default_value.is_some() => (),
+			None => return false,
+		}
+	}
+
+	prev_ver_type.fields().iter().all(|(prev_field_name, _)|
+		copied_fields.contains(&prev_field_name) ||
+			ver_type.removed_fields().iter().any(|(removed_name, _)| removed_name == prev_field_name)
+	)
+}
+
+// A new version can be converted back to the previous version without user code when each of its fields is
+// copied from a field of the previous version with the same type and every field of the previous version is copied.
+// Otherwise, a downgrade would lose values or need values that the new version does not have.
+fn has_automatic_downgrade(scope: &model::Scope, ver_type: &model::TypeVersionDefinition, prev_ver_type: &model::TypeVersionDefinition) -> bool {
+	let mut copied_fields = Vec::new();
+	for (field_name, field) in ver_type.fields() {
+		let prev_field_name = field.renamed_from.as_ref().unwrap_or(field_name);
+		match find_field_type(prev_ver_type.fields(), prev_field_name) {
+			Some(prev_field_type) if is_same_type(scope, prev_field_type, &field.field_type) => copied_fields.push(prev_field_name),
+			_ => return false,
+		}
+	}
+
+	prev_ver_type.fields().iter().all(|(prev_field_name, _)| copied_fields.contains(&prev_field_name))
+}
+
+fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_kind: VersionedTypeKind, ver_type: &model::TypeVersionInfo<&'model model::TypeVersionDefinition>, prev_ver: &BigUint, prev_ver_type: &'model model::TypeVersionDefinition) -> Result<OperationInfo<'model>, GeneratorError> where
 	Gen : TypeGenerator<'model>
 {
 	let version = &ver_type.version;
+	let is_downgrade = matches!(op, Operation::ToPreviousVersion(_));
+	let (from_ver, to_ver) = if is_downgrade { (version, prev_ver) } else { (prev_ver, version) };
 
 	let mut type_params_as_args = Vec::new();
 	let mut type_params = Vec::new();
 	let mut type_args = Vec::new();
 	let mut params = Vec::new();
 	let mut result_type_args = Vec::new();
-	let mut prev_type_args = HashMap::new();
+	let mut from_type_args = HashMap::new();
 	let mut result_type_args_map = HashMap::new();
 	let mut impl_call_args = Vec::new();
 
 	for param in gen.type_def().type_params() {
-		type_params_as_args.push(model::Type { name: model::QualifiedName::from_parts(&[], &param), args: vec!() });
+		type_params_as_args.push(model::TypeArgument::Type(model::Type { name: model::QualifiedName::from_parts(&[], &param), args: vec!() }));
 		let t1 = Gen::Lang::convert_prev_type_param(&param);
 		let t2 = Gen::Lang::convert_current_type_param(&param);
 		type_params.push(t1.clone());
@@ -1097,7 +1344,7 @@ fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_
 		type_args.push(t1_arg.clone());
 		type_args.push(t2_arg.clone());
 		result_type_args.push(t2_arg.clone());
-		prev_type_args.insert(param.clone(), t1_arg);
+		from_type_args.insert(param.clone(), t1_arg);
 		result_type_args_map.insert(param.clone(), t2_arg);
 
 		let conv_type = LangType::Converter(
@@ -1110,30 +1357,60 @@ fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_
 		impl_call_args.push(LangExpr::Identifier(conv_param));
 	}
 
-	let prev_type = build_type_impl(gen.model(), prev_ver, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &prev_type_args)?;
-	let result_type = build_type_impl(gen.model(), version, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &result_type_args_map)?;
+	let from_type = build_type_impl(gen.model(), from_ver, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &from_type_args)?;
+	let result_type = build_type_impl(gen.model(), to_ver, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &result_type_args_map)?;
 
-	let converter_type = LangType::Converter(Box::new(prev_type.clone()), Box::new(result_type.clone()));
+	let converter_type = LangType::Converter(Box::new(from_type.clone()), Box::new(result_type.clone()));
 
-	let implementation = if ver_type.explicit_version && ver_type.version != *prev_ver {
+	let is_new_version = ver_type.explicit_version && ver_type.version != *prev_ver;
+	let has_automatic =
+		if is_downgrade {
+			has_automatic_downgrade(gen.scope(), ver_type.ver_type, prev_ver_type)
+		}
+		else {
+			has_automatic_conversion(gen.scope(), type_kind, ver_type.ver_type, prev_ver_type)
+		};
+
+	let implementation = if is_new_version && !has_automatic {
 		LangExprStmt::Expr(LangExpr::InvokeUserConverter {
 			name: gen.type_def().name(),
-			prev_ver: prev_ver.clone(),
-			version: version.clone(),
+			from_ver: from_ver.clone(),
+			to_ver: to_ver.clone(),
 			type_args: type_args,
 			args: impl_call_args,
 		})
 	}
+	else if is_downgrade {
+		LangExprStmt::CreateConverter {
+			from_type: from_type.clone(),
+			to_type: result_type,
+			body: Box::new(build_downgrade_body(gen, type_kind, ver_type, prev_ver, prev_ver_type, from_type, result_type_args)?),
+		}
+	}
 	else {
 		let body = match type_kind {
 			VersionedTypeKind::Struct => {
 				let mut fields = Vec::new();
 		
 				for (field_name, field) in ver_type.ver_type.fields() {
-					let obj_value = LangExpr::Identifier(Gen::Lang::convert_prev_param_name().to_string());
+					let prev_field_name = match &field.renamed_from {
+						Some(renamed_from) if is_new_version => renamed_from,
+						_ => field_name,
+					};
+					let is_new_field = find_field_type(prev_ver_type.fields(), prev_field_name).is_none();
+
+					let conv_value = match &field.default_value {
+						Some(default_value) if is_new_field => {
+							let field_type = build_type_impl(gen.model(), version, &field.field_type, gen.scope(), &result_type_args_map)?;
+							gen.build_value(version, field_type, default_value.clone())?
+						},
+						_ => {
+							let obj_value = LangExpr::Identifier(Gen::Lang::convert_prev_param_name().to_string());
 		
-					let value_expr = LangExpr::StructField(gen.type_def().name(), ver_type.version.clone(), field_name, Box::new(obj_value));
-					let conv_value = gen.build_conversion(prev_ver, &ver_type.version, &field.field_type, ConvertParam::Expression(value_expr))?;
+							let value_expr = LangExpr::StructField(gen.type_def().name(), ver_type.version.clone(), prev_field_name, Box::new(obj_value));
+							gen.build_conversion(prev_ver, &ver_type.version, &field.field_type, ConvertParam::Expression(value_expr))?
+						},
+					};
 		
 					fields.push((field_name, conv_value));
 				}
@@ -1147,6 +1424,15 @@ fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_
 		
 		
 				for (field_name, field) in ver_type.ver_type.fields() {
+					let prev_field_name = match &field.renamed_from {
+						Some(renamed_from) if is_new_version => renamed_from,
+						_ => field_name,
+					};
+
+					// Cases that were added in this version cannot be matched.
+					if find_field_type(prev_ver_type.fields(), prev_field_name).is_none() {
+						continue;
+					}
 		
 					let value_expr = LangExpr::Identifier(field_name.clone());
 					let conv_value = gen.build_conversion(prev_ver, &ver_type.version, &field.field_type, ConvertParam::Expression(value_expr))?;
@@ -1154,21 +1440,21 @@ fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_
 		
 					cases.push(MatchCase {
 						binding_name: field_name.clone(),
-						case_name: field_name.clone(),
+						case_name: prev_field_name.clone(),
 						body: LangStmt::Expr(vec!(), Some(enum_value)),
 					});
 				}
 		
 				LangStmt::MatchEnum {
 					value: LangExpr::Identifier(Gen::Lang::convert_prev_param_name().to_string()),
-					value_type: build_type_impl(gen.model(), prev_ver, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &prev_type_args)?,
+					value_type: build_type_impl(gen.model(), prev_ver, &model::Type { name: gen.type_def().name().clone(), args: type_params_as_args.clone() }, gen.scope(), &from_type_args)?,
 					cases: cases,
 				}
 			},
 		};
 
 		LangExprStmt::CreateConverter {
-			from_type: prev_type,
+			from_type,
 			to_type: result_type,
 			body: Box::new(body),
 		}
@@ -1184,6 +1470,120 @@ fn build_converter_operation_common<'model, Gen>(gen: &Gen, op: Operation, type_
 	})
 }
 
+// Copies each field of the new version to the field of the previous version that it was copied from.
+fn build_downgrade_body<'model, Gen>(gen: &Gen, type_kind: VersionedTypeKind, ver_type: &model::TypeVersionInfo<&'model model::TypeVersionDefinition>, prev_ver: &BigUint, prev_ver_type: &'model model::TypeVersionDefinition, from_type: LangType<'model>, result_type_args: Vec<LangType<'model>>) -> Result<LangStmt<'model>, GeneratorError> where
+	Gen : TypeGenerator<'model>
+{
+	let version = &ver_type.version;
+	let is_new_version = ver_type.explicit_version && ver_type.version != *prev_ver;
+
+	let prev_field_name = |field_name: &'model String, field: &'model model::FieldInfo| match &field.renamed_from {
+		Some(renamed_from) if is_new_version => renamed_from,
+		_ => field_name,
+	};
+
+	Ok(match type_kind {
+		VersionedTypeKind::Struct => {
+			let mut fields = Vec::new();
+
+			for (prev_name, prev_field) in prev_ver_type.fields() {
+				let (field_name, _) = ver_type.ver_type.fields().iter()
+					.find(|(field_name, field)| prev_field_name(field_name, field) == prev_name)
+					.ok_or(GeneratorError::CouldNotGenerateType)?;
+
+				let obj_value = LangExpr::Identifier(Gen::Lang::convert_prev_param_name().to_string());
+				let value_expr = LangExpr::StructField(gen.type_def().name(), version.clone(), field_name, Box::new(obj_value));
+				let conv_value = gen.build_downgrade(prev_ver, version, &prev_field.field_type, ConvertParam::Expression(value_expr))?;
+
+				fields.push((prev_name, conv_value));
+			}
+
+			LangStmt::Expr(vec!(),
+				Some(LangExpr::CreateStruct(gen.type_def().name(), prev_ver.clone(), result_type_args, fields))
+			)
+		},
+		VersionedTypeKind::Enum => {
+			let mut cases = Vec::new();
+
+			for (field_name, field) in ver_type.ver_type.fields() {
+				let value_expr = LangExpr::Identifier(field_name.clone());
+				let conv_value = gen.build_downgrade(prev_ver, version, &field.field_type, ConvertParam::Expression(value_expr))?;
+				let enum_value = LangExpr::CreateEnum(gen.type_def().name(), prev_ver.clone(), result_type_args.clone(), prev_field_name(field_name, field), Box::new(conv_value));
+
+				cases.push(MatchCase {
+					binding_name: field_name.clone(),
+					case_name: field_name.clone(),
+					body: LangStmt::Expr(vec!(), Some(enum_value)),
+				});
+			}
+
+			LangStmt::MatchEnum {
+				value: LangExpr::Identifier(Gen::Lang::convert_prev_param_name().to_string()),
+				value_type: from_type,
+				cases,
+			}
+		},
+	})
+}
+
+// Each version is read using its codec and converted to the latest version through each FromPreviousVersion operation.
+fn build_read_any_version_operation<'model, Gen>(gen: &Gen, version: &BigUint) -> Result<OperationInfo<'model>, GeneratorError> where
+	Gen : TypeGenerator<'model>
+{
+	let t = model::Type { name: gen.type_def().name().clone(), args: build_type_params_as_args(gen.type_def()) };
+	let obj_type = gen.build_type(version, &t)?;
+
+	let mut params = Vec::new();
+	let mut conv_type_args = Vec::new();
+	let mut conv_args = Vec::new();
+	for param in gen.type_def().type_params() {
+		let param_type = LangType::TypeParameter(param.clone());
+
+		params.push((Gen::Lang::codec_codec_param_name(param), LangType::Codec(Box::new(param_type.clone()))));
+		conv_type_args.push(param_type.clone());
+		conv_type_args.push(param_type.clone());
+		conv_args.push(param_type);
+	}
+
+	let versions = gen.type_def().versions().map(|ver| ver.version).collect::<Vec<_>>();
+	let mut cases = Vec::new();
+
+	for (index, ver) in versions.iter().enumerate() {
+		let codec = gen.build_codec(gen.build_type(ver, &t)?)?;
+		let mut value = LangExpr::CodecRead { codec: Box::new(codec) };
+
+		for (prev_ver, next_ver) in versions[index..].iter().zip(&versions[index + 1..]) {
+			let converter = LangExpr::InvokeOperation(
+				Operation::FromPreviousVersion(prev_ver.clone()),
+				OperationTarget::VersionedType(gen.type_def().name(), next_ver.clone()),
+				conv_type_args.clone(),
+				conv_args.iter().cloned().map(LangExpr::IdentityConverter).collect(),
+			);
+
+			value = LangExpr::InvokeConverter {
+				converter: Box::new(converter),
+				value: Box::new(value),
+			};
+		}
+
+		cases.push((ver.clone(), LangStmt::Expr(vec!(), Some(value))));
+	}
+
+	Ok(OperationInfo {
+		operation: Operation::ReadAnyVersion,
+		version: version.clone(),
+		type_params: gen.type_def().type_params().clone(),
+		params,
+		result: obj_type.clone(),
+		implementation: LangExprStmt::ReadAnyVersion {
+			t: obj_type,
+			read: Box::new(LangStmt::MatchDiscriminator {
+				value: LangExpr::Identifier(Gen::Lang::version_param_name().to_string()),
+				cases,
+			}),
+		},
+	})
+}
 
 fn codec_read_implementation<'model, Gen>(gen: &Gen, t: LangType<'model>) -> Result<LangStmt<'model>, GeneratorError> where
 	Gen : TypeGenerator<'model>
@@ -1336,10 +1736,12 @@ pub trait GeneratorFactory<'a> {
 	type ConstGen: ConstGenerator<'a>;
 	type VersionedTypeGen: TypeGeneratorGenerate<'a>;
 	type InterfaceTypeGen: TypeGeneratorGenerate<'a>;
+	type FormatGen: FormatGenerator<'a>;
 
 	fn create_constant_generator(&'a mut self, constant: Named<'a, model::Constant>) -> Result<Self::ConstGen, GeneratorError>;
 	fn create_versioned_type_generator(&'a mut self, t: Named<'a, model::VersionedTypeDefinitionData>) -> Result<Self::VersionedTypeGen, GeneratorError>;
 	fn create_interface_type_generator(&'a mut self, t: Named<'a, model::InterfaceTypeDefinitionData>) -> Result<Self::InterfaceTypeGen, GeneratorError>;
+	fn create_format_generator(&'a mut self, format: Named<'a, model::FileFormat>) -> Result<Self::FormatGen, GeneratorError>;
 }
 
 pub trait CodeGenerator<'a> {
@@ -1367,8 +1769,14 @@ impl <'a, TImpl: for<'b> GeneratorFactory<'b>> CodeGenerator<'a> for TImpl {
 			}
 		}
 
+		for format in model.formats() {
+			let mut gen = self.create_format_generator(format)?;
+			gen.generate()?;
+		}
+
 		Ok(())
 	}
 }
 
-
+#[cfg(test)]
+mod tests;
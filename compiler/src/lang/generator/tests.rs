@@ -0,0 +1,284 @@
+use super::*;
+use crate::model;
+use crate::parser::parse_model;
+use crate::source::SourceFile;
+use model::Named;
+use num_bigint::BigUint;
+
+struct TestLanguage;
+
+impl GeneratorNameMapping for TestLanguage {
+    fn convert_prev_type_param(param: &str) -> String {
+        format!("{}_1", param)
+    }
+
+    fn convert_current_type_param(param: &str) -> String {
+        format!("{}_2", param)
+    }
+
+    fn convert_conv_param_name(param: &str) -> String {
+        format!("{}_conv", param)
+    }
+
+    fn convert_prev_param_name() -> &'static str {
+        "prev"
+    }
+
+    fn codec_write_value_name() -> &'static str {
+        "value"
+    }
+
+    fn codec_codec_param_name(param: &str) -> String {
+        format!("{}_codec", param)
+    }
+
+    fn format_writer_name() -> &'static str {
+        "writer"
+    }
+
+    fn format_reader_name() -> &'static str {
+        "reader"
+    }
+
+    fn version_param_name() -> &'static str {
+        "version"
+    }
+
+    fn connection_name() -> &'static str {
+        "connection"
+    }
+
+    fn object_id_name() -> &'static str {
+        "id"
+    }
+
+    fn constant_version_name(version: &BigUint) -> String {
+        format!("v{}", version)
+    }
+}
+
+struct TestTypeGenerator<'model> {
+    model: &'model model::Verilization,
+    type_def: Named<'model, model::VersionedTypeDefinitionData>,
+    scope: model::Scope<'model>,
+}
+
+impl <'model> Generator<'model> for TestTypeGenerator<'model> {
+    type Lang = TestLanguage;
+
+    fn model(&self) -> &'model model::Verilization {
+        self.model
+    }
+
+    fn scope(&self) -> &model::Scope<'model> {
+        &self.scope
+    }
+}
+
+impl <'model> TypeGenerator<'model> for TestTypeGenerator<'model> {
+    type TypeDefinition = model::VersionedTypeDefinitionData;
+
+    fn type_def(&self) -> Named<'model, Self::TypeDefinition> {
+        self.type_def
+    }
+
+    fn write_header(&mut self) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+    fn write_version_header(&mut self, _t: LangType<'model>) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+    fn write_operation(&mut self, _operation: OperationInfo<'model>) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+    fn write_version_footer(&mut self) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+    fn write_footer(&mut self) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+}
+
+const SCHEMA: &str = "
+version 2;
+package a;
+
+import a.Inner as Alias;
+
+extern u32 {
+    literal {
+        integer [0, 4294967295];
+    }
+}
+
+struct Unchanged {
+    version 1 {
+        x: u32;
+    }
+    version 2 {
+        x: u32;
+    }
+}
+
+struct FieldAdded {
+    version 1 {
+        x: u32;
+    }
+    version 2 {
+        x: u32;
+        y: u32 = 5;
+    }
+}
+
+struct FieldRemoved {
+    version 1 {
+        x: u32;
+        y: u32;
+    }
+    version 2 {
+        x: u32;
+        removed y;
+    }
+}
+
+struct FieldRenamed {
+    version 1 {
+        x: u32;
+    }
+    version 2 {
+        z: u32 renamed from x;
+    }
+}
+
+struct Inner {
+    version 1 {
+        x: u32;
+    }
+}
+
+struct Respelled {
+    version 1 {
+        x: Inner;
+        y: Inner;
+    }
+    version 2 {
+        x: a.Inner;
+        y: Alias;
+    }
+}
+
+enum CaseAdded {
+    version 1 {
+        x: u32;
+    }
+    version 2 {
+        x: u32;
+        y: u32;
+    }
+}
+";
+
+// Builds the conversion between versions 1 and 2 of a type.
+fn check_conversion(type_name: &str, is_downgrade: bool, check: impl FnOnce(&LangExprStmt)) {
+    let model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(SCHEMA))).unwrap();
+
+    let (type_kind, type_def) = match model.get_type(&model::QualifiedName::from_parts(&["a"], type_name)) {
+        Some(model::NamedTypeDefinition::StructType(type_def)) => (VersionedTypeKind::Struct, type_def),
+        Some(model::NamedTypeDefinition::EnumType(type_def)) => (VersionedTypeKind::Enum, type_def),
+        _ => panic!("Type {} is not a struct or enum", type_name),
+    };
+
+    let gen = TestTypeGenerator {
+        model: &model,
+        type_def,
+        scope: type_def.scope(),
+    };
+
+    let prev_ver = BigUint::from(1u32);
+    let ver_type = type_def.versioned(&BigUint::from(2u32)).unwrap();
+    let prev_ver_type = type_def.versioned(&prev_ver).unwrap();
+
+    let op =
+        if is_downgrade {
+            assert_eq!(
+                has_automatic_downgrade(gen.scope(), ver_type.ver_type, prev_ver_type.ver_type),
+                !matches!(type_name, "FieldAdded" | "FieldRemoved" | "CaseAdded"),
+                "Automatic downgrade of {}", type_name
+            );
+
+            Operation::ToPreviousVersion(prev_ver.clone())
+        }
+        else {
+            Operation::FromPreviousVersion(prev_ver.clone())
+        };
+
+    let op = build_converter_operation_common(&gen, op, type_kind, &ver_type, &prev_ver, prev_ver_type.ver_type).unwrap();
+    check(&op.implementation);
+}
+
+fn check_downgrade(type_name: &str, check: impl FnOnce(&LangExprStmt)) {
+    check_conversion(type_name, true, check)
+}
+
+fn is_user_converter(implementation: &LangExprStmt) -> bool {
+    matches!(implementation, LangExprStmt::Expr(LangExpr::InvokeUserConverter { .. }))
+}
+
+#[test]
+fn unchanged_fields_are_downgraded_automatically() {
+    check_downgrade("Unchanged", |implementation| assert!(matches!(implementation, LangExprStmt::CreateConverter { .. })));
+}
+
+#[test]
+fn added_field_needs_user_downgrade() {
+    check_downgrade("FieldAdded", |implementation| assert!(is_user_converter(implementation)));
+}
+
+#[test]
+fn removed_field_needs_user_downgrade() {
+    check_downgrade("FieldRemoved", |implementation| assert!(is_user_converter(implementation)));
+}
+
+#[test]
+fn added_case_needs_user_downgrade() {
+    check_downgrade("CaseAdded", |implementation| assert!(is_user_converter(implementation)));
+}
+
+#[test]
+fn renamed_field_is_copied_to_old_name() {
+    check_downgrade("FieldRenamed", |implementation| {
+        let body = match implementation {
+            LangExprStmt::CreateConverter { body, .. } => body,
+            _ => panic!("Renamed field was not downgraded automatically"),
+        };
+
+        let fields = match &**body {
+            LangStmt::Expr(exprs, Some(LangExpr::CreateStruct(_, version, _, fields))) if exprs.is_empty() && *version == BigUint::from(1u32) => fields,
+            _ => panic!("Downgrade does not create version 1"),
+        };
+
+        assert_eq!(fields.len(), 1);
+        let (field_name, value) = &fields[0];
+        assert_eq!(field_name.as_str(), "x");
+        assert!(matches!(value, LangExpr::StructField(_, version, name, _) if *version == BigUint::from(2u32) && name.as_str() == "z"), "{:?}", value);
+    });
+}
+
+#[test]
+fn types_are_compared_after_resolving_names() {
+    let model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(SCHEMA))).unwrap();
+    let type_def = match model.get_type(&model::QualifiedName::from_parts(&["a"], "Respelled")) {
+        Some(model::NamedTypeDefinition::StructType(type_def)) => type_def,
+        _ => panic!("Respelled is not a struct"),
+    };
+    let ver_type = type_def.versioned(&BigUint::from(2u32)).unwrap();
+    let prev_ver_type = type_def.versioned(&BigUint::from(1u32)).unwrap();
+    assert!(has_automatic_conversion(&type_def.scope(), VersionedTypeKind::Struct, ver_type.ver_type, prev_ver_type.ver_type));
+
+    check_conversion("Respelled", false, |implementation| assert!(matches!(implementation, LangExprStmt::CreateConverter { .. })));
+    check_downgrade("Respelled", |implementation| assert!(matches!(implementation, LangExprStmt::CreateConverter { .. })));
+}
@@ -5,6 +5,7 @@ pub mod dummy_generator;
 
 use crate::model;
 use std::ffi::OsString;
+use std::fmt;
 use std::io;
 use std::path::Path;
 use num_bigint::BigUint;
@@ -35,6 +36,88 @@ pub enum GeneratorError {
 	TypeMismatch,
     TypeNotFinal,
 	InvalidTypeInExternLiteral,
+	InvalidTypeForBoolean,
+	InvalidTypeForFloat,
+	InvalidTypeForMap,
+	SequenceLengthMismatch(model::QualifiedName),
+}
+
+impl GeneratorError {
+	/// Gets a stable code that identifies the kind of error.
+	pub fn code(&self) -> &'static str {
+		match self {
+			GeneratorError::IOError(_) => "G0001",
+			GeneratorError::UnknownLanguage(_) => "G0002",
+			GeneratorError::InvalidOptions(_) => "G0003",
+			GeneratorError::UnmappedPackage(_) => "G0004",
+			GeneratorError::CouldNotFind(_) => "G0005",
+			GeneratorError::CouldNotFindVersion(..) => "G0006",
+			GeneratorError::CouldNotResolveTypeParameter(_) => "G0007",
+			GeneratorError::TypeCannotBeSequence(_) => "G0008",
+			GeneratorError::TypeDoesNotHaveCase(..) => "G0009",
+			GeneratorError::IncorrectCaseArity(..) => "G0010",
+			GeneratorError::ArityMismatch(..) => "G0011",
+			GeneratorError::RecordLiteralNotForStruct => "G0012",
+			GeneratorError::ExternTypeDoesNotHaveRecordLiteral(_) => "G0013",
+			GeneratorError::CouldNotFindRecordField(..) => "G0014",
+			GeneratorError::CouldNotGenerateType => "G0015",
+			GeneratorError::InvalidTypeForConstant => "G0016",
+			GeneratorError::InvalidTypeForCodec => "G0017",
+			GeneratorError::InvalidTypeForIntValue => "G0018",
+			GeneratorError::InvalidTypeForString => "G0019",
+			GeneratorError::TypeMismatch => "G0020",
+			GeneratorError::TypeNotFinal => "G0021",
+			GeneratorError::InvalidTypeInExternLiteral => "G0022",
+			GeneratorError::InvalidTypeForBoolean => "G0023",
+			GeneratorError::InvalidTypeForFloat => "G0024",
+			GeneratorError::InvalidTypeForMap => "G0025",
+			GeneratorError::SequenceLengthMismatch(_) => "G0026",
+		}
+	}
+}
+
+impl fmt::Display for GeneratorError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			GeneratorError::IOError(err) => write!(f, "{}", err),
+			GeneratorError::UnknownLanguage(lang) => write!(f, "Unknown language {}.", lang),
+			GeneratorError::InvalidOptions(message) => write!(f, "Invalid options. {}.", message),
+			GeneratorError::UnmappedPackage(pkg) => write!(f, "Package {} is not mapped to an output package.", pkg),
+			GeneratorError::CouldNotFind(name) => write!(f, "Could not find {}.", name),
+			GeneratorError::CouldNotFindVersion(name, version) => write!(f, "Could not find version {} of {}.", version, name),
+			GeneratorError::CouldNotResolveTypeParameter(param) => write!(f, "Could not resolve type parameter {}.", param),
+			GeneratorError::TypeCannotBeSequence(name) => write!(f, "Type {} does not have a sequence literal.", name),
+			GeneratorError::TypeDoesNotHaveCase(name, Some(version), case_name) => write!(f, "Version {} of type {} does not have a case named {}.", version, name, case_name),
+			GeneratorError::TypeDoesNotHaveCase(name, None, case_name) => write!(f, "Type {} does not have a case named {}.", name, case_name),
+			GeneratorError::IncorrectCaseArity(name, case_name) => write!(f, "Case {} of type {} was given the wrong number of arguments.", case_name, name),
+			GeneratorError::ArityMismatch(expected, actual) => write!(f, "Expected {} type arguments, but {} were given.", expected, actual),
+			GeneratorError::RecordLiteralNotForStruct => write!(f, "Record literals can only be used for struct types."),
+			GeneratorError::ExternTypeDoesNotHaveRecordLiteral(name) => write!(f, "Type {} does not have a record literal.", name),
+			GeneratorError::CouldNotFindRecordField(name, Some(version), field_name) => write!(f, "Version {} of type {} does not have a field named {}.", version, name, field_name),
+			GeneratorError::CouldNotFindRecordField(name, None, field_name) => write!(f, "Type {} does not have a field named {}.", name, field_name),
+			GeneratorError::CouldNotGenerateType => write!(f, "Could not generate type."),
+			GeneratorError::InvalidTypeForConstant => write!(f, "Invalid type for constant."),
+			GeneratorError::InvalidTypeForCodec => write!(f, "Invalid type for codec."),
+			GeneratorError::InvalidTypeForIntValue => write!(f, "Type cannot be used for an integer value."),
+			GeneratorError::InvalidTypeForString => write!(f, "Type cannot be used for a string value."),
+			GeneratorError::TypeMismatch => write!(f, "Type mismatch."),
+			GeneratorError::TypeNotFinal => write!(f, "Type is not final."),
+			GeneratorError::InvalidTypeInExternLiteral => write!(f, "Invalid type in extern literal."),
+			GeneratorError::InvalidTypeForBoolean => write!(f, "Type cannot be used for a boolean value."),
+			GeneratorError::InvalidTypeForFloat => write!(f, "Type cannot be used for a float value."),
+			GeneratorError::InvalidTypeForMap => write!(f, "Type cannot be used for a map value."),
+			GeneratorError::SequenceLengthMismatch(name) => write!(f, "Sequence literal for type {} has the wrong number of elements.", name),
+		}
+	}
+}
+
+impl std::error::Error for GeneratorError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			GeneratorError::IOError(err) => err.source(),
+			_ => None,
+		}
+	}
 }
 
 impl From<io::Error> for GeneratorError {
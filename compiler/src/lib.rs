@@ -4,6 +4,12 @@ pub mod util;
 pub mod model;
 pub mod lang;
 pub mod parser;
+pub mod source;
+pub mod format;
+pub mod dynamic;
+pub mod symbols;
+pub mod compat;
+pub mod lockfile;
 mod type_check;
 mod model_loader;
 mod verror;
@@ -11,7 +17,7 @@ mod memory_output_handler;
 
 
 pub use memory_output_handler::MemoryOutputHandler;
-pub use model_loader::load_all_models;
+pub use model_loader::{load_all_models, load_models_with_errors};
 
 pub use verror::VError;
 pub use type_check::TypeCheckError;
@@ -23,5 +29,5 @@ mod file_output_handler;
 pub use file_output_handler::FileOutputHandler;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use model_loader::load_files;
+pub use model_loader::{load_files, check_lockfile};
 
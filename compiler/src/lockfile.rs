@@ -0,0 +1,276 @@
+//! Lockfiles record the encoding of every version of each type so that published versions are not changed by accident.
+//!
+//! Each version of a struct or enum, including versions that are copied from an earlier version, is recorded with a
+//! fingerprint of its encoding. The fingerprint includes the types of the fields, so a change to a type also changes
+//! the versions of other types that contain it. Names of fields and cases are not part of the encoding, so they can be
+//! renamed without changing the fingerprint.
+
+use crate::model::{self, QualifiedName, Span};
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The name of the lockfile.
+pub const LOCKFILE_NAME: &str = "verilization.lock";
+
+#[derive(Debug)]
+pub enum LockfileError {
+	InvalidEntry(usize),
+	VersionChanged(QualifiedName, BigUint, Span),
+	VersionRemoved(QualifiedName, BigUint),
+}
+
+impl LockfileError {
+	/// Gets the location in the source that caused the error.
+	pub fn span(&self) -> Option<&Span> {
+		match self {
+			LockfileError::VersionChanged(_, _, span) => Some(span),
+			_ => None,
+		}
+	}
+
+	/// Gets a stable code that identifies the kind of error.
+	pub fn code(&self) -> &'static str {
+		match self {
+			LockfileError::InvalidEntry(_) => "L0001",
+			LockfileError::VersionChanged(..) => "L0002",
+			LockfileError::VersionRemoved(..) => "L0003",
+		}
+	}
+}
+
+impl std::error::Error for LockfileError {}
+
+impl fmt::Display for LockfileError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LockfileError::InvalidEntry(line) => write!(f, "Invalid entry on line {} of the lockfile.", line),
+			LockfileError::VersionChanged(name, version, _) => write!(f, "Version {} of type {} does not match the lockfile.", version, name),
+			LockfileError::VersionRemoved(name, version) => write!(f, "Version {} of type {} is in the lockfile but is not defined.", version, name),
+		}
+	}
+}
+
+// Writes a canonical description of the encoding of a type.
+// Names of structs, enums, fields and cases are not included because they do not affect the encoding.
+// Recursive references are written as the number of types between the reference and the type it refers to.
+struct ShapeWriter<'a> {
+	model: &'a model::Verilization,
+	version: &'a BigUint,
+	stack: Vec<&'a QualifiedName>,
+	out: String,
+}
+
+impl <'a> ShapeWriter<'a> {
+	fn write_versioned_type(&mut self, t: model::Named<'a, model::VersionedTypeDefinitionData>, keyword: &str) {
+		let ver_type = match t.versioned(self.version) {
+			Some(ver) => ver.ver_type,
+			None => {
+				self.out.push_str("missing");
+				return;
+			},
+		};
+
+		let scope = t.scope();
+		self.stack.push(t.name());
+		self.out.push_str(keyword);
+		self.out.push('{');
+		for (_, field) in ver_type.fields() {
+			self.write_type(&scope, &field.field_type);
+			self.out.push(';');
+		}
+		self.out.push('}');
+		self.stack.pop();
+	}
+
+	fn write_args(&mut self, scope: &model::Scope, args: &[model::TypeArgument]) {
+		self.out.push('(');
+		for (index, arg) in args.iter().enumerate() {
+			if index > 0 {
+				self.out.push(',');
+			}
+			match arg {
+				model::TypeArgument::Type(t) => self.write_type(scope, t),
+				model::TypeArgument::Integer(n) => self.out.push_str(&n.to_string()),
+			}
+		}
+		self.out.push(')');
+	}
+
+	fn write_type(&mut self, scope: &model::Scope, t: &model::Type) {
+		let name = match scope.lookup(t.name.clone()) {
+			model::ScopeLookup::TypeParameter(name) => {
+				let index = scope.type_params().iter().position(|param| **param == name).unwrap_or_default();
+				self.out.push_str(&format!("${}", index));
+				return;
+			},
+			model::ScopeLookup::NamedType(name) => name,
+		};
+
+		if let Some(index) = self.stack.iter().rposition(|other| **other == name) {
+			self.out.push_str(&format!("rec{}", self.stack.len() - 1 - index));
+			self.write_args(scope, &t.args);
+			return;
+		}
+
+		match self.model.get_type(&name) {
+			Some(model::NamedTypeDefinition::StructType(type_def)) => {
+				self.write_args(scope, &t.args);
+				self.write_versioned_type(type_def, "struct");
+			},
+			Some(model::NamedTypeDefinition::EnumType(type_def)) => {
+				self.write_args(scope, &t.args);
+				self.write_versioned_type(type_def, "enum");
+			},
+			Some(model::NamedTypeDefinition::ExternType(_)) => {
+				self.out.push_str(&format!("extern:{}", name));
+				self.write_args(scope, &t.args);
+			},
+			Some(model::NamedTypeDefinition::InterfaceType(_)) | None => self.out.push_str(&format!("unknown:{}", name)),
+		}
+	}
+}
+
+fn fingerprint(model: &model::Verilization, t: model::Named<model::VersionedTypeDefinitionData>, version: &BigUint, keyword: &str) -> String {
+	let mut writer = ShapeWriter {
+		model,
+		version,
+		stack: Vec::new(),
+		out: String::new(),
+	};
+	writer.write_versioned_type(t, keyword);
+
+	Sha256::digest(writer.out.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Computes the fingerprint of every version of each struct and enum in a model.
+pub fn model_fingerprints(model: &model::Verilization) -> BTreeMap<QualifiedName, BTreeMap<BigUint, String>> {
+	let mut fingerprints = BTreeMap::new();
+	for t in model.types() {
+		let (type_def, keyword) = match t {
+			model::NamedTypeDefinition::StructType(type_def) => (type_def, "struct"),
+			model::NamedTypeDefinition::EnumType(type_def) => (type_def, "enum"),
+			_ => continue,
+		};
+
+		let versions = type_def.versions()
+			.map(|ver| {
+				let fingerprint = fingerprint(model, type_def, &ver.version, keyword);
+				(ver.version, fingerprint)
+			})
+			.collect();
+
+		fingerprints.insert(type_def.name().clone(), versions);
+	}
+	fingerprints
+}
+
+/// The fingerprints of the versions that have been locked.
+#[derive(Clone, Debug, Default)]
+pub struct Lockfile {
+	versions: BTreeMap<QualifiedName, BTreeMap<BigUint, String>>,
+}
+
+impl Lockfile {
+	pub fn new() -> Self {
+		Lockfile::default()
+	}
+
+	/// Parses the content of a lockfile.
+	///
+	/// Each line contains the name of a type, a version and a fingerprint. Empty lines and lines starting with # are ignored.
+	pub fn parse(content: &str) -> Result<Lockfile, LockfileError> {
+		let mut lockfile = Lockfile::new();
+
+		for (index, line) in content.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let parts: Vec<&str> = line.split_whitespace().collect();
+			let entry = match parts.as_slice() {
+				[name, version, fingerprint] => QualifiedName::from_str(name).zip(version.parse::<BigUint>().ok()).map(|(name, version)| (name, version, fingerprint)),
+				_ => None,
+			};
+
+			let (name, version, fingerprint) = entry.ok_or(LockfileError::InvalidEntry(index + 1))?;
+			lockfile.versions.entry(name).or_default().insert(version, fingerprint.to_string());
+		}
+
+		Ok(lockfile)
+	}
+
+	/// Checks that every locked version is still defined with the same encoding.
+	///
+	/// Versions that are not locked are not checked.
+	pub fn check(&self, model: &model::Verilization) -> Result<(), Vec<LockfileError>> {
+		let fingerprints = model_fingerprints(model);
+		let mut errors = Vec::new();
+
+		for (name, versions) in &self.versions {
+			for (version, locked_fingerprint) in versions {
+				match fingerprints.get(name).and_then(|type_fingerprints| type_fingerprints.get(version)) {
+					Some(fingerprint) if fingerprint == locked_fingerprint => (),
+					Some(_) => errors.push(LockfileError::VersionChanged(name.clone(), version.clone(), version_span(model, name, version))),
+					None => errors.push(LockfileError::VersionRemoved(name.clone(), version.clone())),
+				}
+			}
+		}
+
+		if errors.is_empty() { Ok(()) } else { Err(errors) }
+	}
+
+	/// Finds the versions in a model that are not locked.
+	pub fn unlocked_versions(&self, model: &model::Verilization) -> Vec<(QualifiedName, BigUint)> {
+		model_fingerprints(model).into_iter()
+			.flat_map(|(name, versions)| versions.into_keys().map(move |version| (name.clone(), version)))
+			.filter(|(name, version)| !self.versions.get(name).is_some_and(|versions| versions.contains_key(version)))
+			.collect()
+	}
+
+	/// Adds the versions of a model that are not locked.
+	///
+	/// Locked versions are never replaced, so the lockfile is only updated if every locked version still matches.
+	pub fn update(&mut self, model: &model::Verilization) -> Result<(), Vec<LockfileError>> {
+		self.check(model)?;
+
+		for (name, versions) in model_fingerprints(model) {
+			let locked_versions = self.versions.entry(name).or_default();
+			for (version, fingerprint) in versions {
+				locked_versions.entry(version).or_insert(fingerprint);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Lockfiles are written in the format read by `Lockfile::parse`.
+impl fmt::Display for Lockfile {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "# This file is updated by verilization lock --update. Locked versions must not be changed.")?;
+		for (name, versions) in &self.versions {
+			for (version, fingerprint) in versions {
+				writeln!(f, "{} {} {}", name, version, fingerprint)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+fn version_span(model: &model::Verilization, name: &QualifiedName, version: &BigUint) -> Span {
+	let t = model.get_type(name).expect("Version fingerprint should refer to a type.");
+	let ver_span = match t {
+		model::NamedTypeDefinition::StructType(type_def) | model::NamedTypeDefinition::EnumType(type_def) =>
+			type_def.versioned(version).map(|ver| ver.ver_type.span()),
+		_ => None,
+	};
+
+	ver_span.unwrap_or_else(|| t.span()).clone()
+}
+
+
+#[cfg(test)]
+mod tests;
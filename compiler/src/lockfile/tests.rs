@@ -0,0 +1,136 @@
+use crate::lockfile::{Lockfile, LockfileError, model_fingerprints};
+use crate::model;
+use crate::parser::parse_model;
+use crate::source::SourceFile;
+use num_bigint::BigUint;
+
+const EXTERNS: &str = "
+version 1;
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+extern list<A> {
+    literal {
+        sequence A;
+    }
+}
+";
+
+const SCHEMA: &str = "
+version 2;
+package a;
+struct Person {
+    version 1 {
+        name: Name;
+        friends: list(Person);
+    }
+}
+struct Name {
+    version 1 {
+        length: nat;
+    }
+}
+struct Pair<A, B> {
+    version 1 {
+        left: A;
+        right: B;
+    }
+}
+";
+
+fn load(source: &str) -> model::Verilization {
+    let mut model = parse_model(&SourceFile::new(String::from("test.verilization"), String::from(source))).unwrap();
+    model.merge(parse_model(&SourceFile::new(String::from("externs.verilization"), String::from(EXTERNS))).unwrap()).unwrap();
+    model
+}
+
+fn locked(source: &str) -> Lockfile {
+    let mut lockfile = Lockfile::new();
+    lockfile.update(&load(source)).unwrap();
+    lockfile
+}
+
+fn name(name: &str) -> model::QualifiedName {
+    model::QualifiedName::from_str(name).unwrap()
+}
+
+#[test]
+fn parse_round_trip() {
+    let lockfile = locked(SCHEMA);
+    let content = lockfile.to_string();
+    assert_eq!(content.lines().count(), 7);
+    assert!(content.lines().nth(1).unwrap().starts_with("a.Name 1 "));
+
+    let parsed = Lockfile::parse(&content).unwrap();
+    assert_eq!(parsed.to_string(), content);
+    assert!(parsed.check(&load(SCHEMA)).is_ok());
+}
+
+#[test]
+fn parse_invalid_entry() {
+    let content = "# comment\n\na.Name 1 abc\na.Name one abc\n";
+    assert!(matches!(Lockfile::parse(content), Err(LockfileError::InvalidEntry(4))));
+}
+
+#[test]
+fn fingerprint_ignores_names_of_parameters() {
+    let renamed = SCHEMA.replace("<A, B>", "<X, Y>").replace("left: A;", "left: X;").replace("right: B;", "right: Y;");
+    assert_eq!(model_fingerprints(&load(SCHEMA)), model_fingerprints(&load(&renamed)));
+
+    let swapped = SCHEMA.replace("left: A;", "left: B;").replace("right: B;", "right: A;");
+    assert_ne!(model_fingerprints(&load(SCHEMA)).get(&name("a.Pair")), model_fingerprints(&load(&swapped)).get(&name("a.Pair")));
+}
+
+#[test]
+fn fingerprint_ignores_names_of_fields() {
+    let lockfile = locked(SCHEMA);
+    let renamed = SCHEMA.replace("length: nat;", "size: nat;");
+    assert!(lockfile.check(&load(&renamed)).is_ok());
+
+    let swapped = SCHEMA.replace("name: Name;\n        friends: list(Person);", "friends: list(Person);\n        name: Name;");
+    assert!(lockfile.check(&load(&swapped)).is_err());
+}
+
+#[test]
+fn changed_versions() {
+    let lockfile = locked(SCHEMA);
+
+    // Changing Name also changes the versions of Person that contain it.
+    let changed = SCHEMA.replace("length: nat;", "length: list(nat);");
+    let errors: Vec<String> = lockfile.check(&load(&changed)).unwrap_err().iter().map(|err| format!("{} {}", err.code(), err)).collect();
+    assert_eq!(errors, vec!(
+        "L0002 Version 1 of type a.Name does not match the lockfile.",
+        "L0002 Version 2 of type a.Name does not match the lockfile.",
+        "L0002 Version 1 of type a.Person does not match the lockfile.",
+        "L0002 Version 2 of type a.Person does not match the lockfile.",
+    ));
+
+    let removed = SCHEMA.replace("struct Pair", "final struct Pair");
+    let errors: Vec<String> = lockfile.check(&load(&removed)).unwrap_err().iter().map(|err| format!("{} {}", err.code(), err)).collect();
+    assert_eq!(errors, vec!(
+        "L0003 Version 2 of type a.Pair is in the lockfile but is not defined.",
+    ));
+}
+
+#[test]
+fn update_adds_versions() {
+    let mut lockfile = locked(SCHEMA);
+
+    let next = SCHEMA.replace("version 2;", "version 3;");
+    assert_eq!(lockfile.unlocked_versions(&load(&next)), vec!(
+        (name("a.Name"), BigUint::from(3u32)),
+        (name("a.Pair"), BigUint::from(3u32)),
+        (name("a.Person"), BigUint::from(3u32)),
+    ));
+
+    lockfile.update(&load(&next)).unwrap();
+    assert!(lockfile.unlocked_versions(&load(&next)).is_empty());
+    assert_eq!(lockfile.to_string().lines().count(), 10);
+
+    let changed = next.replace("length: nat;", "length: list(nat);");
+    assert!(lockfile.update(&load(&changed)).is_err());
+    assert_eq!(lockfile.to_string().lines().count(), 10);
+    assert!(lockfile.check(&load(&next)).is_ok());
+}
@@ -6,6 +6,8 @@ use std::cmp::{Ord, Ordering, PartialEq, PartialOrd};
 use lazy_static::lazy_static;
 use std::marker::PhantomData;
 
+pub use crate::source::Span;
+
 /// A dot-separated package.
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub struct PackageName {
@@ -141,51 +143,172 @@ impl PartialOrd for QualifiedName {
 	}
 }
 
+/// A type or constant imported into the scope of a file.
+#[derive(Clone, Debug)]
+pub struct Import {
+	pub name: QualifiedName,
+	pub span: Span,
+}
+
 #[derive(Debug)]
 pub enum ModelError {
-	DuplicateConstant(QualifiedName),
-	DuplicateType(QualifiedName),
-	DuplicateVersion(QualifiedName, BigUint),
-	DuplicateField(QualifiedName, BigUint, String),
-	DuplicateMethod(QualifiedName, BigUint, String),
-	DuplicateLiteralInteger(QualifiedName),
-	DuplicateLiteralString(QualifiedName),
-	DuplicateLiteralSequence(QualifiedName),
-	DuplicateLiteralCase(QualifiedName, String),
-	DuplicateLiteralRecord(QualifiedName),
-	DuplicateLiteralRecordField(QualifiedName, String),
-	DuplicateFieldValue(String),
-	DuplicateTypeParameter(QualifiedName, Option<(BigUint, String)>, String),
-	DuplicateMethodParameter(QualifiedName, BigUint, String, String),
-}
+	DuplicateConstant(QualifiedName, Span),
+	DuplicateType(QualifiedName, Span),
+	DuplicateVersion(QualifiedName, BigUint, Span),
+	DuplicateField(QualifiedName, BigUint, String, Span),
+	DuplicateMethod(QualifiedName, BigUint, String, Span),
+	DuplicateLiteralInteger(QualifiedName, Span),
+	DuplicateLiteralString(QualifiedName, Span),
+	DuplicateLiteralBoolean(QualifiedName, Span),
+	DuplicateLiteralFloat(QualifiedName, Span),
+	DuplicateLiteralMap(QualifiedName, Span),
+	DuplicateLiteralSequence(QualifiedName, Span),
+	DuplicateLiteralCase(QualifiedName, String, Span),
+	DuplicateLiteralRecord(QualifiedName, Span),
+	DuplicateLiteralRecordField(QualifiedName, String, Span),
+	DuplicateFieldValue(String, Option<Span>),
+	DuplicateTypeParameter(QualifiedName, Option<Box<(BigUint, String)>>, String, Span),
+	DuplicateMethodParameter(QualifiedName, Box<(BigUint, String)>, String, Span),
+	DuplicateImport(String, Span),
+	DuplicateFormat(QualifiedName, Span),
+}
+
+impl ModelError {
+	/// Gets the location in the source that caused the error.
+	pub fn span(&self) -> Option<&Span> {
+		Some(match self {
+			ModelError::DuplicateConstant(_, span) => span,
+			ModelError::DuplicateType(_, span) => span,
+			ModelError::DuplicateVersion(_, _, span) => span,
+			ModelError::DuplicateField(_, _, _, span) => span,
+			ModelError::DuplicateMethod(_, _, _, span) => span,
+			ModelError::DuplicateLiteralInteger(_, span) => span,
+			ModelError::DuplicateLiteralString(_, span) => span,
+			ModelError::DuplicateLiteralBoolean(_, span) => span,
+			ModelError::DuplicateLiteralFloat(_, span) => span,
+			ModelError::DuplicateLiteralMap(_, span) => span,
+			ModelError::DuplicateLiteralSequence(_, span) => span,
+			ModelError::DuplicateLiteralCase(_, _, span) => span,
+			ModelError::DuplicateLiteralRecord(_, span) => span,
+			ModelError::DuplicateLiteralRecordField(_, _, span) => span,
+			ModelError::DuplicateFieldValue(_, span) => return span.as_ref(),
+			ModelError::DuplicateTypeParameter(_, _, _, span) => span,
+			ModelError::DuplicateMethodParameter(_, _, _, span) => span,
+			ModelError::DuplicateImport(_, span) => span,
+			ModelError::DuplicateFormat(_, span) => span,
+		})
+	}
+
+	/// Gets a stable code that identifies the kind of error.
+	pub fn code(&self) -> &'static str {
+		match self {
+			ModelError::DuplicateConstant(..) => "M0001",
+			ModelError::DuplicateType(..) => "M0002",
+			ModelError::DuplicateVersion(..) => "M0003",
+			ModelError::DuplicateField(..) => "M0004",
+			ModelError::DuplicateMethod(..) => "M0005",
+			ModelError::DuplicateLiteralInteger(..) => "M0006",
+			ModelError::DuplicateLiteralString(..) => "M0007",
+			ModelError::DuplicateLiteralSequence(..) => "M0008",
+			ModelError::DuplicateLiteralCase(..) => "M0009",
+			ModelError::DuplicateLiteralRecord(..) => "M0010",
+			ModelError::DuplicateLiteralRecordField(..) => "M0011",
+			ModelError::DuplicateFieldValue(..) => "M0012",
+			ModelError::DuplicateTypeParameter(..) => "M0013",
+			ModelError::DuplicateMethodParameter(..) => "M0014",
+			ModelError::DuplicateImport(..) => "M0015",
+			ModelError::DuplicateFormat(..) => "M0016",
+			ModelError::DuplicateLiteralBoolean(..) => "M0017",
+			ModelError::DuplicateLiteralFloat(..) => "M0018",
+			ModelError::DuplicateLiteralMap(..) => "M0019",
+		}
+	}
+}
+
+impl std::error::Error for ModelError {}
 
 impl fmt::Display for ModelError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
-			ModelError::DuplicateConstant(name) => write!(f, "Cannot declare constant {}. Name is already defined.", name),
-			ModelError::DuplicateType(name) => write!(f, "Cannot declare type {}. Name is already defined.", name),
-			ModelError::DuplicateVersion(name, version) => write!(f, "Version {} of type {} is already defined.", version, name),
-			ModelError::DuplicateField(name, version, field) => write!(f, "Version {} of type {} already has a field named {}.", version, name, field),
-			ModelError::DuplicateMethod(name, version, method) => write!(f, "Version {} of interface {} already has a method named {}.", version, name, method),
-			ModelError::DuplicateLiteralInteger(type_name) => write!(f, "Type {} already has an integer literal.", type_name),
-			ModelError::DuplicateLiteralString(type_name) => write!(f, "Type {} already has a string literal.", type_name),
-			ModelError::DuplicateLiteralSequence(type_name) => write!(f, "Type {} already has a sequence literal.", type_name),
-			ModelError::DuplicateLiteralCase(type_name, name) => write!(f, "Type {} already has a literal for case {}.", type_name, name),
-			ModelError::DuplicateLiteralRecord(type_name) => write!(f, "Type {} already has a record literal.", type_name),
-			ModelError::DuplicateLiteralRecordField(type_name, field) => write!(f, "Record literal for type {} already has a field named {}.", type_name, field),
-			ModelError::DuplicateFieldValue(name) => write!(f, "Record constant already has a field named {}.", name),
-			ModelError::DuplicateTypeParameter(type_name, None, param_name) => write!(f, "Duplicate type parameter {} in {}.", param_name, type_name),
-			ModelError::DuplicateTypeParameter(type_name, Some((version, method_name)), param_name) => write!(f, "Duplicate type parameter {} in method {} of {} version {}.", param_name, method_name, type_name, version),
-			ModelError::DuplicateMethodParameter(type_name, version, method_name, param_name) => write!(f, "Duplicate parameter {} in method {} of {} version {}.", param_name, method_name, type_name, version),
+			ModelError::DuplicateConstant(name, _) => write!(f, "Cannot declare constant {}. Name is already defined.", name),
+			ModelError::DuplicateType(name, _) => write!(f, "Cannot declare type {}. Name is already defined.", name),
+			ModelError::DuplicateVersion(name, version, _) => write!(f, "Version {} of type {} is already defined.", version, name),
+			ModelError::DuplicateField(name, version, field, _) => write!(f, "Version {} of type {} already has a field named {}.", version, name, field),
+			ModelError::DuplicateMethod(name, version, method, _) => write!(f, "Version {} of interface {} already has a method named {}.", version, name, method),
+			ModelError::DuplicateLiteralInteger(type_name, _) => write!(f, "Type {} already has an integer literal.", type_name),
+			ModelError::DuplicateLiteralString(type_name, _) => write!(f, "Type {} already has a string literal.", type_name),
+			ModelError::DuplicateLiteralBoolean(type_name, _) => write!(f, "Type {} already has a boolean literal.", type_name),
+			ModelError::DuplicateLiteralFloat(type_name, _) => write!(f, "Type {} already has a float literal.", type_name),
+			ModelError::DuplicateLiteralMap(type_name, _) => write!(f, "Type {} already has a map literal.", type_name),
+			ModelError::DuplicateLiteralSequence(type_name, _) => write!(f, "Type {} already has a sequence literal.", type_name),
+			ModelError::DuplicateLiteralCase(type_name, name, _) => write!(f, "Type {} already has a literal for case {}.", type_name, name),
+			ModelError::DuplicateLiteralRecord(type_name, _) => write!(f, "Type {} already has a record literal.", type_name),
+			ModelError::DuplicateLiteralRecordField(type_name, field, _) => write!(f, "Record literal for type {} already has a field named {}.", type_name, field),
+			ModelError::DuplicateFieldValue(name, _) => write!(f, "Record constant already has a field named {}.", name),
+			ModelError::DuplicateTypeParameter(type_name, None, param_name, _) => write!(f, "Duplicate type parameter {} in {}.", param_name, type_name),
+			ModelError::DuplicateTypeParameter(type_name, Some(method), param_name, _) => {
+				let (version, method_name) = &**method;
+				write!(f, "Duplicate type parameter {} in method {} of {} version {}.", param_name, method_name, type_name, version)
+			},
+			ModelError::DuplicateMethodParameter(type_name, method, param_name, _) => {
+				let (version, method_name) = &**method;
+				write!(f, "Duplicate parameter {} in method {} of {} version {}.", param_name, method_name, type_name, version)
+			},
+			ModelError::DuplicateImport(name, _) => write!(f, "Cannot import {}. Name is already imported.", name),
+			ModelError::DuplicateFormat(name, _) => write!(f, "Cannot declare format {}. Name is already defined.", name),
 		}
 	}
 }
 
 /// A data type. This includes the name of the type and the type arguments.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Type {
 	pub name: QualifiedName,
-	pub args: Vec<Type>,
+	pub args: Vec<TypeArgument>,
+}
+
+/// An argument of a type. Integers are only accepted by `const` parameters of extern types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeArgument {
+	Type(Type),
+	Integer(BigUint),
+}
+
+impl TypeArgument {
+	/// Gets the type if the argument is not an integer.
+	pub fn as_type(&self) -> Option<&Type> {
+		match self {
+			TypeArgument::Type(t) => Some(t),
+			TypeArgument::Integer(_) => None,
+		}
+	}
+}
+
+impl fmt::Display for TypeArgument {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TypeArgument::Type(t) => write!(f, "{}", t),
+			TypeArgument::Integer(n) => write!(f, "{}", n),
+		}
+	}
+}
+
+/// Types are written in the same form as the source, such as `list(a.b.C)`.
+impl fmt::Display for Type {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.name)?;
+		if !self.args.is_empty() {
+			write!(f, "(")?;
+			for (index, arg) in self.args.iter().enumerate() {
+				if index > 0 {
+					write!(f, ", ")?;
+				}
+				write!(f, "{}", arg)?;
+			}
+			write!(f, ")")?;
+		}
+		Ok(())
+	}
 }
 
 // Attaches a name to something.
@@ -236,7 +359,10 @@ impl <'a, A> Named<'a, A> {
 pub enum ConstantValue {
 	Integer(BigInt),
 	String(String),
+	Boolean(bool),
+	Float(f64),
 	Sequence(Vec<ConstantValue>),
+	Map(Vec<(ConstantValue, ConstantValue)>),
 	Case(String, Vec<ConstantValue>),
 	Record(ConstantValueRecord),
 	Constant(QualifiedName),
@@ -272,13 +398,13 @@ impl ConstantValueRecordBuilder {
 		}
 	}
 
-	pub fn add_field(&mut self, name: String, value: ConstantValue) -> Result<(), ModelError> {
+	pub fn add_field(&mut self, name: String, value: ConstantValue, span: Option<Span>) -> Result<(), ModelError> {
 		if self.field_names.insert(name.to_ascii_uppercase()) {
 			self.record.field_values.insert(name, value);
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateFieldValue(name))
+			Err(ModelError::DuplicateFieldValue(name, span))
 		}
 	}
 
@@ -294,6 +420,7 @@ pub struct ConstantVersionInfo<'a> {
 	pub version: BigUint,
 	pub explicit_version: bool,
 	pub value: &'a ConstantValue,
+	pub span: &'a Span,
 
 	dummy: PhantomData<()>,
 }
@@ -303,9 +430,15 @@ pub struct ConstantVersionInfo<'a> {
 /// See accessor methods for [`Named`] constants.
 pub struct Constant {
 	latest_version: BigUint,
-	imports: HashMap<String, QualifiedName>,
+	imports: HashMap<String, Import>,
 	value_type: Type,
-	versions: HashMap<BigUint, ConstantValue>,
+	versions: HashMap<BigUint, ConstantVersion>,
+	span: Span,
+}
+
+struct ConstantVersion {
+	value: ConstantValue,
+	span: Span,
 }
 
 pub struct ConstantBuilder {
@@ -314,7 +447,7 @@ pub struct ConstantBuilder {
 }
 
 impl ConstantBuilder {
-	pub fn new(latest_version: BigUint, name: QualifiedName, value_type: Type, imports: HashMap<String, QualifiedName>) -> Self {
+	pub fn new(latest_version: BigUint, name: QualifiedName, value_type: Type, imports: HashMap<String, Import>, span: Span) -> Self {
 		ConstantBuilder {
 			name: name,
 			constant: Constant {
@@ -322,16 +455,17 @@ impl ConstantBuilder {
 				imports: imports,
 				value_type: value_type,
 				versions: HashMap::new(),
+				span,
 			},
 		}
 	}
 
-	pub fn add_version(&mut self, version: BigUint, value: ConstantValue) -> Result<(), ModelError> {
+	pub fn add_version(&mut self, version: BigUint, value: ConstantValue, span: Span) -> Result<(), ModelError> {
 		if self.constant.versions.contains_key(&version) {
-			Err(ModelError::DuplicateVersion(self.name.clone(), version.clone()))
+			Err(ModelError::DuplicateVersion(self.name.clone(), version.clone(), span))
 		}
 		else {
-			self.constant.versions.insert(version, value);
+			self.constant.versions.insert(version, ConstantVersion { value, span });
 			Ok(())
 		}
 	}
@@ -339,6 +473,11 @@ impl ConstantBuilder {
 
 impl <'a> Named<'a, Constant> {
 
+	/// The location of the constant in the source.
+	pub fn span(self) -> &'a Span {
+		&self.value.span
+	}
+
 	/// The type of the constant.
 	pub fn value_type(self) -> &'a Type {
 		&self.value.value_type
@@ -378,11 +517,12 @@ impl <'a> Named<'a, Constant> {
 			self.value.versions.iter()
 				.filter(|(ver, _)| ver <= &version)
 				.max_by_key(|(ver, _)| ver.clone())
-				.map(|(actual_ver, value)| {
+				.map(|(actual_ver, ver_const)| {
 					ConstantVersionInfo {
 						version: version.clone(),
 						explicit_version: version == actual_ver,
-						value: value,
+						value: &ver_const.value,
+						span: &ver_const.span,
 						dummy: PhantomData {},
 					}
 				})
@@ -396,22 +536,131 @@ impl <'a> Named<'a, Constant> {
 
 }
 
+/// The encoding of the version number in the header of a file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormatVersionEncoding {
+	Nat,
+	U8,
+	U16,
+	U32,
+}
+
+impl FormatVersionEncoding {
+	/// Gets the largest version that can be encoded, or None if there is no limit.
+	pub fn max_version(self) -> Option<BigUint> {
+		match self {
+			FormatVersionEncoding::Nat => None,
+			FormatVersionEncoding::U8 => Some(BigUint::from(u8::MAX)),
+			FormatVersionEncoding::U16 => Some(BigUint::from(u16::MAX)),
+			FormatVersionEncoding::U32 => Some(BigUint::from(u32::MAX)),
+		}
+	}
+}
+
+/// A file format definition.
+/// A file starts with the magic bytes and the version number, followed by the root type in that version.
+/// 
+/// See accessor methods for [`Named`] file formats.
+pub struct FileFormat {
+	imports: HashMap<String, Import>,
+	root_type: Type,
+	magic: Vec<u8>,
+	version_encoding: FormatVersionEncoding,
+	span: Span,
+}
+
+pub struct FileFormatBuilder {
+	name: QualifiedName,
+	format: FileFormat,
+}
+
+impl FileFormatBuilder {
+	pub fn new(name: QualifiedName, root_type: Type, magic: Vec<u8>, version_encoding: FormatVersionEncoding, imports: HashMap<String, Import>, span: Span) -> Self {
+		FileFormatBuilder {
+			name,
+			format: FileFormat {
+				imports,
+				root_type,
+				magic,
+				version_encoding,
+				span,
+			},
+		}
+	}
+}
+
+impl <'a> Named<'a, FileFormat> {
+
+	/// The location of the format in the source.
+	pub fn span(self) -> &'a Span {
+		&self.value.span
+	}
+
+	/// The type of the value stored in the file.
+	pub fn root_type(self) -> &'a Type {
+		&self.value.root_type
+	}
+
+	/// The bytes at the start of the file.
+	pub fn magic(self) -> &'a Vec<u8> {
+		&self.value.magic
+	}
+
+	/// The encoding of the version number that follows the magic bytes.
+	pub fn version_encoding(self) -> FormatVersionEncoding {
+		self.value.version_encoding
+	}
+
+	/// Iterates over types referenced by the format.
+	pub fn referenced_types(self) -> ReferencedTypeIteratorVersionedType<'a> {
+		ReferencedTypeIteratorVersionedType::from_type(&self.value.root_type)
+	}
+
+	/// Gets a scope for this format.
+	pub fn scope(self) -> Scope<'a> {
+		Scope {
+			model: self.model,
+			current_pkg: Some(&self.name.package),
+			imports: Some(&self.value.imports),
+			type_params: Vec::new(),
+		}
+	}
+
+}
+
 /// A field of a struct or enum. An enum field represents a single case.
 #[derive(Debug)]
 pub struct FieldInfo {
 	pub field_type: Type,
+	/// The value used for the field when upgrading from a version that does not have it.
+	pub default_value: Option<ConstantValue>,
+	/// The name of the field in the previous version when the field has been renamed.
+	pub renamed_from: Option<String>,
+	pub span: Span,
 }
 
 /// A versioned type defines the contents of a type for a specific format version.
 #[derive(Debug)]
 pub struct TypeVersionDefinition {
 	fields: Vec<(String, FieldInfo)>,
+	removed_fields: Vec<(String, Span)>,
+	span: Span,
 }
 
 impl TypeVersionDefinition {
 	pub fn fields(&self) -> &Vec<(String, FieldInfo)> {
 		&self.fields
 	}
+
+	/// The fields of the previous version that are intentionally not kept in this version.
+	pub fn removed_fields(&self) -> &Vec<(String, Span)> {
+		&self.removed_fields
+	}
+
+	/// The location of the version definition in the source.
+	pub fn span(&self) -> &Span {
+		&self.span
+	}
 }
 
 pub struct TypeVersionDefinitionBuilder<'a> {
@@ -428,7 +677,17 @@ impl <'a> TypeVersionDefinitionBuilder<'a> {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateField(self.name.clone(), self.version.clone(), name))
+			Err(ModelError::DuplicateField(self.name.clone(), self.version.clone(), name, field.span))
+		}
+	}
+
+	pub fn add_removed_field(&mut self, name: String, span: Span) -> Result<(), ModelError> {
+		if self.field_names.insert(name.to_ascii_uppercase()) {
+			self.ver_type.removed_fields.push((name, span));
+			Ok(())
+		}
+		else {
+			Err(ModelError::DuplicateField(self.name.clone(), self.version.clone(), name, span))
 		}
 	}
 }
@@ -480,10 +739,13 @@ pub trait GeneratableType<'a> : Sized {
 	fn versions(named: Named<'a, Self>) -> Self::TypeVersionIterator;
 
 	/// Gets the imports that are in scope for this type.
-	fn imports(&'a self) -> &'a HashMap<String, QualifiedName>;
+	fn imports(&'a self) -> &'a HashMap<String, Import>;
 
 	/// Gets the parameters of the type.
 	fn type_params(&'a self) -> &'a Vec<String>;
+
+	/// Gets the location of the type in the source.
+	fn span(&'a self) -> &'a Span;
 }
 
 impl <'a, TypeDef: GeneratableType<'a>> Named<'a, TypeDef> {
@@ -526,16 +788,22 @@ impl <'a, TypeDef: GeneratableType<'a>> Named<'a, TypeDef> {
 	pub fn type_params(self) -> &'a Vec<String> {
 		self.value.type_params()
 	}
+
+	/// Gets the location of the type in the source.
+	pub fn span(self) -> &'a Span {
+		self.value.span()
+	}
 }
 
 /// Defines a versioned type. Could be a struct or enum.
 #[derive(Debug)]
 pub struct VersionedTypeDefinitionData {
 	latest_version: BigUint,
-	imports: HashMap<String, QualifiedName>,
+	imports: HashMap<String, Import>,
 	type_params: Vec<String>,
 	versions: HashMap<BigUint, TypeVersionDefinition>,
 	is_final: bool,
+	span: Span,
 }
 
 pub struct VersionedTypeDefinitionBuilder {
@@ -544,7 +812,7 @@ pub struct VersionedTypeDefinitionBuilder {
 }
 
 impl VersionedTypeDefinitionBuilder {
-	pub fn new(latest_version: BigUint, name: QualifiedName, type_params: Vec<String>, is_final: bool, imports: HashMap<String, QualifiedName>) -> Self {
+	pub fn new(latest_version: BigUint, name: QualifiedName, type_params: Vec<String>, is_final: bool, imports: HashMap<String, Import>, span: Span) -> Self {
 		VersionedTypeDefinitionBuilder {
 			name: name,
 			t: VersionedTypeDefinitionData {
@@ -553,16 +821,19 @@ impl VersionedTypeDefinitionBuilder {
 				type_params: type_params,
 				is_final: is_final,
 				versions: HashMap::new(),
+				span,
 			},
 		}
 	}
 
-	pub fn add_version<'a>(&'a mut self, version: BigUint) -> Result<TypeVersionDefinitionBuilder<'a>, ModelError> {
+	pub fn add_version<'a>(&'a mut self, version: BigUint, span: Span) -> Result<TypeVersionDefinitionBuilder<'a>, ModelError> {
 		match self.t.versions.entry(version.clone()) {
-			std::collections::hash_map::Entry::Occupied(_) => Err(ModelError::DuplicateVersion(self.name.clone(), version)),
+			std::collections::hash_map::Entry::Occupied(_) => Err(ModelError::DuplicateVersion(self.name.clone(), version, span)),
 			std::collections::hash_map::Entry::Vacant(entry) => {
 				let ver_type = entry.insert(TypeVersionDefinition {
 					fields: Vec::new(),
+					removed_fields: Vec::new(),
+					span,
 				});
 
 				Ok(TypeVersionDefinitionBuilder {
@@ -637,28 +908,37 @@ impl <'a> GeneratableType<'a> for VersionedTypeDefinitionData {
 		}
 	}
 
-	fn imports(&'a self) -> &'a HashMap<String, QualifiedName> {
+	fn imports(&'a self) -> &'a HashMap<String, Import> {
 		&self.imports
 	}
 
 	fn type_params(&'a self) -> &'a Vec<String> {
 		&self.type_params
 	}
+
+	fn span(&'a self) -> &'a Span {
+		&self.span
+	}
 }
 
 /// Defines an extern type.
 #[derive(Debug)]
 pub struct ExternTypeDefinitionData {
-	imports: HashMap<String, QualifiedName>,
+	imports: HashMap<String, Import>,
 	type_params: Vec<String>,
+	const_params: HashSet<String>,
 	literals: Vec<ExternLiteralSpecifier>,
+	span: Span,
 }
 
 pub struct ExternTypeDefinitionBuilder {
 	name: QualifiedName,
 	has_integer: bool,
 	has_string: bool,
+	has_boolean: bool,
+	has_float: bool,
 	has_sequence: bool,
+	has_map: bool,
 	cases: HashSet<String>,
 	has_record: bool,
 	t: ExternTypeDefinitionData,
@@ -671,25 +951,30 @@ pub struct ExternLiteralRecordBuilder<'a> {
 }
 
 impl ExternTypeDefinitionBuilder {
-	pub fn new(name: QualifiedName, type_params: Vec<String>, imports: HashMap<String, QualifiedName>) -> Self {
+	pub fn new(name: QualifiedName, type_params: Vec<String>, const_params: HashSet<String>, imports: HashMap<String, Import>, span: Span) -> Self {
 		ExternTypeDefinitionBuilder {
 			name: name,
 			has_integer: false,
 			has_string: false,
+			has_boolean: false,
+			has_float: false,
 			has_sequence: false,
+			has_map: false,
 			cases: HashSet::new(),
 			has_record: false,
 			t: ExternTypeDefinitionData {
 				imports: imports,
 				type_params: type_params,
+				const_params,
 				literals: Vec::new(),
+				span,
 			},
 		}
 	}
 	
-	pub fn add_integer_literal(&mut self, lower_type: ExternLiteralIntBound, lower: Option<BigInt>, upper_type: ExternLiteralIntBound, upper: Option<BigInt>) -> Result<(), ModelError> {
+	pub fn add_integer_literal(&mut self, lower_type: ExternLiteralIntBound, lower: Option<BigInt>, upper_type: ExternLiteralIntBound, upper: Option<BigInt>, span: Span) -> Result<(), ModelError> {
 		if self.has_integer {
-			Err(ModelError::DuplicateLiteralInteger(self.name.clone()))
+			Err(ModelError::DuplicateLiteralInteger(self.name.clone(), span))
 		}
 		else {
 			self.t.literals.push(ExternLiteralSpecifier::Integer(lower_type, lower, upper_type, upper));
@@ -698,9 +983,9 @@ impl ExternTypeDefinitionBuilder {
 		}
 	}
 	
-	pub fn add_string_literal(&mut self) -> Result<(), ModelError> {
+	pub fn add_string_literal(&mut self, span: Span) -> Result<(), ModelError> {
 		if self.has_string {
-			Err(ModelError::DuplicateLiteralString(self.name.clone()))
+			Err(ModelError::DuplicateLiteralString(self.name.clone(), span))
 		}
 		else {
 			self.t.literals.push(ExternLiteralSpecifier::String);
@@ -709,30 +994,63 @@ impl ExternTypeDefinitionBuilder {
 		}
 	}
 	
-	pub fn add_sequence_literal(&mut self, element_type: Type) -> Result<(), ModelError> {
+	pub fn add_boolean_literal(&mut self, span: Span) -> Result<(), ModelError> {
+		if self.has_boolean {
+			Err(ModelError::DuplicateLiteralBoolean(self.name.clone(), span))
+		}
+		else {
+			self.t.literals.push(ExternLiteralSpecifier::Boolean);
+			self.has_boolean = true;
+			Ok(())
+		}
+	}
+	
+	pub fn add_float_literal(&mut self, span: Span) -> Result<(), ModelError> {
+		if self.has_float {
+			Err(ModelError::DuplicateLiteralFloat(self.name.clone(), span))
+		}
+		else {
+			self.t.literals.push(ExternLiteralSpecifier::Float);
+			self.has_float = true;
+			Ok(())
+		}
+	}
+	
+	pub fn add_sequence_literal(&mut self, element_type: Type, length: Option<TypeArgument>, span: Span) -> Result<(), ModelError> {
 		if self.has_sequence {
-			Err(ModelError::DuplicateLiteralSequence(self.name.clone()))
+			Err(ModelError::DuplicateLiteralSequence(self.name.clone(), span))
 		}
 		else {
-			self.t.literals.push(ExternLiteralSpecifier::Sequence(element_type));
+			self.t.literals.push(ExternLiteralSpecifier::Sequence(element_type, length));
 			self.has_sequence = true;
 			Ok(())
 		}
 	}
 	
-	pub fn add_case_literal(&mut self, case_name: String, params: Vec<Type>) -> Result<(), ModelError> {
+	pub fn add_map_literal(&mut self, key_type: Type, value_type: Type, span: Span) -> Result<(), ModelError> {
+		if self.has_map {
+			Err(ModelError::DuplicateLiteralMap(self.name.clone(), span))
+		}
+		else {
+			self.t.literals.push(ExternLiteralSpecifier::Map(key_type, value_type));
+			self.has_map = true;
+			Ok(())
+		}
+	}
+	
+	pub fn add_case_literal(&mut self, case_name: String, params: Vec<Type>, span: Span) -> Result<(), ModelError> {
 		if self.cases.insert(case_name.to_ascii_uppercase()) {
 			self.t.literals.push(ExternLiteralSpecifier::Case(case_name, params));
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateLiteralCase(self.name.clone(), case_name))
+			Err(ModelError::DuplicateLiteralCase(self.name.clone(), case_name, span))
 		}
 	}
 	
-	pub fn add_record_literal<'a>(&'a mut self) -> Result<ExternLiteralRecordBuilder<'a>, ModelError> {
+	pub fn add_record_literal<'a>(&'a mut self, span: Span) -> Result<ExternLiteralRecordBuilder<'a>, ModelError> {
 		if self.has_record {
-			Err(ModelError::DuplicateLiteralRecord(self.name.clone()))
+			Err(ModelError::DuplicateLiteralRecord(self.name.clone(), span))
 		}
 		else {
 			self.has_record = true;
@@ -759,7 +1077,7 @@ impl <'a> ExternLiteralRecordBuilder<'a> {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateLiteralRecordField(self.name.clone(), name))
+			Err(ModelError::DuplicateLiteralRecordField(self.name.clone(), name, field.span))
 		}
 	}
 }
@@ -772,11 +1090,15 @@ pub enum ExternLiteralIntBound {
 }
 
 /// Defines a literal for an extern type.
+/// A sequence may have a fixed length, which is either an integer or a `const` parameter.
 #[derive(Debug)]
 pub enum ExternLiteralSpecifier {
 	Integer(ExternLiteralIntBound, Option<BigInt>, ExternLiteralIntBound, Option<BigInt>),
 	String,
-	Sequence(Type),
+	Boolean,
+	Float,
+	Sequence(Type, Option<TypeArgument>),
+	Map(Type, Type),
 	Case(String, Vec<Type>),
 	Record(Vec<(String, FieldInfo)>),
 }
@@ -801,11 +1123,22 @@ impl <'a> Named<'a, ExternTypeDefinitionData> {
 	pub fn type_params(self) -> &'a Vec<String> {
 		&self.value.type_params
 	}
+
+	/// Returns true if the parameter takes an integer rather than a type.
+	pub fn is_const_param(self, name: &str) -> bool {
+		self.value.const_params.contains(name)
+	}
+
+	/// Gets the location of the type in the source.
+	pub fn span(self) -> &'a Span {
+		&self.value.span
+	}
 }
 
 pub struct ParameterInfo {
 	pub name: String,
 	pub param_type: Type,
+	pub span: Span,
 }
 
 pub struct OfInterface<'a, A> {
@@ -837,18 +1170,21 @@ pub struct InterfaceMethod {
 	type_params: Vec<String>,
 	parameters: Vec<ParameterInfo>,
 	return_type: Type,
+	span: Span,
 }
 
 pub struct InterfaceVersionDefinition {
 	methods: Vec<(String, InterfaceMethod)>,
+	span: Span,
 }
 
 pub struct InterfaceTypeDefinitionData {
 	latest_version: BigUint,
-	imports: HashMap<String, QualifiedName>,
+	imports: HashMap<String, Import>,
 	type_params: Vec<String>,
 	versions: HashMap<BigUint, InterfaceVersionDefinition>,
 	is_final: bool,
+	span: Span,
 }
 
 pub struct InterfaceMethodBuilder<'a> {
@@ -873,7 +1209,7 @@ pub struct InterfaceTypeDefinitionBuilder {
 }
 
 impl InterfaceTypeDefinitionBuilder {
-	pub fn new(latest_version: BigUint, name: QualifiedName, type_params: Vec<String>, is_final: bool, imports: HashMap<String, QualifiedName>) -> Self {
+	pub fn new(latest_version: BigUint, name: QualifiedName, type_params: Vec<String>, is_final: bool, imports: HashMap<String, Import>, span: Span) -> Self {
 		InterfaceTypeDefinitionBuilder {
 			name: name,
 			data: InterfaceTypeDefinitionData {
@@ -882,16 +1218,18 @@ impl InterfaceTypeDefinitionBuilder {
 				type_params,
 				versions: HashMap::new(),
 				is_final,
+				span,
 			},
 		}
 	}
 
-	pub fn add_version<'a>(&'a mut self, version: BigUint) -> Result<InterfaceVersionDefinitionBuilder<'a>, ModelError> {
+	pub fn add_version<'a>(&'a mut self, version: BigUint, span: Span) -> Result<InterfaceVersionDefinitionBuilder<'a>, ModelError> {
 		match self.data.versions.entry(version.clone()) {
-			std::collections::hash_map::Entry::Occupied(_) => Err(ModelError::DuplicateVersion(self.name.clone(), version)),
+			std::collections::hash_map::Entry::Occupied(_) => Err(ModelError::DuplicateVersion(self.name.clone(), version, span)),
 			std::collections::hash_map::Entry::Vacant(entry) => {
 				let ver_type = entry.insert(InterfaceVersionDefinition {
 					methods: Vec::new(),
+					span,
 				});
 
 				Ok(InterfaceVersionDefinitionBuilder {
@@ -906,12 +1244,13 @@ impl InterfaceTypeDefinitionBuilder {
 }
 
 impl <'a> InterfaceVersionDefinitionBuilder<'a> {
-	pub fn add_method<'b>(&'b mut self, name: String, return_type: Type) -> Result<InterfaceMethodBuilder<'b>, ModelError> where 'a : 'b {
+	pub fn add_method<'b>(&'b mut self, name: String, return_type: Type, span: Span) -> Result<InterfaceMethodBuilder<'b>, ModelError> where 'a : 'b {
 		if self.method_names.insert(name.to_ascii_uppercase()) {
 			let method = InterfaceMethod {
 				type_params: Vec::new(),
 				parameters: Vec::new(),
 				return_type: return_type,
+				span,
 			};
 
 			self.ver.methods.push((name, method));
@@ -927,7 +1266,7 @@ impl <'a> InterfaceVersionDefinitionBuilder<'a> {
 			})
 		}
 		else {
-			Err(ModelError::DuplicateMethod(self.name.clone(), self.version.clone(), name))
+			Err(ModelError::DuplicateMethod(self.name.clone(), self.version.clone(), name, span))
 		}
 	}
 }
@@ -939,7 +1278,7 @@ impl <'a> InterfaceMethodBuilder<'a> {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateTypeParameter(self.interface_name.clone(), Some((self.interface_version.clone(), self.method_name.clone())), name))
+			Err(ModelError::DuplicateTypeParameter(self.interface_name.clone(), Some(Box::new((self.interface_version.clone(), self.method_name.clone()))), name, self.method.span.clone()))
 		}
 	}
 
@@ -949,7 +1288,7 @@ impl <'a> InterfaceMethodBuilder<'a> {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateMethodParameter(self.interface_name.clone(), self.interface_version.clone(), self.method_name.clone(), param.name))
+			Err(ModelError::DuplicateMethodParameter(self.interface_name.clone(), Box::new((self.interface_version.clone(), self.method_name.clone())), param.name, param.span))
 		}
 	}
 }
@@ -1015,16 +1354,25 @@ impl <'a> GeneratableType<'a> for InterfaceTypeDefinitionData {
 		}
 	}
 
-	fn imports(&'a self) -> &'a HashMap<String, QualifiedName> {
+	fn imports(&'a self) -> &'a HashMap<String, Import> {
 		&self.imports
 	}
 	
 	fn type_params(&'a self) -> &'a Vec<String> {
 		&self.type_params
 	}
+
+	fn span(&'a self) -> &'a Span {
+		&self.span
+	}
 }
 
 impl <'a> OfInterface<'a, InterfaceVersionDefinition> {
+	/// The location of the version definition in the source.
+	pub fn span(self) -> &'a Span {
+		&self.value.span
+	}
+
 	pub fn methods(self) -> Vec<(&'a String, OfInterface<'a, InterfaceMethod>)> {
 		let mut result = Vec::new();
 		for (name, method) in &self.value.methods {
@@ -1047,6 +1395,11 @@ impl <'a> OfInterface<'a, InterfaceMethod> {
 		&self.value.return_type
 	}
 
+	/// The location of the method in the source.
+	pub fn span(self) -> &'a Span {
+		&self.value.span
+	}
+
 	pub fn scope(self) -> Scope<'a> {
 		let mut scope = self.interface.scope();
 		scope.type_params.push(&self.value.type_params);
@@ -1093,11 +1446,30 @@ impl <'a> NamedTypeDefinition<'a> {
 		}
 	}
 
+	/// Gets the location of the type in the source.
+	pub fn span(&self) -> &'a Span {
+		match self {
+			NamedTypeDefinition::StructType(t) => &t.value.span,
+			NamedTypeDefinition::EnumType(t) => &t.value.span,
+			NamedTypeDefinition::ExternType(t) => &t.value.span,
+			NamedTypeDefinition::InterfaceType(t) => &t.value.span,
+		}
+	}
+
 	/// Gets the number of parameters of the type.
 	pub fn arity(&self) -> usize {
 		self.type_params().len()
 	}
 
+	/// Returns true if the parameter takes an integer rather than a type.
+	/// Only extern types have `const` parameters.
+	pub fn is_const_param(&self, name: &str) -> bool {
+		match self {
+			NamedTypeDefinition::ExternType(t) => t.is_const_param(name),
+			_ => false,
+		}
+	}
+
 	/// Returns true if the type exists in the specified version.
 	pub fn has_version(self, version: &BigUint) -> bool {
 		match self {
@@ -1123,6 +1495,7 @@ impl <'a> NamedTypeDefinition<'a> {
 pub struct Verilization {
 	constants: HashMap<QualifiedName, Constant>,
 	type_definitions: HashMap<QualifiedName, TypeDefinition>,
+	formats: HashMap<QualifiedName, FileFormat>,
 	names: HashSet<QualifiedName>,
 }
 
@@ -1139,7 +1512,7 @@ pub enum ScopeLookup {
 pub struct Scope<'a> {
 	model: &'a Verilization,
 	current_pkg: Option<&'a PackageName>,
-	imports: Option<&'a HashMap<String, QualifiedName>>,
+	imports: Option<&'a HashMap<String, Import>>,
 	type_params: Vec<&'a Vec<String>>,
 }
 
@@ -1162,7 +1535,7 @@ impl <'a> Scope<'a> {
 			}
 
 			if let Some(import) = self.imports.and_then(|imports| imports.get(&name.name)) {
-				return ScopeLookup::NamedType(import.clone());
+				return ScopeLookup::NamedType(import.name.clone());
 			}
 
 			if let Some(current_pkg) = self.current_pkg {
@@ -1185,7 +1558,7 @@ impl <'a> Scope<'a> {
 	pub fn lookup_constant(&self, mut name: QualifiedName) -> QualifiedName {
 		if name.package.package.is_empty() {
 			if let Some(import) = self.imports.and_then(|imports| imports.get(&name.name)) {
-				return import.clone();
+				return import.name.clone();
 			}
 
 			if let Some(current_pkg) = self.current_pkg {
@@ -1205,6 +1578,11 @@ impl <'a> Scope<'a> {
 		name
 	}
 
+	/// Iterates over the imports in scope as pairs of the local name and the import.
+	pub fn imports(&self) -> impl Iterator<Item = (&'a String, &'a Import)> {
+		self.imports.into_iter().flat_map(|imports| imports.iter())
+	}
+
 	pub fn type_params<'b>(&'b self) -> Vec<&'b String> where 'a : 'b {
 		let mut result = Vec::new();
 		for param_list in &self.type_params {
@@ -1231,6 +1609,7 @@ impl Verilization {
 		Verilization {
 			constants: HashMap::new(),
 			type_definitions: HashMap::new(),
+			formats: HashMap::new(),
 			names: HashSet::new(),
 		}
 	}
@@ -1244,7 +1623,7 @@ impl Verilization {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateConstant(constant.name))
+			Err(ModelError::DuplicateConstant(constant.name, constant.constant.span))
 		}
 	}
 
@@ -1257,7 +1636,7 @@ impl Verilization {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateType(type_def.name))
+			Err(ModelError::DuplicateType(type_def.name, type_def.t.span))
 		}
 	}
 
@@ -1270,7 +1649,7 @@ impl Verilization {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateType(type_def.name))
+			Err(ModelError::DuplicateType(type_def.name, type_def.t.span))
 		}
 	}
 
@@ -1283,7 +1662,7 @@ impl Verilization {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateType(type_def.name))
+			Err(ModelError::DuplicateType(type_def.name, type_def.t.span))
 		}
 	}
 
@@ -1296,7 +1675,20 @@ impl Verilization {
 			Ok(())
 		}
 		else {
-			Err(ModelError::DuplicateType(type_def.name))
+			Err(ModelError::DuplicateType(type_def.name, type_def.data.span))
+		}
+	}
+
+	/// Adds a file format to the model.
+	pub fn add_format(&mut self, format: FileFormatBuilder) -> Result<(), ModelError> {
+		let mut case_name = format.name.clone();
+		make_name_uppercase(&mut case_name);
+		if self.names.insert(case_name) {
+			self.formats.insert(format.name, format.format);
+			Ok(())
+		}
+		else {
+			Err(ModelError::DuplicateFormat(format.name, format.format.span))
 		}
 	}
 
@@ -1319,6 +1711,13 @@ impl Verilization {
 		})
 	}
 
+	/// Finds a file format in the model.
+	pub fn get_format<'a>(&'a self, name: &QualifiedName) -> Option<Named<'a, FileFormat>> {
+		let (name, format) = self.formats.get_key_value(name)?;
+
+		Some(Named::new(self, name, format))
+	}
+
 	/// Determines whether a type with this name exists.
 	pub fn has_type(&self, name: &QualifiedName) -> bool {
 		self.type_definitions.contains_key(name)
@@ -1329,16 +1728,23 @@ impl Verilization {
 	}
 
 	/// Merges two serialization formats.
-	pub fn merge(&mut self, other: Verilization) -> Result<(), ModelError> {
-		other.constants.into_iter().try_for_each(|(name, constant)| self.add_constant(ConstantBuilder { name: name, constant: constant }))?;
-		other.type_definitions.into_iter().try_for_each(|(name, t)| match t {
+	pub fn merge(&mut self, other: Verilization) -> Result<(), Vec<ModelError>> {
+		let constant_results = other.constants.into_iter().map(|(name, constant)| self.add_constant(ConstantBuilder { name: name, constant: constant })).collect::<Vec<_>>();
+		let type_results = other.type_definitions.into_iter().map(|(name, t)| match t {
 			TypeDefinition::StructType(type_def) => self.add_struct_type(VersionedTypeDefinitionBuilder { name: name, t: type_def }),
 			TypeDefinition::EnumType(type_def) => self.add_enum_type(VersionedTypeDefinitionBuilder { name: name, t: type_def }),
-			TypeDefinition::ExternType(type_def) => self.add_extern_type(ExternTypeDefinitionBuilder { name: name, t: type_def, has_integer: false, has_string: false, has_sequence: false, cases: HashSet::new(), has_record: false, }),
+			TypeDefinition::ExternType(type_def) => self.add_extern_type(ExternTypeDefinitionBuilder { name: name, t: type_def, has_integer: false, has_string: false, has_boolean: false, has_float: false, has_sequence: false, has_map: false, cases: HashSet::new(), has_record: false, }),
 			TypeDefinition::InterfaceType(type_def) => self.add_interface(InterfaceTypeDefinitionBuilder { name: name, data: type_def })
-		})?;
+		}).collect::<Vec<_>>();
+		let format_results = other.formats.into_iter().map(|(name, format)| self.add_format(FileFormatBuilder { name, format })).collect::<Vec<_>>();
 
-		Ok(())
+		let errors: Vec<ModelError> = constant_results.into_iter().chain(type_results).chain(format_results).filter_map(Result::err).collect();
+		if errors.is_empty() {
+			Ok(())
+		}
+		else {
+			Err(errors)
+		}
 	}
 
 
@@ -1358,6 +1764,14 @@ impl Verilization {
 		}
 	}
 
+	/// Iterate over file formats defined in the model.
+	pub fn formats<'a>(&'a self) -> FormatIterator<'a> {
+		FormatIterator {
+			model: self,
+			iter: self.formats.iter(),
+		}
+	}
+
 }
 
 
@@ -1377,11 +1791,24 @@ impl <'a> Iterator for ConstantIterator<'a> {
 	}
 }
 
+pub struct FormatIterator<'a> {
+	model: &'a Verilization,
+	iter: std::collections::hash_map::Iter<'a, QualifiedName, FileFormat>,
+}
+
+impl <'a> Iterator for FormatIterator<'a> {
+	type Item = Named<'a, FileFormat>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|(name, format)| Named::new(self.model, name, format))
+	}
+}
+
 pub struct ConstantVersionIterator<'a> {
 	constant: Named<'a, Constant>,
 	version: BigUint,
 	max_version: BigUint,
-	last_seen_version: Option<&'a ConstantValue>,
+	last_seen_version: Option<&'a ConstantVersion>,
 }
 
 impl <'a> Iterator for ConstantVersionIterator<'a> {
@@ -1396,7 +1823,8 @@ impl <'a> Iterator for ConstantVersionIterator<'a> {
 				self.last_seen_version = Some(ver_const);
 				return Some(ConstantVersionInfo {
 					version: version,
-					value: ver_const,
+					value: &ver_const.value,
+					span: &ver_const.span,
 					dummy: PhantomData {},
 					explicit_version: true,
 				});
@@ -1404,7 +1832,8 @@ impl <'a> Iterator for ConstantVersionIterator<'a> {
 			else if let Some(ver_const) = self.last_seen_version {
 				return Some(ConstantVersionInfo {
 					version: version,
-					value: ver_const,
+					value: &ver_const.value,
+					span: &ver_const.span,
 					dummy: PhantomData {},
 					explicit_version: false,
 				});
@@ -1510,11 +1939,18 @@ impl <'a> Iterator for InterfaceVersionIterator<'a> {
 }
 
 
+// Integer arguments do not refer to any types, so they are skipped.
+type ReferencedTypeArgIter<'a> = Box<dyn Iterator<Item = &'a Type> + 'a>;
+
+fn referenced_type_args(t: &Type) -> ReferencedTypeArgIter<'_> {
+	Box::new(t.args.iter().filter_map(TypeArgument::as_type))
+}
+
 pub struct ReferencedTypeIteratorVersionedType<'a> {
 	seen_types: HashSet<&'a QualifiedName>,
 	ver_iter: std::collections::hash_map::Values<'a, BigUint, TypeVersionDefinition>,
 	field_iter: std::slice::Iter<'a, (String, FieldInfo)>,
-	arg_iters: Vec<std::slice::Iter<'a, Type>>,
+	arg_iters: Vec<ReferencedTypeArgIter<'a>>,
 }
 
 lazy_static! {
@@ -1529,7 +1965,7 @@ impl <'a> Iterator for ReferencedTypeIteratorVersionedType<'a> {
 		loop {
 			while let Some(arg_iter) = self.arg_iters.last_mut() {
 				if let Some(arg) = arg_iter.next() {
-					self.arg_iters.push(arg.args.iter());
+					self.arg_iters.push(referenced_type_args(arg));
 					if self.seen_types.insert(&arg.name) {
 						return Some(&arg.name);
 					}
@@ -1540,7 +1976,7 @@ impl <'a> Iterator for ReferencedTypeIteratorVersionedType<'a> {
 			}
 
 			if let Some((_, field)) = self.field_iter.next() {
-				self.arg_iters.push(std::slice::from_ref(&field.field_type).iter());
+				self.arg_iters.push(Box::new(std::iter::once(&field.field_type)));
 			}
 			else if let Some(ver_type) = self.ver_iter.next() {
 				self.field_iter = ver_type.fields.iter();
@@ -1567,7 +2003,7 @@ impl <'a> ReferencedTypeIteratorVersionedType<'a> {
 			seen_types: HashSet::new(),
 			ver_iter: REF_TYPE_ITER_EMPTY_VER_MAP.values(),
 			field_iter: REF_TYPE_ITER_EMPTY_FIELD_SLICE.iter(),
-			arg_iters: vec!(std::slice::from_ref(t).iter()),
+			arg_iters: vec!(Box::new(std::iter::once(t))),
 		}
 	}
 }
@@ -1579,7 +2015,7 @@ pub struct ReferencedTypeIteratorInterfaceType<'a> {
 	current_method_type_params: Vec<&'a String>,
 	ver_iter: std::collections::hash_map::Values<'a, BigUint, InterfaceVersionDefinition>,
 	method_iter: std::slice::Iter<'a, (String, InterfaceMethod)>,
-	arg_iters: Vec<std::slice::Iter<'a, Type>>,
+	arg_iters: Vec<ReferencedTypeArgIter<'a>>,
 }
 
 const REF_TYPE_ITER_EMPTY_INTERFACE_METHODS_SLICE: &[(String, InterfaceMethod)] = &[];
@@ -1591,7 +2027,7 @@ impl <'a> Iterator for ReferencedTypeIteratorInterfaceType<'a> {
 		loop {
 			while let Some(arg_iter) = self.arg_iters.last_mut() {
 				if let Some(arg) = arg_iter.next() {
-					self.arg_iters.push(arg.args.iter());
+					self.arg_iters.push(referenced_type_args(arg));
 
 					let is_method_type_param = arg.name.package.package.is_empty() && self.current_method_type_params.contains(&&arg.name.name);
 
@@ -1608,9 +2044,9 @@ impl <'a> Iterator for ReferencedTypeIteratorInterfaceType<'a> {
 				self.current_method_type_params.clear();
 				self.current_method_type_params.extend(&method.type_params);
 
-				self.arg_iters.push(std::slice::from_ref(&method.return_type).iter());
+				self.arg_iters.push(Box::new(std::iter::once(&method.return_type)));
 				for param in &method.parameters {
-					self.arg_iters.push(std::slice::from_ref(&param.param_type).iter());
+					self.arg_iters.push(Box::new(std::iter::once(&param.param_type)));
 				}
 			}
 			else if let Some(ver_type) = self.ver_iter.next() {
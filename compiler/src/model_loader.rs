@@ -1,12 +1,18 @@
 use crate::VError;
 use crate::model;
+use crate::source::SourceFile;
 use crate::type_check::type_check_verilization;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::lockfile;
+
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
 /// Loads a set of files into a model.
 /// 
+/// Locked versions are not checked; use [`check_lockfile`] for that.
+/// 
 /// ```no_run
 /// use verilization_compiler::load_files;
 /// # fn main() -> Result<(), verilization_compiler::VError> {
@@ -22,24 +28,69 @@ pub fn load_files<P : AsRef<Path>>(files: Vec<P>) -> Result<model::Verilization,
 	let models = files
 		.into_iter()
 		.map(|file| {
-			let content = std::fs::read_to_string(file).expect("Could not read input file.");
-			let (_, model) = parser::parse_model(&content)?;
-			let model = model()?;
-			Ok(model)
+			let content = std::fs::read_to_string(&file)
+				.map_err(|err| VError::FileReadError(file.as_ref().to_path_buf(), err))?;
+			let file = SourceFile::new(file.as_ref().to_string_lossy().into_owned(), content);
+			parser::parse_model(&file)
 		});
 
 	load_all_models(models)
 }
 
-pub fn load_all_models<M : Iterator<Item = Result<model::Verilization, VError>>>(mut models: M) -> Result<model::Verilization, VError> {
+/// Checks a model against a lockfile if the lockfile exists.
+///
+/// Every locked version must match the model.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn check_lockfile(model: &model::Verilization, path: &Path) -> Result<(), VError> {
+	let content = match std::fs::read_to_string(path) {
+		Ok(content) => content,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+		Err(err) => return Err(VError::FileReadError(path.to_path_buf(), err)),
+	};
+
+	lockfile::Lockfile::parse(&content)?
+		.check(model)
+		.map_err(|errors| VError::from_errors(errors.into_iter().map(VError::from).collect()))
+}
+
+/// Merges models and type checks the result.
+/// 
+/// Every error from parsing, merging and type checking is reported rather than only the first.
+pub fn load_all_models<M : Iterator<Item = Result<model::Verilization, VError>>>(models: M) -> Result<model::Verilization, VError> {
+	match load_models_with_errors(models) {
+		(Some(model), errors) if errors.is_empty() => Ok(model),
+		(None, errors) if errors.is_empty() => Err(VError::NoInputFiles),
+		(_, errors) => Err(VError::from_errors(errors)),
+	}
+}
 
-	let mut model = models.next().ok_or(VError::NoInputFiles)??;
-	while let Some(other) = models.next() {
-		model.merge(other?)?;
+/// Merges models and type checks the result, keeping the definitions that loaded even if there are errors.
+/// 
+/// Tools such as editors can still use the model while the errors are fixed.
+pub fn load_models_with_errors<M : Iterator<Item = Result<model::Verilization, VError>>>(models: M) -> (Option<model::Verilization>, Vec<VError>) {
+
+	let mut errors = Vec::new();
+	let mut model: Option<model::Verilization> = None;
+	for other in models {
+		match (other, &mut model) {
+			(Ok(other), Some(model)) => {
+				if let Err(merge_errors) = model.merge(other) {
+					errors.extend(merge_errors.into_iter().map(VError::from));
+				}
+			},
+			(Ok(other), None) => model = Some(other),
+			(Err(err), _) => errors.extend(err.into_errors()),
+		}
 	}
 
-	type_check_verilization(&model)?;
+	// Type checking a partial model would report errors for definitions that failed to load.
+	if errors.is_empty() {
+		if let Some(model) = &model {
+			if let Err(type_errors) = type_check_verilization(model) {
+				errors.extend(type_errors.into_iter().map(VError::from));
+			}
+		}
+	}
 
-	Ok(model)
+	(model, errors)
 }
-
@@ -1,169 +1,329 @@
 use crate::model;
+use crate::source::SourceFile;
+use crate::VError;
 use num_bigint::{ BigUint, BigInt, Sign };
-use num_traits::Zero;
-use std::collections::{HashMap};
+use num_traits::{Zero, ToPrimitive};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
 
 use nom::{
 	IResult,
 	branch::{alt},
-	multi::{many0, separated_list1, separated_list0},
-	character::complete::{multispace0, multispace1, alphanumeric1, one_of, none_of, char},
-	combinator::{map, opt, eof, value, cut},
-	bytes::complete::tag,
-	sequence::{preceded, terminated},
+	multi::{many0, many1, separated_list1, separated_list0},
+	character::complete::{multispace1, alphanumeric1, digit1, one_of, none_of, char, not_line_ending},
+	combinator::{map, map_opt, opt, eof, value, cut, recognize},
+	bytes::complete::{tag, take_until},
+	sequence::{preceded, terminated, tuple, pair, delimited},
 };
 
 type PResult<I, A> = IResult<I, A>;
 
-type ImportMap = HashMap<String, model::QualifiedName>;
-type LazyConstantValue = dyn FnOnce() -> Result<model::ConstantValue, model::ModelError>;
-type TopLevelDefinitionAdder = dyn FnOnce(&mut model::Verilization) -> Result<(), model::ModelError>;
-type TypeVersionAdder = dyn FnOnce(&mut model::VersionedTypeDefinitionBuilder) -> Result<(), model::ModelError>;
-type InterfaceVersionAdder = dyn FnOnce(&mut model::InterfaceTypeDefinitionBuilder) -> Result<(), model::ModelError>;
-type InterfaceMethodAdder = dyn FnOnce(&mut model::InterfaceVersionDefinitionBuilder) -> Result<(), model::ModelError>;
-type ExternLiteralAdder = dyn FnOnce(&mut model::ExternTypeDefinitionBuilder) -> Result<(), model::ModelError>;
-type LazyModel = dyn FnOnce() -> Result<model::Verilization, model::ModelError>;
+type ImportMap = HashMap<String, (model::QualifiedName, InputSpan)>;
+type LazyConstantValue = dyn FnOnce(&Arc<SourceFile>) -> Result<model::ConstantValue, model::ModelError>;
+type TopLevelDefinitionAdder = dyn FnOnce(&mut model::Verilization, &Arc<SourceFile>) -> Result<(), model::ModelError>;
+type TypeVersionAdder = dyn FnOnce(&mut model::VersionedTypeDefinitionBuilder, &Arc<SourceFile>) -> Result<(), model::ModelError>;
+type InterfaceVersionAdder = dyn FnOnce(&mut model::InterfaceTypeDefinitionBuilder, &Arc<SourceFile>) -> Result<(), model::ModelError>;
+type InterfaceMethodAdder = dyn FnOnce(&mut model::InterfaceVersionDefinitionBuilder, &Arc<SourceFile>) -> Result<(), model::ModelError>;
+type ExternLiteralAdder = dyn FnOnce(&mut model::ExternTypeDefinitionBuilder, &Arc<SourceFile>, model::Span) -> Result<(), model::ModelError>;
+type LazyModel = dyn FnOnce(&Arc<SourceFile>) -> Result<model::Verilization, Vec<model::ModelError>>;
+
+/// An error that occurred while parsing a file.
+#[derive(Debug)]
+pub struct ParseError {
+	pub kind: nom::error::ErrorKind,
+	pub span: model::Span,
+}
+
+impl ParseError {
+	/// Gets a stable code that identifies the kind of error.
+	pub fn code(&self) -> &'static str {
+		match self.kind {
+			nom::error::ErrorKind::TakeUntil => "P0002",
+			_ => "P0001",
+		}
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+impl std::fmt::Display for ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.kind == nom::error::ErrorKind::TakeUntil {
+			return write!(f, "Unterminated block comment.")
+		}
+
+		let token = &self.span.file().content()[self.span.start()..self.span.end()];
+		if token.is_empty() {
+			write!(f, "Syntax error. Unexpected end of file.")
+		}
+		else {
+			write!(f, "Syntax error. Unexpected `{}`.", token)
+		}
+	}
+}
+
+
+// Source locations
+
+/// A span of the input, stored as the lengths of the remaining input at the start and end.
+/// This allows spans to be recorded before the location in the file is known.
+#[derive(Clone, Copy)]
+struct InputSpan {
+	start: usize,
+	end: usize,
+}
+
+impl InputSpan {
+	fn resolve(self, file: &Arc<SourceFile>) -> model::Span {
+		let len = file.content().len();
+		model::Span::new(file.clone(), len - self.start, len - self.end)
+	}
+}
+
+fn spanned<'a, A>(mut parser: impl FnMut(&'a str) -> PResult<&'a str, A>) -> impl FnMut(&'a str) -> PResult<&'a str, (A, InputSpan)> {
+	move |input| {
+		let (input, _) = skip_ws0(input)?;
+		let start = input.len();
+		let (input, value) = parser(input)?;
+		Ok((input, (value, InputSpan { start, end: input.len() })))
+	}
+}
+
 
+// Whitespace and comments
+fn line_comment(input: &str) -> PResult<&str, ()> {
+	let (input, _) = tag("//")(input)?;
+	let (input, _) = not_line_ending(input)?;
+	Ok((input, ()))
+}
+
+fn block_comment(input: &str) -> PResult<&str, ()> {
+	let (input, _) = tag("/*")(input)?;
+	let (input, _) = cut(take_until("*/"))(input)?;
+	let (input, _) = tag("*/")(input)?;
+	Ok((input, ()))
+}
+
+fn ws_item(input: &str) -> PResult<&str, ()> {
+	alt((
+		value((), multispace1),
+		line_comment,
+		block_comment,
+	))(input)
+}
+
+fn skip_ws0(input: &str) -> PResult<&str, ()> {
+	value((), many0(ws_item))(input)
+}
+
+fn skip_ws1(input: &str) -> PResult<&str, ()> {
+	value((), many1(ws_item))(input)
+}
 
 // Keywords
 fn kw_version(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("version")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_package(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("package")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_import(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("import")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_as(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("as")(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_const(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("const")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_enum(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("enum")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_struct(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("struct")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_extern(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("extern")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_interface(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("interface")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_final(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("final")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 fn kw_literal(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("literal")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_renamed(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("renamed")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_from(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("from")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_removed(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("removed")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_format(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("format")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_root(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("root")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	Ok((input, ()))
+}
+
+fn kw_magic(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("magic")(input)?;
+	let (input, _) = skip_ws1(input)?;
 	Ok((input, ()))
 }
 
 // Symbols
 fn sym_semicolon(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char(';')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_colon(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char(':')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_dot(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('.')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_comma(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char(',')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_eq(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('=')(input)?;
 	Ok((input, ()))
 }
 
+fn sym_arrow(input: &str) -> PResult<&str, ()> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("=>")(input)?;
+	Ok((input, ()))
+}
+
 fn sym_open_curly(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('{')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_close_curly(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('}')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_open_paren(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('(')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_close_paren(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char(')')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_open_bracket(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('[')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_close_bracket(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char(']')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_open_angle(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('<')(input)?;
 	Ok((input, ()))
 }
 
 fn sym_close_angle(input: &str) -> PResult<&str, ()> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('>')(input)?;
 	Ok((input, ()))
 }
@@ -178,7 +338,7 @@ fn dec_digit(input: &str) -> PResult<&str, u8> {
 
 // Integer literal (no signs)
 fn biguint(input: &str) -> PResult<&str, BigUint> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, first_dig) = dec_digit(input)?;
 	
 	if first_dig == 0 {
@@ -193,7 +353,7 @@ fn biguint(input: &str) -> PResult<&str, BigUint> {
 
 // Allows an optional sign before the integer literal
 fn bigint(input: &str) -> PResult<&str, BigInt> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, sign) = opt(one_of("+-"))(input)?;
 	let (input, n) = biguint(input)?;
 
@@ -211,7 +371,7 @@ fn bigint(input: &str) -> PResult<&str, BigInt> {
 }
 
 fn string_literal(input: &str) -> PResult<&str, String> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = char('\"')(input)?;
 
 	let (input, chars) = many0(
@@ -221,8 +381,8 @@ fn string_literal(input: &str) -> PResult<&str, String> {
 				alt((
 					value('\\', char('\\')),
 					value('\"', char('\"')),
-					value('n', char('\n')),
-					value('\r', char('\r')),
+					value('\n', char('n')),
+					value('\r', char('r')),
 				))
 			)
 		))
@@ -234,7 +394,7 @@ fn string_literal(input: &str) -> PResult<&str, String> {
 }
 
 fn identifier(input: &str) -> PResult<&str, String> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, str) = alphanumeric1(input)?;
 	Ok((input, str.to_string()))
 }
@@ -257,9 +417,45 @@ fn package_directive(input: &str) -> PResult<&str, model::PackageName> {
 	Ok((input, model::PackageName { package: pkg }))
 }
 
-fn type_expr_args(input: &str) -> PResult<&str, Vec<model::Type>> {
+// Ex: import hello.world.Type;
+// Ex: import hello.world.Type as Alias;
+fn import_directive(input: &str) -> PResult<&str, (String, model::QualifiedName)> {
+	let (input, _) = kw_import(input)?;
+	let (input, first) = cut(identifier)(input)?;
+	let (input, mut parts) = cut(many1(preceded(sym_dot, identifier)))(input)?;
+	let (input, alias) = opt(preceded(kw_as, cut(identifier)))(input)?;
+	let (input, _) = cut(sym_semicolon)(input)?;
+
+	let name = parts.pop().unwrap();
+	parts.insert(0, first);
+
+	let alias = alias.unwrap_or_else(|| name.clone());
+	let qual_name = model::QualifiedName {
+		package: model::PackageName { package: parts },
+		name,
+	};
+
+	Ok((input, (alias, qual_name)))
+}
+
+fn resolve_imports(imports: ImportMap, file: &Arc<SourceFile>) -> HashMap<String, model::Import> {
+	imports.into_iter()
+		.map(|(alias, (name, span))| (alias, model::Import { name, span: span.resolve(file) }))
+		.collect()
+}
+
+// Ex: 32
+// Ex: list(u8)
+fn type_arg(input: &str) -> PResult<&str, model::TypeArgument> {
+	alt((
+		map(biguint, model::TypeArgument::Integer),
+		map(type_expr, model::TypeArgument::Type),
+	))(input)
+}
+
+fn type_expr_args(input: &str) -> PResult<&str, Vec<model::TypeArgument>> {
 	let (input, _) = sym_open_paren(input)?;
-	let (input, args) = separated_list1(sym_comma, type_expr)(input)?;
+	let (input, args) = separated_list1(sym_comma, type_arg)(input)?;
 	let (input, _) = sym_close_paren(input)?;
 	Ok((input, args))
 }
@@ -292,12 +488,43 @@ fn type_expr(input: &str) -> PResult<&str, model::Type> {
 
 fn constant_integer_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 	let (input, n) = bigint(input)?;
-	Ok((input, Box::new(move || Ok(model::ConstantValue::Integer(n)))))
+	Ok((input, Box::new(move |_| Ok(model::ConstantValue::Integer(n)))))
+}
+
+// A float literal requires a decimal point or an exponent to distinguish it from an integer.
+fn float_literal(input: &str) -> PResult<&str, f64> {
+	let (input, _) = skip_ws0(input)?;
+	let exponent = |input| recognize(tuple((one_of("eE"), opt(one_of("+-")), digit1)))(input);
+	map_opt(
+		recognize(tuple((
+			opt(one_of("+-")),
+			digit1,
+			alt((
+				recognize(tuple((char('.'), digit1, opt(exponent)))),
+				exponent,
+			)),
+		))),
+		|s: &str| s.parse::<f64>().ok().filter(|f| f.is_finite())
+	)(input)
+}
+
+fn constant_float_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
+	let (input, f) = float_literal(input)?;
+	Ok((input, Box::new(move |_| Ok(model::ConstantValue::Float(f)))))
 }
 
 fn constant_string_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 	let (input, s) = string_literal(input)?;
-	Ok((input, Box::new(move || Ok(model::ConstantValue::String(s)))))
+	Ok((input, Box::new(move |_| Ok(model::ConstantValue::String(s)))))
+}
+
+fn constant_boolean_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
+	let (input, b) = map_opt(identifier, |name| match name.as_str() {
+		"true" => Some(true),
+		"false" => Some(false),
+		_ => None,
+	})(input)?;
+	Ok((input, Box::new(move |_| Ok(model::ConstantValue::Boolean(b)))))
 }
 
 fn sequence_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
@@ -310,39 +537,64 @@ fn sequence_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 	)(input)?;
 	let (input, _) = sym_close_bracket(input)?;
 
-	Ok((input, Box::new(move || {
+	Ok((input, Box::new(move |file| {
 		let values = values
 			.unwrap_or_else(|| Vec::new())
 			.into_iter()
-			.map(|lazy_const| lazy_const())
+			.map(|lazy_const| lazy_const(file))
 			.collect::<Result<Vec<_>, _>>()?;
 		Ok(model::ConstantValue::Sequence(values))
 	})))
 }
 
+fn map_entry(input: &str) -> PResult<&str, (Box<LazyConstantValue>, Box<LazyConstantValue>)> {
+	let (input, key) = constant_value(input)?;
+	let (input, _) = sym_arrow(input)?;
+	let (input, value) = constant_value(input)?;
+	Ok((input, (key, value)))
+}
+
+// An empty map is written as an empty sequence.
+fn map_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
+	let (input, _) = sym_open_bracket(input)?;
+	let (input, entries) = terminated(
+		separated_list1(sym_comma, map_entry),
+		opt(sym_comma)
+	)(input)?;
+	let (input, _) = sym_close_bracket(input)?;
+
+	Ok((input, Box::new(move |file| {
+		let entries = entries
+			.into_iter()
+			.map(|(key, value)| Ok((key(file)?, value(file)?)))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(model::ConstantValue::Map(entries))
+	})))
+}
+
 fn case_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 	let (input, name) = identifier(input)?;
 	let (input, _) = sym_open_paren(input)?;
 	let (input, args) = opt(separated_list1(sym_comma, constant_value))(input)?;
 	let (input, _) = sym_close_paren(input)?;
 
-	Ok((input, Box::new(move || {
+	Ok((input, Box::new(move |file| {
 		let args = args
 			.unwrap_or_else(|| Vec::new())
 			.into_iter()
-			.map(|lazy_const| lazy_const())
+			.map(|lazy_const| lazy_const(file))
 			.collect::<Result<Vec<_>, _>>()?;
 
 		Ok(model::ConstantValue::Case(name, args))
 	})))
 }
 
-fn record_field_literal(input: &str) -> PResult<&str, (String, Box<LazyConstantValue>)> {
-	let (input, name) = identifier(input)?;
+fn record_field_literal(input: &str) -> PResult<&str, (String, InputSpan, Box<LazyConstantValue>)> {
+	let (input, (name, span)) = spanned(identifier)(input)?;
 	let (input, _) = sym_eq(input)?;
 	let (input, value) = constant_value(input)?;
 	let (input, _) = sym_semicolon(input)?;
-	Ok((input, (name, value)))
+	Ok((input, (name, span, value)))
 }
 
 fn record_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
@@ -352,10 +604,10 @@ fn record_literal(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 
 	let (input, _) = sym_close_curly(input)?;
 
-	Ok((input, Box::new(move || {
+	Ok((input, Box::new(move |file| {
 		let mut record = model::ConstantValueRecordBuilder::new();
-		for (name, value) in fields {
-			record.add_field(name, value()?)?;
+		for (name, span, value) in fields {
+			record.add_field(name, value(file)?, Some(span.resolve(file)))?;
 		}
 
 		Ok(model::ConstantValue::Record(record.build()))
@@ -375,7 +627,7 @@ fn other_constant(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 		name = part;
 	}
 
-	Ok((input, Box::new(move || Ok(model::ConstantValue::Constant(
+	Ok((input, Box::new(move |_| Ok(model::ConstantValue::Constant(
 		model::QualifiedName {
 			package: model::PackageName {
 				package: package,
@@ -388,22 +640,24 @@ fn other_constant(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 
 fn constant_value(input: &str) -> PResult<&str, Box<LazyConstantValue>> {
 	alt((
+		constant_float_literal,
 		constant_integer_literal,
 		constant_string_literal,
+		map_literal,
 		sequence_literal,
 		case_literal,
 		record_literal,
+		constant_boolean_literal,
 		other_constant,
 	))(input)
 }
 
-fn versioned_constant(input: &str) -> PResult<&str, (BigUint, Box<LazyConstantValue>)> {
-	let (input, _) = kw_version(input)?;
-	let (input, ver) = biguint(input)?;
+fn versioned_constant(input: &str) -> PResult<&str, (BigUint, InputSpan, Box<LazyConstantValue>)> {
+	let (input, (ver, span)) = spanned(preceded(kw_version, biguint))(input)?;
 	let (input, _) = sym_eq(input)?;
 	let (input, value) = constant_value(input)?;
 	let (input, _) = sym_semicolon(input)?;
-	Ok((input, (ver, value)))
+	Ok((input, (ver, span, value)))
 }
 
 // Ex:
@@ -413,10 +667,10 @@ fn versioned_constant(input: &str) -> PResult<&str, (BigUint, Box<LazyConstantVa
 fn constant_defn(latest_version: BigUint, current_package: model::PackageName, imports: ImportMap) -> impl Fn(&str) -> PResult<&str, Box<TopLevelDefinitionAdder>> {
 	move |input| {
 		let (input, _) = kw_const(input)?;
-		let (input, name) = identifier(input)?;
+		let (input, (name, span)) = spanned(identifier)(input)?;
 		let (input, _) = sym_colon(input)?;
 		let (input, t) = type_expr(input)?;
-		let (input, _) = multispace0(input)?;
+		let (input, _) = skip_ws0(input)?;
 		let (input, _) = sym_open_curly(input)?;
 		let (input, versions) = many0(versioned_constant)(input)?;
 		let (input, _) = sym_close_curly(input)?;
@@ -426,10 +680,10 @@ fn constant_defn(latest_version: BigUint, current_package: model::PackageName, i
 
 		let latest_version = latest_version.clone();
 	
-		Ok((input, Box::new(move |model| {
-			let mut constant = model::ConstantBuilder::new(latest_version.clone(), name, t, imports);
-			for (ver, value) in versions {
-				constant.add_version(ver, value()?)?;
+		Ok((input, Box::new(move |model, file| {
+			let mut constant = model::ConstantBuilder::new(latest_version.clone(), name, t, resolve_imports(imports, file), span.resolve(file));
+			for (ver, ver_span, value) in versions {
+				constant.add_version(ver, value(file)?, ver_span.resolve(file))?;
 			}
 			
 			model.add_constant(constant)
@@ -445,40 +699,107 @@ fn variable_declaration_part(input: &str) -> PResult<&str, (String, model::Type)
 	Ok((input, (name, t)))
 }
 
+struct FieldDefinition {
+	name: String,
+	field_type: model::Type,
+	default_value: Option<Box<LazyConstantValue>>,
+	renamed_from: Option<String>,
+	span: InputSpan,
+}
+
+impl FieldDefinition {
+	fn build(self, file: &Arc<SourceFile>) -> Result<(String, model::FieldInfo), model::ModelError> {
+		let default_value = match self.default_value {
+			Some(value) => Some(value(file)?),
+			None => None,
+		};
+
+		Ok((self.name, model::FieldInfo {
+			field_type: self.field_type,
+			default_value,
+			renamed_from: self.renamed_from,
+			span: self.span.resolve(file),
+		}))
+	}
+}
+
 // Ex: name: Type;
-fn field_definition(input: &str) -> PResult<&str, (String, model::FieldInfo)> {
-	let (input, (name, t)) = variable_declaration_part(input)?;
+// Ex: name: Type = value;
+// Ex: name: Type renamed from oldName;
+fn field_definition(input: &str) -> PResult<&str, FieldDefinition> {
+	let (input, ((name, t), span)) = spanned(variable_declaration_part)(input)?;
+	let (input, renamed_from) = opt(preceded(kw_renamed, cut(preceded(kw_from, identifier))))(input)?;
+	let (input, default_value) = opt(preceded(sym_eq, cut(constant_value)))(input)?;
 	let (input, _) = cut(sym_semicolon)(input)?;
 
-	Ok((input, (name, model::FieldInfo {
+	Ok((input, FieldDefinition {
+		name,
 		field_type: t,
-	})))
+		default_value,
+		renamed_from,
+		span,
+	}))
 }
 
-fn param_definition(input: &str) -> PResult<&str, model::ParameterInfo> {
-	let (input, (name, t)) = variable_declaration_part(input)?;
+// Ex: name: Type;
+fn record_field_definition(input: &str) -> PResult<&str, FieldDefinition> {
+	let (input, ((name, t), span)) = spanned(variable_declaration_part)(input)?;
+	let (input, _) = cut(sym_semicolon)(input)?;
 
-	Ok((input, model::ParameterInfo {
-		name: name,
-		param_type: t,
+	Ok((input, FieldDefinition {
+		name,
+		field_type: t,
+		default_value: None,
+		renamed_from: None,
+		span,
 	}))
 }
 
+// Ex: removed name;
+fn removed_field_definition(input: &str) -> PResult<&str, (String, InputSpan)> {
+	let (input, (name, span)) = spanned(preceded(kw_removed, identifier))(input)?;
+	let (input, _) = cut(sym_semicolon)(input)?;
+	Ok((input, (name, span)))
+}
+
+enum TypeVersionItem {
+	Field(FieldDefinition),
+	RemovedField(String, InputSpan),
+}
+
+fn type_version_item(input: &str) -> PResult<&str, TypeVersionItem> {
+	alt((
+		map(removed_field_definition, |(name, span)| TypeVersionItem::RemovedField(name, span)),
+		map(field_definition, TypeVersionItem::Field),
+	))(input)
+}
+
+fn param_definition(input: &str) -> PResult<&str, (String, model::Type, InputSpan)> {
+	let (input, ((name, t), span)) = spanned(variable_declaration_part)(input)?;
+
+	Ok((input, (name, t, span)))
+}
+
 // Ex:
 // version 5 {
 //   ...	
 // }
 fn type_version_definition(input: &str) -> PResult<&str, Box<TypeVersionAdder>> {
-	let (input, _) = kw_version(input)?;
-	let (input, ver) = cut(biguint)(input)?;
+	let (input, (ver, span)) = spanned(preceded(kw_version, cut(biguint)))(input)?;
 	let (input, _) = cut(sym_open_curly)(input)?;
-	let (input, fields_orig) = many0(field_definition)(input)?;
+	let (input, items) = many0(type_version_item)(input)?;
 	let (input, _) = cut(sym_close_curly)(input)?;
 
-	Ok((input, Box::new(|type_def| {
-		let mut ver_type = type_def.add_version(ver)?;
-		for (name, field) in fields_orig {	
-			ver_type.add_field(name, field)?;
+	Ok((input, Box::new(move |type_def, file| {
+		let mut ver_type = type_def.add_version(ver, span.resolve(file))?;
+		for item in items {
+			match item {
+				TypeVersionItem::Field(field) => {
+					let (name, field) = field.build(file)?;
+					ver_type.add_field(name, field)?;
+				},
+				TypeVersionItem::RemovedField(name, span) => ver_type.add_removed_field(name, span.resolve(file))?,
+			}
 		}
 		Ok(())
 	})))
@@ -487,7 +808,7 @@ fn type_version_definition(input: &str) -> PResult<&str, Box<TypeVersionAdder>>
 // Ex:
 // funcName<T1, T2>(arg1: A1, arg2: A2): R;
 fn method_definition(input: &str) -> PResult<&str, Box<InterfaceMethodAdder>> {
-	let (input, name) = identifier(input)?;
+	let (input, (name, span)) = spanned(identifier)(input)?;
 	let (input, type_params) = opt(type_param_list)(input)?;
 	let type_params = type_params.unwrap_or(Vec::new());
 
@@ -499,10 +820,14 @@ fn method_definition(input: &str) -> PResult<&str, Box<InterfaceMethodAdder>> {
 	let (input, t) = cut(type_expr)(input)?;
 	let (input, _) = cut(sym_semicolon)(input)?;
 
-	Ok((input, Box::new(|ver_builder| {
-		let mut method = ver_builder.add_method(name, t)?;
+	Ok((input, Box::new(move |ver_builder, file| {
+		let mut method = ver_builder.add_method(name, t, span.resolve(file))?;
 		type_params.into_iter().try_for_each(|p| method.add_type_param(p))?;
-		params.into_iter().try_for_each(|p| method.add_param(p))?;
+		params.into_iter().try_for_each(|(name, param_type, span)| method.add_param(model::ParameterInfo {
+			name,
+			param_type,
+			span: span.resolve(file),
+		}))?;
 		Ok(())
 	})))
 }
@@ -512,16 +837,15 @@ fn method_definition(input: &str) -> PResult<&str, Box<InterfaceMethodAdder>> {
 //   ...	
 // }
 fn interface_version_definition(input: &str) -> PResult<&str, Box<InterfaceVersionAdder>> {
-	let (input, _) = kw_version(input)?;
-	let (input, ver) = cut(biguint)(input)?;
+	let (input, (ver, span)) = spanned(preceded(kw_version, cut(biguint)))(input)?;
 	let (input, _) = cut(sym_open_curly)(input)?;
 	let (input, methods_adders) = many0(method_definition)(input)?;
 	let (input, _) = cut(sym_close_curly)(input)?;
 
-	Ok((input, Box::new(|type_def| {
-		let mut ver_type = type_def.add_version(ver)?;
+	Ok((input, Box::new(move |type_def, file| {
+		let mut ver_type = type_def.add_version(ver, span.resolve(file))?;
 		for method_adder in methods_adders {	
-			method_adder(&mut ver_type)?;
+			method_adder(&mut ver_type, file)?;
 		}
 		Ok(())
 	})))
@@ -554,7 +878,7 @@ fn versioned_type_definition(latest_version: BigUint, current_package: model::Pa
 			map(kw_struct, |_| false),
 		))(input)?;
 	
-		let (input, name) = cut(identifier)(input)?;
+		let (input, (name, span)) = spanned(cut(identifier))(input)?;
 		let (input, type_params) = opt(type_param_list)(input)?;
 		let type_params = type_params.unwrap_or(Vec::new());
 		
@@ -567,10 +891,10 @@ fn versioned_type_definition(latest_version: BigUint, current_package: model::Pa
 	
 		let latest_version = latest_version.clone();
 		
-		Ok((input, Box::new(move |model| {
-			let mut type_def = model::VersionedTypeDefinitionBuilder::new(latest_version, name, type_params, is_final, imports);
+		Ok((input, Box::new(move |model, file| {
+			let mut type_def = model::VersionedTypeDefinitionBuilder::new(latest_version, name, type_params, is_final, resolve_imports(imports, file), span.resolve(file));
 			for adder in versions {
-				adder(&mut type_def)?;
+				adder(&mut type_def, file)?;
 			}
 
 			if is_enum {
@@ -584,69 +908,98 @@ fn versioned_type_definition(latest_version: BigUint, current_package: model::Pa
 }
 
 fn extern_literal_integer(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("integer")(input)?;
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, open) = one_of("[(")(input)?;
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, lower) = opt(bigint)(input)?;
 	let (input, _) = sym_comma(input)?;
 	let (input, upper) = opt(bigint)(input)?;
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, close) = one_of("])")(input)?;
 
 	let bound = |ch: char| if ch == '(' { model::ExternLiteralIntBound::Exclusive } else { model::ExternLiteralIntBound::Inclusive };
 
-	Ok((input, Box::new(move |type_def| type_def.add_integer_literal(bound(open), lower, bound(close), upper))))
+	Ok((input, Box::new(move |type_def, _, span| type_def.add_integer_literal(bound(open), lower, bound(close), upper, span))))
 }
 
 fn extern_literal_string(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("string")(input)?;
 
-	Ok((input, Box::new(model::ExternTypeDefinitionBuilder::add_string_literal)))
+	Ok((input, Box::new(|type_def, _, span| type_def.add_string_literal(span))))
+}
+
+fn extern_literal_boolean(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("boolean")(input)?;
+
+	Ok((input, Box::new(|type_def, _, span| type_def.add_boolean_literal(span))))
+}
+
+fn extern_literal_float(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("float")(input)?;
+
+	Ok((input, Box::new(|type_def, _, span| type_def.add_float_literal(span))))
 }
 
+// Ex: sequence A
+// Ex: sequence A [N]
 fn extern_literal_sequence(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("sequence")(input)?;
-	let (input, _) = multispace1(input)?;
+	let (input, _) = skip_ws1(input)?;
 	let (input, element_type) = type_expr(input)?;
+	let (input, length) = opt(delimited(sym_open_bracket, type_arg, sym_close_bracket))(input)?;
 
-	Ok((input, Box::new(|type_def| type_def.add_sequence_literal(element_type))))
+	Ok((input, Box::new(|type_def, _, span| type_def.add_sequence_literal(element_type, length, span))))
 }
 
 
+fn extern_literal_map(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
+	let (input, _) = skip_ws0(input)?;
+	let (input, _) = tag("map")(input)?;
+	let (input, _) = skip_ws1(input)?;
+	let (input, key_type) = type_expr(input)?;
+	let (input, _) = skip_ws1(input)?;
+	let (input, value_type) = type_expr(input)?;
+
+	Ok((input, Box::new(|type_def, _, span| type_def.add_map_literal(key_type, value_type, span))))
+}
+
 fn extern_literal_case(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("case")(input)?;
 	let (input, name) = identifier(input)?;
 	let (input, _) = sym_open_paren(input)?;
 	let (input, params) = separated_list0(sym_comma, type_expr)(input)?;
 	let (input, _) = sym_close_paren(input)?;
 
-	Ok((input, Box::new(|type_def| type_def.add_case_literal(name, params))))
+	Ok((input, Box::new(|type_def, _, span| type_def.add_case_literal(name, params, span))))
 }
 
 
 fn extern_literal_record(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, _) = multispace0(input)?;
+	let (input, _) = skip_ws0(input)?;
 	let (input, _) = tag("record")(input)?;
 	let (input, _) = sym_open_curly(input)?;
-	let (input, fields) = many0(field_definition)(input)?;
+	let (input, fields) = many0(record_field_definition)(input)?;
 	let (input, _) = sym_close_curly(input)?;
 
-	Ok((input, Box::new(|type_def| {
-		let mut record = type_def.add_record_literal()?;
-		for (name, field) in fields {
+	Ok((input, Box::new(|type_def, file, span| {
+		let mut record = type_def.add_record_literal(span)?;
+		for field in fields {
+			let (name, field) = field.build(file)?;
 			record.add_field(name, field)?;
 		}
 		Ok(())
 	})))
 }
 
-fn extern_literal(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
-	let (input, literal) = alt((extern_literal_integer, extern_literal_string, extern_literal_sequence, extern_literal_case, extern_literal_record))(input)?;
+fn extern_literal(input: &str) -> PResult<&str, (Box<ExternLiteralAdder>, InputSpan)> {
+	let (input, literal) = spanned(alt((extern_literal_integer, extern_literal_string, extern_literal_boolean, extern_literal_float, extern_literal_sequence, extern_literal_map, extern_literal_case, extern_literal_record)))(input)?;
 	let (input, _) = sym_semicolon(input)?;
 
 	Ok((input, literal))
@@ -657,7 +1010,7 @@ fn extern_literal(input: &str) -> PResult<&str, Box<ExternLiteralAdder>> {
 // literal {
 //   ...
 // }
-fn extern_literal_block(input: &str) -> PResult<&str, Vec<Box<ExternLiteralAdder>>> {
+fn extern_literal_block(input: &str) -> PResult<&str, Vec<(Box<ExternLiteralAdder>, InputSpan)>> {
 	let (input, _) = kw_literal(input)?;
 	let (input, _) = sym_open_curly(input)?;
 	
@@ -669,6 +1022,15 @@ fn extern_literal_block(input: &str) -> PResult<&str, Vec<Box<ExternLiteralAdder
 }
 
 
+// Ex: <A, const N>
+fn extern_type_param_list(input: &str) -> PResult<&str, Vec<(String, bool)>> {
+	let (input, _) = sym_open_angle(input)?;
+	let (input, result) = separated_list1(sym_comma, map(pair(opt(kw_const), identifier), |(is_const, param)| (param, is_const.is_some())))(input)?;
+	let (input, _) = sym_close_angle(input)?;
+
+	Ok((input, result))
+}
+
 // Ex:
 // extern Name {
 //   ...
@@ -676,9 +1038,11 @@ fn extern_literal_block(input: &str) -> PResult<&str, Vec<Box<ExternLiteralAdder
 fn extern_type_definition(current_package: model::PackageName, imports: ImportMap) -> impl Fn(&str) -> PResult<&str, Box<TopLevelDefinitionAdder>> {
 	move |input| {
 		let (input, _) = kw_extern(input)?;
-		let (input, name) = identifier(input)?;
-		let (input, type_params) = opt(type_param_list)(input)?;
+		let (input, (name, span)) = spanned(identifier)(input)?;
+		let (input, type_params) = opt(extern_type_param_list)(input)?;
 		let type_params = type_params.unwrap_or(Vec::new());
+		let const_params = type_params.iter().filter(|(_, is_const)| *is_const).map(|(param, _)| param.clone()).collect::<HashSet<_>>();
+		let type_params = type_params.into_iter().map(|(param, _)| param).collect::<Vec<_>>();
 	
 		let (input, _) = sym_open_curly(input)?;
 		
@@ -691,10 +1055,10 @@ fn extern_type_definition(current_package: model::PackageName, imports: ImportMa
 		let imports = imports.clone();
 	
 	
-		Ok((input, Box::new(|model| {
-			let mut type_def = model::ExternTypeDefinitionBuilder::new(name, type_params, imports);
-			for literal_adder in literals {
-				literal_adder(&mut type_def)?;
+		Ok((input, Box::new(move |model, file| {
+			let mut type_def = model::ExternTypeDefinitionBuilder::new(name, type_params, const_params, resolve_imports(imports, file), span.resolve(file));
+			for (literal_adder, literal_span) in literals {
+				literal_adder(&mut type_def, file, literal_span.resolve(file))?;
 			}
 			model.add_extern_type(type_def)
 		})))
@@ -713,7 +1077,7 @@ fn interface_type_definition(latest_version: BigUint, current_package: model::Pa
 
 		let (input, _) = kw_interface(input)?;
 	
-		let (input, name) = cut(identifier)(input)?;
+		let (input, (name, span)) = spanned(cut(identifier))(input)?;
 		let (input, type_params) = opt(type_param_list)(input)?;
 		let type_params = type_params.unwrap_or(Vec::new());
 		
@@ -726,10 +1090,10 @@ fn interface_type_definition(latest_version: BigUint, current_package: model::Pa
 	
 		let latest_version = latest_version.clone();
 		
-		Ok((input, Box::new(move |model| {
-			let mut type_def = model::InterfaceTypeDefinitionBuilder::new(latest_version, name, type_params, is_final, imports);
+		Ok((input, Box::new(move |model, file| {
+			let mut type_def = model::InterfaceTypeDefinitionBuilder::new(latest_version, name, type_params, is_final, resolve_imports(imports, file), span.resolve(file));
 			for adder in versions {
-				adder(&mut type_def)?;
+				adder(&mut type_def, file)?;
 			}
 
 			model.add_interface(type_def)
@@ -738,39 +1102,219 @@ fn interface_type_definition(latest_version: BigUint, current_package: model::Pa
 }
 
 
+// Ex: magic [86, 76, 90, 0];
+fn format_magic(input: &str) -> PResult<&str, Vec<u8>> {
+	let (input, _) = kw_magic(input)?;
+	let (input, _) = cut(sym_open_bracket)(input)?;
+	let (input, magic) = cut(separated_list1(sym_comma, map_opt(biguint, |b| b.to_u8())))(input)?;
+	let (input, _) = cut(sym_close_bracket)(input)?;
+	let (input, _) = cut(sym_semicolon)(input)?;
+	Ok((input, magic))
+}
+
+// Ex: version nat;
+fn format_version_encoding(input: &str) -> PResult<&str, model::FormatVersionEncoding> {
+	let (input, _) = kw_version(input)?;
+	let (input, encoding) = cut(alt((
+		value(model::FormatVersionEncoding::Nat, tag("nat")),
+		value(model::FormatVersionEncoding::U8, tag("u8")),
+		value(model::FormatVersionEncoding::U16, tag("u16")),
+		value(model::FormatVersionEncoding::U32, tag("u32")),
+	)))(input)?;
+	let (input, _) = cut(sym_semicolon)(input)?;
+	Ok((input, encoding))
+}
+
+// Ex:
+// format Name {
+//     root Type;
+//     magic [...];
+//     version nat;
+// }
+fn format_definition(current_package: model::PackageName, imports: ImportMap) -> impl Fn(&str) -> PResult<&str, Box<TopLevelDefinitionAdder>> {
+	move |input| {
+		let (input, _) = kw_format(input)?;
+		let (input, (name, span)) = spanned(cut(identifier))(input)?;
+		let (input, _) = cut(sym_open_curly)(input)?;
+		let (input, _) = cut(kw_root)(input)?;
+		let (input, root_type) = cut(type_expr)(input)?;
+		let (input, _) = cut(sym_semicolon)(input)?;
+		let (input, magic) = cut(format_magic)(input)?;
+		let (input, version_encoding) = cut(format_version_encoding)(input)?;
+		let (input, _) = cut(sym_close_curly)(input)?;
+
+		let name = model::QualifiedName { package: current_package.clone(), name };
+		let imports = imports.clone();
+
+		Ok((input, Box::new(move |model, file| {
+			model.add_format(model::FileFormatBuilder::new(name, root_type, magic, version_encoding, resolve_imports(imports, file), span.resolve(file)))
+		})))
+	}
+}
+
 
 fn top_level_definition(latest_version: BigUint, current_package: model::PackageName, imports: ImportMap) -> impl Fn(&str) -> PResult<&str, Box<TopLevelDefinitionAdder>> {
 	move |input| alt((
 		constant_defn(latest_version.clone(), current_package.clone(), imports.clone()),
 		versioned_type_definition(latest_version.clone(), current_package.clone(), imports.clone()),
 		extern_type_definition(current_package.clone(), imports.clone()),
-		interface_type_definition(latest_version.clone(), current_package.clone(), imports.clone())
+		interface_type_definition(latest_version.clone(), current_package.clone(), imports.clone()),
+		format_definition(current_package.clone(), imports.clone())
 	))(input)
 }
 
 
-pub fn parse_model(input: &str) -> PResult<&str, Box<LazyModel>> {
+const TOP_LEVEL_KEYWORDS: &[&str] = &["const", "enum", "struct", "extern", "interface", "final", "format"];
+
+// Skips past a definition that failed to parse.
+// Parsing resumes at the next line that starts with a keyword that can begin a definition.
+fn recover_definition(input: &str) -> &str {
+	let mut rest = input;
+	while let Some(index) = rest.find('\n') {
+		rest = &rest[index + 1..];
+
+		let line = rest.trim_start_matches([' ', '\t', '\r']);
+		let starts_definition = TOP_LEVEL_KEYWORDS.iter().any(|kw| {
+			line.strip_prefix(kw)
+				.and_then(|after| after.chars().next())
+				.is_some_and(char::is_whitespace)
+		});
+
+		if starts_definition {
+			return rest
+		}
+	}
+
+	&input[input.len()..]
+}
+
+fn model_definition(input: &str) -> PResult<&str, (Box<LazyModel>, Vec<nom::error::Error<&str>>)> {
 	let (input, latest_ver) = version_directive(input)?;
 	let (input, package) = opt(package_directive)(input)?;
 	let package =
 			if let Some(pkg) = package { pkg }
 			else { model::PackageName { package: Vec::new() } };
 
+	let (input, import_list) = many0(spanned(import_directive))(input)?;
 
+	let mut imports = ImportMap::new();
+	let mut duplicate_imports = Vec::new();
+	for ((alias, name), span) in import_list {
+		if imports.insert(alias.clone(), (name, span)).is_some() {
+			duplicate_imports.push((alias, span));
+		}
+	}
 
-	let (input, defs) = many0(top_level_definition(latest_ver, package, HashMap::new()))(input)?;
-	let (input, _) = multispace0(input)?;
-	let (input, _) = eof(input)?;
-
+	let definition = top_level_definition(latest_ver, package, imports);
+	let mut defs = Vec::new();
+	let mut errors = Vec::new();
+	let mut input = input;
+	loop {
+		let (rest, _) = skip_ws0(input)?;
+		if let Ok((rest, _)) = eof::<_, nom::error::Error<&str>>(rest) {
+			input = rest;
+			break;
+		}
 
+		match definition(rest) {
+			Ok((rest, def)) => {
+				defs.push(def);
+				input = rest;
+			},
+			Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => {
+				// An unterminated block comment runs to the end of the file, so there is nothing left to recover.
+				input =
+					if err.code == nom::error::ErrorKind::TakeUntil { &rest[rest.len()..] }
+					else { recover_definition(rest) };
+				errors.push(err);
+			},
+			Err(err) => return Err(err),
+		}
+	}
 
-	Ok((input, Box::new(move || {
+	Ok((input, (Box::new(move |file| {
 		let mut model = model::Verilization::new();
+		let mut errors = Vec::new();
+
+		for (alias, span) in duplicate_imports {
+			errors.push(model::ModelError::DuplicateImport(alias, span.resolve(file)));
+		}
 	
 		for def_adder in defs.into_iter() {
-			def_adder(&mut model)?;
+			if let Err(err) = def_adder(&mut model, file) {
+				errors.push(err);
+			}
 		}
 
-		Ok(model)
-	})))
+		if errors.is_empty() {
+			Ok(model)
+		}
+		else {
+			Err(errors)
+		}
+	}), errors)))
+}
+
+// The span of a parse error covers the token where parsing failed.
+// For an unterminated block comment, this is the opening "/*".
+fn parse_error(file: &Arc<SourceFile>, err: nom::error::Error<&str>) -> VError {
+	let offset = file.content().len() - err.input.len();
+
+	let (start, end) =
+		if err.code == nom::error::ErrorKind::TakeUntil { (offset - 2, offset) }
+		else {
+			let token_len = match err.input.find(|ch: char| !(ch.is_alphanumeric() || ch == '_')) {
+				Some(0) => err.input.chars().next().map_or(0, char::len_utf8),
+				Some(len) => len,
+				None => err.input.len(),
+			};
+			(offset, offset + token_len)
+		};
+
+	VError::ParseError(ParseError {
+		kind: err.code,
+		span: model::Span::new(file.clone(), start, end),
+	})
+}
+
+/// Parses a type expression such as `list(a.b.C)`.
+/// 
+/// Returns `None` if the input is not a single type expression.
+pub fn parse_type(input: &str) -> Option<model::Type> {
+	let (input, t) = type_expr(input).ok()?;
+	let (input, _) = skip_ws0(input).ok()?;
+	if input.is_empty() { Some(t) } else { None }
+}
+
+/// Parses a constant value such as `{ x = some(1); }`.
+/// 
+/// Names of other constants are not resolved.
+pub fn parse_constant_value(file: &Arc<SourceFile>) -> Result<model::ConstantValue, VError> {
+	let (_, value) = terminated(constant_value, preceded(skip_ws0, eof))(file.content()).map_err(|err| match err {
+		nom::Err::Incomplete(_) => VError::ParseIncompleteError,
+		nom::Err::Error(err) | nom::Err::Failure(err) => parse_error(file, err),
+	})?;
+
+	Ok(value(file)?)
+}
+
+/// Parses a source file into a model.
+/// 
+/// Parsing continues after a definition that contains an error so that every error in the file is reported.
+pub fn parse_model(file: &Arc<SourceFile>) -> Result<model::Verilization, VError> {
+	let (_, (model, parse_errors)) = model_definition(file.content()).map_err(|err| match err {
+		nom::Err::Incomplete(_) => VError::ParseIncompleteError,
+		nom::Err::Error(err) | nom::Err::Failure(err) => parse_error(file, err),
+	})?;
+
+	let mut errors: Vec<VError> = parse_errors.into_iter().map(|err| parse_error(file, err)).collect();
+
+	match model(file) {
+		Ok(model) if errors.is_empty() => Ok(model),
+		Ok(_) => Err(VError::from_errors(errors)),
+		Err(model_errors) => {
+			errors.extend(model_errors.into_iter().map(VError::from));
+			Err(VError::from_errors(errors))
+		},
+	}
 }
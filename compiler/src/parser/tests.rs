@@ -0,0 +1,361 @@
+use crate::{model, VError};
+use crate::parser::{parse_model, parse_constant_value};
+use crate::source::SourceFile;
+
+fn parse(file_data: &str) -> Result<model::Verilization, VError> {
+    parse_model(&SourceFile::new(String::from("test.verilization"), String::from(file_data)))
+}
+
+fn run_parse_test(file_data: &str) -> model::Verilization {
+    parse(file_data).unwrap()
+}
+
+#[test]
+fn comments_as_whitespace() {
+    let model = run_parse_test(
+"
+// Leading comment
+version /* latest */ 2;
+package /* pkg */ a.b; // trailing
+
+/* block
+   comment */
+struct /* name: */ A {
+    version 1 { // open
+        // inside version
+        x: /* type */ u32; /* after field */
+    }
+}
+
+extern E {
+    literal {
+        // inside literal
+        integer [ /* lower */ 0, 10 /* upper */ ];
+        string;
+    }
+}
+
+const c: /* c type */ list(E) {
+    version 1 = /* value */ [ 5, /* elem */ 6 ] // between
+        ;
+}
+"
+    );
+
+    let name = model::QualifiedName::from_str("a.b.A").unwrap();
+    assert!(model.get_type(&name).is_some());
+
+    let name = model::QualifiedName::from_str("a.b.c").unwrap();
+    assert!(model.get_constant(&name).is_some());
+}
+
+#[test]
+fn unterminated_block_comment() {
+    match parse("version 1; /* struct A {}") {
+        Err(err @ VError::ParseError(_)) => {
+            assert_eq!(err.code(), Some("P0002"));
+            assert_eq!(err.to_string(), "Unterminated block comment.");
+            assert_eq!(err.span().unwrap().to_string(), "test.verilization:1:12");
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn duplicate_import() {
+    let result = parse(
+"
+version 1;
+import a.A;
+import b.B as A;
+import c.C;
+import d.D as C;
+import e.E as A;
+"
+    );
+
+    match result {
+        Err(VError::Multiple(errors)) => {
+            let duplicates: Vec<(String, String)> = errors.iter().map(|err| match err {
+                VError::ModelError(model::ModelError::DuplicateImport(name, span)) => (name.clone(), span.to_string()),
+                _ => panic!("{:?}", err),
+            }).collect();
+            assert_eq!(duplicates, vec!(
+                (String::from("A"), String::from("test.verilization:4:1")),
+                (String::from("C"), String::from("test.verilization:6:1")),
+                (String::from("A"), String::from("test.verilization:7:1")),
+            ));
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn parse_error_span() {
+    match parse("version 1;\nstruct A {\n    version 1 {\n        a A;\n    }\n}\n") {
+        Err(VError::ParseError(err)) => {
+            assert_eq!(err.span.to_string(), "test.verilization:4:11");
+            assert_eq!(err.code(), "P0001");
+            assert_eq!(err.to_string(), "Syntax error. Unexpected `A`.");
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn recovers_after_definition_error() {
+    let result = parse(
+"version 1;
+struct A {
+    version 1 {
+        a A;
+    }
+}
+
+struct B {
+    version 1 {
+        b: u32;
+    }
+}
+
+enum C {
+    version 1 {
+        c u32;
+    }
+}
+"
+    );
+
+    match result {
+        Err(VError::Multiple(errors)) => {
+            let spans: Vec<String> = errors.iter().map(|err| match err {
+                VError::ParseError(err) => err.span.to_string(),
+                _ => panic!("{:?}", err),
+            }).collect();
+            assert_eq!(spans, vec!("test.verilization:4:11", "test.verilization:16:11"));
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn constant_value() {
+    let parse_value = |data: &str| parse_constant_value(&SourceFile::new(String::from("value.txt"), String::from(data)));
+
+    match parse_value(" [\"a\\\"b\\n\", some(-3), none()] ").unwrap() {
+        model::ConstantValue::Sequence(values) => match values.as_slice() {
+            [model::ConstantValue::String(s), model::ConstantValue::Case(some, some_args), model::ConstantValue::Case(none, none_args)] => {
+                assert_eq!(s, "a\"b\n");
+                assert_eq!(some, "some");
+                assert!(matches!(some_args.as_slice(), [model::ConstantValue::Integer(n)] if *n == (-3).into()));
+                assert_eq!(none, "none");
+                assert!(none_args.is_empty());
+            },
+            _ => panic!("{:?}", values),
+        },
+        value => panic!("{:?}", value),
+    }
+
+    match parse_value("[1.5, -2e3, 7]").unwrap() {
+        model::ConstantValue::Sequence(values) => match values.as_slice() {
+            [model::ConstantValue::Float(a), model::ConstantValue::Float(b), model::ConstantValue::Integer(c)] => {
+                assert_eq!(*a, 1.5);
+                assert_eq!(*b, -2000.0);
+                assert_eq!(*c, 7.into());
+            },
+            _ => panic!("{:?}", values),
+        },
+        value => panic!("{:?}", value),
+    }
+
+    match parse_value("[true, false]").unwrap() {
+        model::ConstantValue::Sequence(values) => assert!(matches!(values.as_slice(), [model::ConstantValue::Boolean(true), model::ConstantValue::Boolean(false)])),
+        value => panic!("{:?}", value),
+    }
+
+    match parse_value("[\"a\" => 1, \"b\" => [2]]").unwrap() {
+        model::ConstantValue::Map(entries) => match entries.as_slice() {
+            [(model::ConstantValue::String(a), model::ConstantValue::Integer(n)), (model::ConstantValue::String(b), model::ConstantValue::Sequence(values))] => {
+                assert_eq!(a, "a");
+                assert_eq!(*n, 1.into());
+                assert_eq!(b, "b");
+                assert!(matches!(values.as_slice(), [model::ConstantValue::Integer(n)] if *n == 2.into()));
+            },
+            _ => panic!("{:?}", entries),
+        },
+        value => panic!("{:?}", value),
+    }
+
+    match parse_value("{ x = 1; } y") {
+        Err(VError::ParseError(err)) => assert_eq!(err.span.to_string(), "value.txt:1:12"),
+        result => panic!("{:?}", result),
+    }
+}
+
+#[test]
+fn duplicate_boolean_literal() {
+    let result = parse(
+"
+version 1;
+package a;
+extern bool {
+    literal {
+        boolean;
+        boolean;
+    }
+}
+"
+    );
+
+    match result {
+        Err(VError::ModelError(err @ model::ModelError::DuplicateLiteralBoolean(..))) => {
+            assert_eq!(err.code(), "M0017");
+            assert_eq!(err.to_string(), "Type a.bool already has a boolean literal.");
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn field_default_value() {
+    let model = run_parse_test(
+"
+version 2;
+package a;
+struct A {
+    version 1 {
+        x: u32;
+    }
+    version 2 {
+        x: u32;
+        y: u32 = 4;
+        z: B = { w = [ 1 ]; };
+    }
+}
+"
+    );
+
+    let name = model::QualifiedName::from_str("a.A").unwrap();
+    let ver_type = match model.get_type(&name) {
+        Some(model::NamedTypeDefinition::StructType(t)) => t.versioned(&2u32.into()).unwrap().ver_type,
+        _ => panic!(),
+    };
+
+    let defaults: Vec<(&str, Option<&model::ConstantValue>)> = ver_type.fields().iter().map(|(name, field)| (name.as_str(), field.default_value.as_ref())).collect();
+    match defaults.as_slice() {
+        [("x", None), ("y", Some(model::ConstantValue::Integer(n))), ("z", Some(model::ConstantValue::Record(_)))] => assert_eq!(*n, 4.into()),
+        _ => panic!("{:?}", defaults),
+    }
+}
+
+#[test]
+fn renamed_and_removed_fields() {
+    let model = run_parse_test(
+"
+version 2;
+package a;
+struct A {
+    version 1 {
+        x: u32;
+        y: u32;
+        removed: u32;
+    }
+    version 2 {
+        z: u32 renamed from x;
+        removed y;
+        removed removed;
+    }
+}
+"
+    );
+
+    let name = model::QualifiedName::from_str("a.A").unwrap();
+    let t = match model.get_type(&name) {
+        Some(model::NamedTypeDefinition::StructType(t)) => t,
+        _ => panic!(),
+    };
+
+    let ver1 = t.versioned(&1u32.into()).unwrap().ver_type;
+    let fields: Vec<&str> = ver1.fields().iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(fields, vec!("x", "y", "removed"));
+
+    let ver2 = t.versioned(&2u32.into()).unwrap().ver_type;
+    let renames: Vec<(&str, Option<&str>)> = ver2.fields().iter().map(|(name, field)| (name.as_str(), field.renamed_from.as_deref())).collect();
+    assert_eq!(renames, vec!(("z", Some("x"))));
+    let removed: Vec<&str> = ver2.removed_fields().iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(removed, vec!("y", "removed"));
+}
+
+#[test]
+fn format_definition() {
+    let model = run_parse_test(
+"
+version 1;
+package a;
+struct A {
+    version 1 {
+        x: u32;
+    }
+}
+
+format F {
+    root A;
+    magic [86, 76, 90, 0];
+    version u16;
+}
+"
+    );
+
+    let name = model::QualifiedName::from_str("a.F").unwrap();
+    let format = model.get_format(&name).unwrap();
+    assert_eq!(format.root_type().name, model::QualifiedName::from_str("A").unwrap());
+    assert_eq!(format.magic(), &vec!(86, 76, 90, 0));
+    assert_eq!(format.version_encoding(), model::FormatVersionEncoding::U16);
+}
+
+#[test]
+fn const_type_parameters() {
+    let model = run_parse_test(
+"
+version 1;
+package a;
+extern array<A, const N> {
+    literal {
+        sequence A [N];
+    }
+}
+
+struct Hash {
+    version 1 {
+        digest: array(u8, 32);
+    }
+}
+"
+    );
+
+    let name = model::QualifiedName::from_str("a.array").unwrap();
+    let array_type = model.get_type(&name).unwrap();
+    assert_eq!(array_type.type_params(), &vec!(String::from("A"), String::from("N")));
+    assert!(!array_type.is_const_param("A"));
+    assert!(array_type.is_const_param("N"));
+
+    let name = model::QualifiedName::from_str("a.Hash").unwrap();
+    let hash_type = match model.get_type(&name) {
+        Some(model::NamedTypeDefinition::StructType(t)) => t,
+        _ => panic!(),
+    };
+    let ver_type = hash_type.versions().next().unwrap().ver_type;
+    let args: Vec<String> = ver_type.fields()[0].1.field_type.args.iter().map(|arg| arg.to_string()).collect();
+    assert_eq!(args, vec!(String::from("u8"), String::from("32")));
+}
+
+#[test]
+fn format_magic_out_of_range() {
+    match parse("version 1; package a; format F { root A; magic [256]; version nat; }") {
+        Err(VError::ParseError(err)) => {
+            assert_eq!(err.span.to_string(), "test.verilization:1:49");
+            assert_eq!(err.to_string(), "Syntax error. Unexpected `256`.");
+        },
+        _ => panic!()
+    }
+}
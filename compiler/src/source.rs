@@ -0,0 +1,134 @@
+//! Source files and locations within them.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+/// A file that a model was parsed from.
+#[derive(Debug)]
+pub struct SourceFile {
+	name: String,
+	content: String,
+	line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+	pub fn new(name: String, content: String) -> Arc<SourceFile> {
+		let line_starts = std::iter::once(0)
+			.chain(content.match_indices('\n').map(|(index, _)| index + 1))
+			.collect();
+
+		Arc::new(SourceFile {
+			name,
+			content,
+			line_starts,
+		})
+	}
+
+	/// The name of the file, usually the path that it was loaded from.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// The text of the file.
+	pub fn content(&self) -> &str {
+		&self.content
+	}
+
+	/// Gets the line and column (both starting at 1) of a byte offset into the file.
+	pub fn line_col(&self, offset: usize) -> (usize, usize) {
+		let line = match self.line_starts.binary_search(&offset) {
+			Ok(line) => line,
+			Err(line) => line - 1,
+		};
+
+		let col = self.content[self.line_starts[line]..offset].chars().count() + 1;
+		(line + 1, col)
+	}
+
+	/// Gets the text of a line (starting at 1) without the line terminator.
+	pub fn line(&self, line: usize) -> &str {
+		let start = self.line_starts[line - 1];
+		let end = self.line_starts.get(line).copied().unwrap_or(self.content.len());
+		self.content[start..end].trim_end_matches(['\n', '\r'])
+	}
+}
+
+/// A range of text within a source file.
+/// 
+/// Spans are shared so that they stay small and cheap to clone in errors and model elements.
+#[derive(Clone)]
+pub struct Span(Arc<SpanData>);
+
+struct SpanData {
+	file: Arc<SourceFile>,
+	start: usize,
+	end: usize,
+}
+
+impl Span {
+	/// Creates a span from byte offsets into the file.
+	pub fn new(file: Arc<SourceFile>, start: usize, end: usize) -> Span {
+		Span(Arc::new(SpanData {
+			file,
+			start,
+			end,
+		}))
+	}
+
+	pub fn file(&self) -> &Arc<SourceFile> {
+		&self.0.file
+	}
+
+	/// The byte offset of the start of the span.
+	pub fn start(&self) -> usize {
+		self.0.start
+	}
+
+	/// The byte offset of the end of the span.
+	pub fn end(&self) -> usize {
+		self.0.end
+	}
+
+	/// Orders spans by the name of their file and then by their start offset.
+	pub fn cmp_location(&self, other: &Span) -> Ordering {
+		(self.0.file.name(), self.0.start).cmp(&(other.0.file.name(), other.0.start))
+	}
+
+	/// Gets the line and column (both starting at 1) of the start of the span.
+	pub fn line_col(&self) -> (usize, usize) {
+		self.0.file.line_col(self.0.start)
+	}
+
+	/// Formats the first line of the span with the spanned text underlined.
+	pub fn snippet(&self) -> String {
+		let (line, col) = self.line_col();
+		let text = self.0.file.line(line);
+
+		let line_num = line.to_string();
+		let gutter = " ".repeat(line_num.len());
+
+		// Reuse tabs from the line so that the underline stays aligned.
+		let indent: String = text.chars().take(col - 1).map(|ch| if ch == '\t' { '\t' } else { ' ' }).collect();
+
+		let (end_line, end_col) = self.0.file.line_col(self.0.end);
+		let width =
+			if end_line == line && end_col > col { end_col - col }
+			else { text.chars().count().saturating_sub(col - 1).max(1) };
+
+		format!("{0} |\n{1} | {2}\n{0} | {3}{4}\n", gutter, line_num, text, indent, "^".repeat(width))
+	}
+}
+
+impl fmt::Display for Span {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let (line, col) = self.line_col();
+		write!(f, "{}:{}:{}", self.0.file.name, line, col)
+	}
+}
+
+impl fmt::Debug for Span {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
@@ -0,0 +1,316 @@
+//! Finds the types and constants that names in a source file refer to.
+//!
+//! This is used by editor tooling for navigation, so names are found from the tokens of the file rather than the model,
+//! which does not keep the location of every reference.
+
+use crate::model;
+use crate::format::{tokenize, Token, TokenKind};
+use crate::source::SourceFile;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Words with special meaning in source files.
+pub const KEYWORDS: &[&str] = &[
+	"version", "package", "import", "as",
+	"const", "enum", "struct", "extern", "interface", "final",
+	"literal", "integer", "string", "boolean", "float", "sequence", "map", "case", "record",
+	"true", "false",
+	"renamed", "from", "removed",
+	"format", "root", "magic",
+];
+
+/// The kind of definition that a symbol refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+	Type,
+	Constant,
+}
+
+/// A name in a source file that refers to a type or constant.
+#[derive(Clone, Debug)]
+pub struct Symbol {
+	pub name: model::QualifiedName,
+	pub kind: SymbolKind,
+	pub span: model::Span,
+	pub is_definition: bool,
+}
+
+/// The symbols in a source file.
+#[derive(Clone, Debug)]
+pub struct FileSymbols {
+	pub package: model::PackageName,
+	pub symbols: Vec<Symbol>,
+}
+
+impl FileSymbols {
+	/// Gets the symbol at a byte offset into the file.
+	pub fn symbol_at(&self, offset: usize) -> Option<&Symbol> {
+		self.symbols.iter().find(|symbol| symbol.span.start() <= offset && offset <= symbol.span.end())
+	}
+}
+
+// The definition that contains the current token.
+enum Definition<'model> {
+	Type(model::NamedTypeDefinition<'model>),
+	Constant(model::Named<'model, model::Constant>),
+	Format(model::Named<'model, model::FileFormat>),
+}
+
+impl <'model> Definition<'model> {
+	fn scope(&self) -> model::Scope<'model> {
+		match self {
+			Definition::Type(t) => t.scope(),
+			Definition::Constant(constant) => constant.scope(),
+			Definition::Format(format) => format.scope(),
+		}
+	}
+}
+
+// Reads a dotted name starting at a token.
+// Returns the name and the index of the token after it.
+fn read_name(tokens: &[&Token], start: usize) -> Option<(model::QualifiedName, usize)> {
+	let is_name_part = |token: &Token| token.kind == TokenKind::Word && token.text.starts_with(|ch: char| ch.is_ascii_alphabetic());
+
+	if !is_name_part(tokens[start]) {
+		return None;
+	}
+
+	let mut parts = vec!(tokens[start].text.to_string());
+	let mut index = start + 1;
+	while index + 1 < tokens.len() && tokens[index].is_punct('.') && is_name_part(tokens[index + 1]) {
+		parts.push(tokens[index + 1].text.to_string());
+		index += 2;
+	}
+
+	let name = parts.pop().unwrap();
+	Some((model::QualifiedName { package: model::PackageName { package: parts }, name }, index))
+}
+
+fn resolve_type(model: &model::Verilization, scope: &model::Scope, name: model::QualifiedName) -> Option<model::QualifiedName> {
+	match scope.lookup(name) {
+		model::ScopeLookup::NamedType(name) if model.has_type(&name) => Some(name),
+		_ => None,
+	}
+}
+
+fn resolve_constant(model: &model::Verilization, scope: &model::Scope, name: model::QualifiedName) -> Option<model::QualifiedName> {
+	let name = scope.lookup_constant(name);
+	if model.has_constant(&name) { Some(name) } else { None }
+}
+
+/// Finds the names in a file that refer to types and constants of the model.
+///
+/// Names in definitions that are not part of the model, such as those that failed to parse, are not resolved.
+pub fn find_symbols(model: &model::Verilization, file: &Arc<SourceFile>) -> FileSymbols {
+	let tokens = tokenize(file.content());
+	let tokens: Vec<&Token> = tokens.iter().filter(|token| !token.is_comment()).collect();
+
+	let mut package = model::PackageName::new();
+	let mut symbols = Vec::new();
+	let mut definition: Option<Definition> = None;
+	let mut depth = 0;
+	let mut angle_depth = 0;
+	let mut in_value = false;
+	let mut method_type_params: Vec<&str> = Vec::new();
+
+	let mut index = 0;
+	while index < tokens.len() {
+		let token = tokens[index];
+		let prev = if index > 0 { Some(tokens[index - 1]) } else { None };
+
+		if token.kind == TokenKind::Punct('{') {
+			depth += 1;
+		}
+		else if token.kind == TokenKind::Punct('}') {
+			depth -= 1;
+			if depth == 0 {
+				definition = None;
+			}
+		}
+		else if token.is_punct('<') {
+			angle_depth += 1;
+		}
+		else if token.is_punct('>') {
+			angle_depth -= 1;
+		}
+		else if token.is_punct('=') && depth == 1 && matches!(definition, Some(Definition::Constant(_))) {
+			in_value = true;
+		}
+		else if token.is_punct('=') && depth == 2 && matches!(definition, Some(Definition::Type(_))) {
+			// Default value of a field.
+			in_value = true;
+		}
+		else if token.is_punct(';') && depth == 1 {
+			in_value = false;
+		}
+		else if token.is_punct(';') && depth == 2 {
+			if matches!(definition, Some(Definition::Type(_))) {
+				in_value = false;
+			}
+			method_type_params.clear();
+		}
+		else if token.kind == TokenKind::Word && angle_depth > 0 && depth == 2 {
+			// Type parameters of interface methods hide types with the same name until the end of the method.
+			method_type_params.push(token.text);
+		}
+
+		let (name, next_index) = match read_name(&tokens, index) {
+			Some(name) => name,
+			None => {
+				index += 1;
+				continue;
+			},
+		};
+
+		let next = tokens.get(next_index).copied();
+		let start = token.offset;
+		let end = tokens[next_index - 1].offset + tokens[next_index - 1].text.len();
+		index = next_index;
+
+		let prev_word = prev.filter(|prev| prev.kind == TokenKind::Word).map(|prev| prev.text);
+		let prev_punct = prev.and_then(|prev| match prev.kind {
+			TokenKind::Punct(ch) => Some(ch),
+			_ => None,
+		});
+
+		let symbol = match prev_word {
+			Some("package") if depth == 0 => {
+				let mut parts = name.package.package;
+				parts.push(name.name);
+				package = model::PackageName { package: parts };
+				None
+			},
+
+			Some("import") if depth == 0 =>
+				if model.has_type(&name) { Some((name, SymbolKind::Type, false)) }
+				else if model.has_constant(&name) { Some((name, SymbolKind::Constant, false)) }
+				else { None },
+
+			Some("struct") | Some("enum") | Some("extern") | Some("interface") if depth == 0 => {
+				let name = model::QualifiedName { package: package.clone(), name: name.name };
+				definition = model.get_type(&name).map(Definition::Type);
+				definition.as_ref().map(|_| (name, SymbolKind::Type, true))
+			},
+
+			Some("const") if depth == 0 => {
+				let name = model::QualifiedName { package: package.clone(), name: name.name };
+				definition = model.get_constant(&name).map(Definition::Constant);
+				definition.as_ref().map(|_| (name, SymbolKind::Constant, true))
+			},
+
+			Some("format") if depth == 0 => {
+				let name = model::QualifiedName { package: package.clone(), name: name.name };
+				definition = model.get_format(&name).map(Definition::Format);
+				None
+			},
+
+			Some("root") if depth == 1 => match &definition {
+				Some(definition @ Definition::Format(_)) => resolve_type(model, &definition.scope(), name).map(|name| (name, SymbolKind::Type, false)),
+				_ => None,
+			},
+
+			_ => match &definition {
+				// Names followed by an argument list are cases and names followed by = are record fields.
+				Some(definition) if in_value =>
+					if next.is_some_and(|next| next.is_punct('(') || next.is_punct('=')) { None }
+					else { resolve_constant(model, &definition.scope(), name).map(|name| (name, SymbolKind::Constant, false)) },
+
+				// Types follow a colon, an opening parenthesis, a comma or the sequence keyword.
+				// Names followed by a colon are fields and parameters.
+				Some(definition) if angle_depth == 0 && (matches!(prev_punct, Some(':') | Some('(') | Some(',')) || prev_word == Some("sequence")) => {
+					let is_field = next.is_some_and(|next| next.is_punct(':'));
+					let is_method_type_param = name.package.package.is_empty() && method_type_params.contains(&name.name.as_str());
+					if is_field || is_method_type_param { None }
+					else { resolve_type(model, &definition.scope(), name).map(|name| (name, SymbolKind::Type, false)) }
+				},
+
+				_ => None,
+			},
+		};
+
+		if let Some((name, kind, is_definition)) = symbol {
+			symbols.push(Symbol {
+				name,
+				kind,
+				span: model::Span::new(file.clone(), start, end),
+				is_definition,
+			});
+		}
+	}
+
+	FileSymbols {
+		package,
+		symbols,
+	}
+}
+
+/// Gets the location where a type or constant is defined.
+pub fn definition_span<'model>(model: &'model model::Verilization, name: &model::QualifiedName, kind: SymbolKind) -> Option<&'model model::Span> {
+	match kind {
+		SymbolKind::Type => model.get_type(name).map(|t| t.span()),
+		SymbolKind::Constant => model.get_constant(name).map(|constant| constant.span()),
+	}
+}
+
+fn write_versions<I: Iterator<Item = (num_bigint::BigUint, bool)>>(out: &mut String, versions: I) {
+	let mut explicit_versions = Vec::new();
+	let mut latest_version = None;
+	for (version, explicit_version) in versions {
+		if explicit_version {
+			explicit_versions.push(version.to_string());
+		}
+		latest_version = Some(version);
+	}
+
+	match latest_version {
+		Some(latest_version) => {
+			let _ = write!(out, "\n\nDefined in versions {}. Available through version {}.", explicit_versions.join(", "), latest_version);
+		},
+		None => out.push_str("\n\nNot defined in any version."),
+	}
+}
+
+/// Describes a type or constant, including the versions it is defined in.
+///
+/// The description is formatted as Markdown.
+pub fn describe(model: &model::Verilization, name: &model::QualifiedName, kind: SymbolKind) -> Option<String> {
+	let mut out = String::new();
+
+	match kind {
+		SymbolKind::Type => {
+			let t = model.get_type(name)?;
+			let keyword = match t {
+				model::NamedTypeDefinition::StructType(_) => "struct",
+				model::NamedTypeDefinition::EnumType(_) => "enum",
+				model::NamedTypeDefinition::ExternType(_) => "extern",
+				model::NamedTypeDefinition::InterfaceType(_) => "interface",
+			};
+
+			let _ = write!(out, "```verilization\n{} {}", keyword, name);
+			if !t.type_params().is_empty() {
+				let _ = write!(out, "<{}>", t.type_params().join(", "));
+			}
+			out.push_str("\n```");
+
+			match t {
+				model::NamedTypeDefinition::StructType(t) | model::NamedTypeDefinition::EnumType(t) =>
+					write_versions(&mut out, t.versions().map(|ver| (ver.version, ver.explicit_version))),
+				model::NamedTypeDefinition::InterfaceType(t) =>
+					write_versions(&mut out, t.versions().map(|ver| (ver.version, ver.explicit_version))),
+				model::NamedTypeDefinition::ExternType(_) => out.push_str("\n\nExtern types are not versioned."),
+			}
+		},
+
+		SymbolKind::Constant => {
+			let constant = model.get_constant(name)?;
+			let _ = write!(out, "```verilization\nconst {}: {}\n```", name, constant.value_type());
+			write_versions(&mut out, constant.versions().map(|ver| (ver.version, ver.explicit_version)));
+		},
+	}
+
+	Some(out)
+}
+
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+use crate::model;
+use crate::parser::parse_model;
+use crate::source::SourceFile;
+use crate::symbols::{find_symbols, describe, definition_span, SymbolKind};
+use std::sync::Arc;
+
+const SOURCE: &str = "version 3;
+package a.b;
+
+import c.D as Other;
+
+// Point is not a reference in a comment.
+struct Point {
+    version 1 {
+        x: Other;
+        next: list(Point);
+    }
+    version 3 {
+        x: Other;
+        next: list(Point);
+        start: Point = origin;
+    }
+}
+
+enum Shape<T> {
+    version 2 {
+        point: a.b.Point;
+        other: T;
+    }
+}
+
+interface Visitor {
+    version 1 {
+        visit<Point>(p: Point): Point;
+        accept(p: Point): Point;
+    }
+}
+
+const origin: Point {
+    version 1 = { x = Other(1); next = [origin]; };
+}
+";
+
+fn load() -> (model::Verilization, Arc<SourceFile>) {
+    let file = SourceFile::new(String::from("test.verilization"), String::from(SOURCE));
+    let mut model = parse_model(&file).unwrap();
+    let other = parse_model(&SourceFile::new(String::from("other.verilization"), String::from("version 1; package c; extern D {} extern list<A> {}"))).unwrap();
+    model.merge(other).unwrap();
+    (model, file)
+}
+
+fn symbol_text(file: &SourceFile, start: usize, end: usize) -> &str {
+    &file.content()[start..end]
+}
+
+#[test]
+fn finds_references() {
+    let (model, file) = load();
+    let symbols = find_symbols(&model, &file);
+
+    assert_eq!(symbols.package.package, vec!("a", "b"));
+
+    let found: Vec<(String, String, SymbolKind, bool)> = symbols.symbols.iter()
+        .map(|symbol| (symbol_text(&file, symbol.span.start(), symbol.span.end()).to_string(), symbol.name.to_string(), symbol.kind, symbol.is_definition))
+        .collect();
+
+    let expected = vec!(
+        ("c.D", "c.D", SymbolKind::Type, false),
+        ("Point", "a.b.Point", SymbolKind::Type, true),
+        ("Other", "c.D", SymbolKind::Type, false),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("Other", "c.D", SymbolKind::Type, false),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("origin", "a.b.origin", SymbolKind::Constant, false),
+        ("Shape", "a.b.Shape", SymbolKind::Type, true),
+        ("a.b.Point", "a.b.Point", SymbolKind::Type, false),
+        ("Visitor", "a.b.Visitor", SymbolKind::Type, true),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("origin", "a.b.origin", SymbolKind::Constant, true),
+        ("Point", "a.b.Point", SymbolKind::Type, false),
+        ("origin", "a.b.origin", SymbolKind::Constant, false),
+    );
+
+    let expected: Vec<(String, String, SymbolKind, bool)> = expected.into_iter()
+        .map(|(text, name, kind, is_definition)| (text.to_string(), name.to_string(), kind, is_definition))
+        .collect();
+
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn symbol_definition() {
+    let (model, file) = load();
+    let symbols = find_symbols(&model, &file);
+
+    let offset = SOURCE.find("list(Point)").unwrap() + 6;
+    let symbol = symbols.symbol_at(offset).unwrap();
+    let span = definition_span(&model, &symbol.name, symbol.kind).unwrap();
+    assert_eq!(span.to_string(), "test.verilization:7:8");
+
+    assert!(symbols.symbol_at(SOURCE.find("comment").unwrap()).is_none());
+}
+
+#[test]
+fn describe_versions() {
+    let (model, _) = load();
+    let name = |name: &str| model::QualifiedName::from_str(name).unwrap();
+
+    assert_eq!(describe(&model, &name("a.b.Point"), SymbolKind::Type).unwrap(), "```verilization\nstruct a.b.Point\n```\n\nDefined in versions 1, 3. Available through version 3.");
+    assert_eq!(describe(&model, &name("a.b.Shape"), SymbolKind::Type).unwrap(), "```verilization\nenum a.b.Shape<T>\n```\n\nDefined in versions 2. Available through version 3.");
+    assert_eq!(describe(&model, &name("a.b.origin"), SymbolKind::Constant).unwrap(), "```verilization\nconst a.b.origin: Point\n```\n\nDefined in versions 1. Available through version 3.");
+    assert!(describe(&model, &name("a.b.Missing"), SymbolKind::Type).is_none());
+}
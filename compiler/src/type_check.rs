@@ -2,27 +2,168 @@ use crate::model::*;
 use num_bigint::BigUint;
 use num_traits::One;
 use std::collections::{HashSet, HashMap};
+use std::fmt;
 
 #[cfg(test)]
 mod tests;
 
 #[derive(Debug)]
 pub enum TypeCheckError {
-    TypeNotDefined(QualifiedName),
-    ConstantNotDefined(QualifiedName),
-    TypeNotInVersion(QualifiedName, BigUint),
-    ConstantNotInVersion(QualifiedName, BigUint),
-    ConstantHasIncorrectType(QualifiedName, BigUint),
-    CouldNotFindLastVersion(QualifiedName),
-    ArityMismatch(usize, usize),
-    TypeNotFinal(QualifiedName),
-    DuplicateLiteral(QualifiedName),
-    InterfaceTypeNotAllowed(QualifiedName),
+    TypeNotDefined(QualifiedName, Span),
+    ConstantNotDefined(QualifiedName, Span),
+    TypeNotInVersion(QualifiedName, BigUint, Span),
+    ConstantNotInVersion(QualifiedName, BigUint, Span),
+    ConstantHasIncorrectType(QualifiedName, BigUint, Span),
+    CouldNotFindLastVersion(QualifiedName, Span),
+    ArityMismatch(QualifiedName, usize, usize, Span),
+    TypeNotFinal(QualifiedName, Span),
+    DuplicateLiteral(QualifiedName, Span),
+    InterfaceTypeNotAllowed(QualifiedName, Span),
+    ImportNotDefined(QualifiedName, Span),
+    ImportConflict(String, QualifiedName, Span),
+    DefaultValueNotAllowed(QualifiedName, String, Span),
+    DefaultValueHasIncorrectType(QualifiedName, BigUint, String, Span),
+    RenamedFieldNotFound(QualifiedName, Box<(BigUint, String)>, String, Span),
+    RemovedFieldNotFound(QualifiedName, BigUint, String, Span),
+    RemovedCaseNotAllowed(QualifiedName, String, Span),
+    FormatRootNotVersioned(QualifiedName, Span),
+    FormatVersionOutOfRange(QualifiedName, BigUint, Span),
+    TypeArgumentNotType(QualifiedName, String, Span),
+    TypeArgumentNotInteger(QualifiedName, String, Span),
+    ConstParameterUsedAsType(String, Span),
+    InvalidSequenceLength(QualifiedName, Span),
+    KeyTypeNotAllowed(QualifiedName, String, Span),
+    RenamedFieldStillExists(QualifiedName, Box<(BigUint, String)>, String, Span),
+    FieldRenamedTwice(QualifiedName, BigUint, String, Span),
+    RenamedFieldRemoved(QualifiedName, BigUint, String, Span),
+    FieldNotRemoved(QualifiedName, BigUint, String, Span),
 }
 
+impl TypeCheckError {
+    /// Gets the location in the source that caused the error.
+    pub fn span(&self) -> &Span {
+        match self {
+            TypeCheckError::TypeNotDefined(_, span) => span,
+            TypeCheckError::ConstantNotDefined(_, span) => span,
+            TypeCheckError::TypeNotInVersion(_, _, span) => span,
+            TypeCheckError::ConstantNotInVersion(_, _, span) => span,
+            TypeCheckError::ConstantHasIncorrectType(_, _, span) => span,
+            TypeCheckError::CouldNotFindLastVersion(_, span) => span,
+            TypeCheckError::ArityMismatch(_, _, _, span) => span,
+            TypeCheckError::TypeNotFinal(_, span) => span,
+            TypeCheckError::DuplicateLiteral(_, span) => span,
+            TypeCheckError::InterfaceTypeNotAllowed(_, span) => span,
+            TypeCheckError::ImportNotDefined(_, span) => span,
+            TypeCheckError::ImportConflict(_, _, span) => span,
+            TypeCheckError::DefaultValueNotAllowed(_, _, span) => span,
+            TypeCheckError::DefaultValueHasIncorrectType(_, _, _, span) => span,
+            TypeCheckError::RenamedFieldNotFound(_, _, _, span) => span,
+            TypeCheckError::RemovedFieldNotFound(_, _, _, span) => span,
+            TypeCheckError::RemovedCaseNotAllowed(_, _, span) => span,
+            TypeCheckError::FormatRootNotVersioned(_, span) => span,
+            TypeCheckError::FormatVersionOutOfRange(_, _, span) => span,
+            TypeCheckError::TypeArgumentNotType(_, _, span) => span,
+            TypeCheckError::TypeArgumentNotInteger(_, _, span) => span,
+            TypeCheckError::ConstParameterUsedAsType(_, span) => span,
+            TypeCheckError::InvalidSequenceLength(_, span) => span,
+            TypeCheckError::KeyTypeNotAllowed(_, _, span) => span,
+            TypeCheckError::RenamedFieldStillExists(_, _, _, span) => span,
+            TypeCheckError::FieldRenamedTwice(_, _, _, span) => span,
+            TypeCheckError::RenamedFieldRemoved(_, _, _, span) => span,
+            TypeCheckError::FieldNotRemoved(_, _, _, span) => span,
+        }
+    }
+
+    /// Gets a stable code that identifies the kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TypeCheckError::TypeNotDefined(..) => "T0001",
+            TypeCheckError::ConstantNotDefined(..) => "T0002",
+            TypeCheckError::TypeNotInVersion(..) => "T0003",
+            TypeCheckError::ConstantNotInVersion(..) => "T0004",
+            TypeCheckError::ConstantHasIncorrectType(..) => "T0005",
+            TypeCheckError::CouldNotFindLastVersion(..) => "T0006",
+            TypeCheckError::ArityMismatch(..) => "T0007",
+            TypeCheckError::TypeNotFinal(..) => "T0008",
+            TypeCheckError::DuplicateLiteral(..) => "T0009",
+            TypeCheckError::InterfaceTypeNotAllowed(..) => "T0010",
+            TypeCheckError::ImportNotDefined(..) => "T0011",
+            TypeCheckError::ImportConflict(..) => "T0012",
+            TypeCheckError::DefaultValueNotAllowed(..) => "T0013",
+            TypeCheckError::DefaultValueHasIncorrectType(..) => "T0014",
+            TypeCheckError::RenamedFieldNotFound(..) => "T0015",
+            TypeCheckError::RemovedFieldNotFound(..) => "T0016",
+            TypeCheckError::RemovedCaseNotAllowed(..) => "T0017",
+            TypeCheckError::FormatRootNotVersioned(..) => "T0018",
+            TypeCheckError::FormatVersionOutOfRange(..) => "T0019",
+            TypeCheckError::TypeArgumentNotType(..) => "T0020",
+            TypeCheckError::TypeArgumentNotInteger(..) => "T0021",
+            TypeCheckError::ConstParameterUsedAsType(..) => "T0022",
+            TypeCheckError::InvalidSequenceLength(..) => "T0023",
+            TypeCheckError::KeyTypeNotAllowed(..) => "T0024",
+            TypeCheckError::RenamedFieldStillExists(..) => "T0025",
+            TypeCheckError::FieldRenamedTwice(..) => "T0026",
+            TypeCheckError::RenamedFieldRemoved(..) => "T0027",
+            TypeCheckError::FieldNotRemoved(..) => "T0028",
+        }
+    }
+}
+
+impl std::error::Error for TypeCheckError {}
+
+impl fmt::Display for TypeCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeCheckError::TypeNotDefined(name, _) => write!(f, "Type {} is not defined.", name),
+            TypeCheckError::ConstantNotDefined(name, _) => write!(f, "Constant {} is not defined.", name),
+            TypeCheckError::TypeNotInVersion(name, version, _) => write!(f, "Type {} does not have version {}.", name, version),
+            TypeCheckError::ConstantNotInVersion(name, version, _) => write!(f, "Constant {} does not have version {}.", name, version),
+            TypeCheckError::ConstantHasIncorrectType(name, version, _) => write!(f, "Version {} of constant {} has a value that does not match its type.", version, name),
+            TypeCheckError::CouldNotFindLastVersion(name, _) => write!(f, "Could not find the last version of type {}.", name),
+            TypeCheckError::ArityMismatch(name, expected, actual, _) => write!(f, "Type {} expects {} type arguments, but {} were given.", name, expected, actual),
+            TypeCheckError::TypeNotFinal(name, _) => write!(f, "Final type {} cannot use a type that is not final.", name),
+            TypeCheckError::DuplicateLiteral(name, _) => write!(f, "Type {} has a duplicate literal.", name),
+            TypeCheckError::InterfaceTypeNotAllowed(name, _) => write!(f, "Interface type {} cannot be used as a field type.", name),
+            TypeCheckError::ImportNotDefined(name, _) => write!(f, "Cannot import {}. No type or constant has that name.", name),
+            TypeCheckError::ImportConflict(alias, local_name, _) => write!(f, "Import {} conflicts with {} in the current package.", alias, local_name),
+            TypeCheckError::DefaultValueNotAllowed(name, field, _) => write!(f, "Field {} of type {} cannot have a default value. Only fields of structs can have default values.", field, name),
+            TypeCheckError::DefaultValueHasIncorrectType(name, version, field, _) => write!(f, "Default value of field {} in version {} of type {} does not match its type.", field, version, name),
+            TypeCheckError::RenamedFieldNotFound(name, field, old_name, _) => {
+                let (version, field_name) = &**field;
+                write!(f, "Field {} in version {} of type {} is renamed from {}, but the previous version does not have that field.", field_name, version, name, old_name)
+            },
+            TypeCheckError::RemovedFieldNotFound(name, version, field, _) => write!(f, "Version {} of type {} removes field {}, but the previous version does not have that field.", version, name, field),
+            TypeCheckError::RemovedCaseNotAllowed(name, case_name, _) => write!(f, "Case {} of enum {} cannot be marked as removed. Cases that are removed must be handled by a converter.", case_name, name),
+            TypeCheckError::FormatRootNotVersioned(name, _) => write!(f, "The root type of format {} must be a struct or enum without type parameters.", name),
+            TypeCheckError::FormatVersionOutOfRange(name, version, _) => write!(f, "Format {} cannot encode version {} in its header.", name, version),
+            TypeCheckError::TypeArgumentNotType(name, param, _) => write!(f, "Parameter {} of type {} expects a type, but an integer was given.", param, name),
+            TypeCheckError::TypeArgumentNotInteger(name, param, _) => write!(f, "Parameter {} of type {} is a const parameter and expects an integer.", param, name),
+            TypeCheckError::ConstParameterUsedAsType(param, _) => write!(f, "Const parameter {} cannot be used as a type.", param),
+            TypeCheckError::InvalidSequenceLength(name, _) => write!(f, "The length of the sequence literal of type {} must be an integer or a const parameter.", name),
+            TypeCheckError::KeyTypeNotAllowed(name, key_type, _) => write!(f, "Type {} cannot be used as a key of {}. Keys must be integer, bool or string types.", key_type, name),
+            TypeCheckError::RenamedFieldStillExists(name, field, old_name, _) => {
+                let (version, field_name) = &**field;
+                write!(f, "Field {} in version {} of type {} is renamed from {}, but that field is still in version {}.", field_name, version, name, old_name, version)
+            },
+            TypeCheckError::FieldRenamedTwice(name, version, old_name, _) => write!(f, "Field {} is renamed more than once in version {} of type {}.", old_name, version, name),
+            TypeCheckError::RenamedFieldRemoved(name, version, old_name, _) => write!(f, "Version {} of type {} both renames and removes field {}.", version, name, old_name),
+            TypeCheckError::FieldNotRemoved(name, version, field, _) => write!(f, "Version {} of type {} does not have field {} of the previous version. Fields that are no longer used must be marked as removed.", version, name, field),
+        }
+    }
+}
+
+const KEY_TYPES: &[&str] = &[
+    "i8", "i16", "i24", "i32", "i64", "i128",
+    "u8", "u16", "u24", "u32", "u64", "u128",
+    "i16be", "i24be", "i32be", "i64be", "i128be",
+    "u16be", "u24be", "u32be", "u64be", "u128be",
+    "int", "nat", "bool", "string",
+];
+
 struct TypeCheck<'model> {
     model: &'model Verilization,
     scope: Scope<'model>,
+    extern_type: Option<Named<'model, ExternTypeDefinitionData>>,
 }
 
 fn try_all<I: Iterator, E>(iter: I, mut f: impl FnMut(I::Item) -> Result<bool, E>) -> Result<bool, E> {
@@ -43,7 +184,41 @@ fn try_any<I: Iterator, E>(iter: I, mut f: impl FnMut(I::Item) -> Result<bool, E
     Ok(false)
 }
 
-fn check_record(tc: &TypeCheck, version: &BigUint, record: &ConstantValueRecord, record_def: &Vec<(String, FieldInfo)>) -> Result<bool, TypeCheckError> {
+// Resolves a type used by a literal of an extern type so that it can be checked in the scope of the value.
+// Type parameters of the extern type are replaced by the type arguments.
+fn extern_literal_type_arg(extern_type: Named<ExternTypeDefinitionData>, args: &[TypeArgument], literal_arg: &TypeArgument) -> TypeArgument {
+    let literal_type = match literal_arg {
+        TypeArgument::Type(literal_type) => literal_type,
+        TypeArgument::Integer(n) => return TypeArgument::Integer(n.clone()),
+    };
+
+    let name = match extern_type.scope().lookup(literal_type.name.clone()) {
+        ScopeLookup::NamedType(name) => name,
+        ScopeLookup::TypeParameter(param) => {
+            let index = extern_type.type_params().iter().position(|type_param| *type_param == param);
+            match index.and_then(|index| args.get(index)) {
+                Some(arg) => return arg.clone(),
+                None => QualifiedName { package: PackageName::new(), name: param },
+            }
+        },
+    };
+
+    TypeArgument::Type(Type {
+        name,
+        args: literal_type.args.iter().map(|arg| extern_literal_type_arg(extern_type, args, arg)).collect(),
+    })
+}
+
+fn extern_literal_type(extern_type: Named<ExternTypeDefinitionData>, args: &[TypeArgument], literal_type: &Type) -> Type {
+    match extern_literal_type_arg(extern_type, args, &TypeArgument::Type(literal_type.clone())) {
+        TypeArgument::Type(t) => t,
+
+        // Const parameters used as types are reported when the extern type is checked.
+        TypeArgument::Integer(n) => Type { name: QualifiedName { package: PackageName::new(), name: n.to_string() }, args: Vec::new() },
+    }
+}
+
+fn check_record(tc: &TypeCheck, version: &BigUint, record: &ConstantValueRecord, record_def: &Vec<(String, FieldInfo)>, span: &Span) -> Result<bool, TypeCheckError> {
     let mut value_map = HashMap::new();
     for (field_name, value) in record.field_values() {
         value_map.insert(field_name, value);
@@ -51,7 +226,7 @@ fn check_record(tc: &TypeCheck, version: &BigUint, record: &ConstantValueRecord,
 
     for (field_name, field) in record_def {
         if let Some(field_value) = value_map.remove(field_name) {
-            if !tc.check_value_type(version, &field.field_type, field_value)? {
+            if !tc.check_value_type(version, &field.field_type, field_value, span)? {
                 return Ok(false)
             }
         }
@@ -77,37 +252,97 @@ fn same_types(a: &Type, a_scope: &Scope, b: &Type, b_scope: &Scope) -> bool {
     }
 
     a.args.iter().zip(b.args.iter())
-        .all(|(a_arg, b_arg)| same_types(a_arg, a_scope, b_arg, b_scope))
+        .all(|(a_arg, b_arg)| match (a_arg, b_arg) {
+            (TypeArgument::Type(a_arg), TypeArgument::Type(b_arg)) => same_types(a_arg, a_scope, b_arg, b_scope),
+            (TypeArgument::Integer(a_n), TypeArgument::Integer(b_n)) => a_n == b_n,
+            _ => false,
+        })
 }
 
 impl <'model> TypeCheck<'model> {
 
-    fn check_type(&self, version: &BigUint, t: &Type) -> Result<(), TypeCheckError> {
+    // Imports are shared by every definition in a file, so each import is only checked once.
+    fn check_imports(&self, current_pkg: &PackageName, checked: &mut HashSet<(String, usize)>, errors: &mut Vec<TypeCheckError>) {
+        for (alias, import) in self.scope.imports() {
+            if !checked.insert((import.span.file().name().to_string(), import.span.start())) {
+                continue;
+            }
+
+            let local_name = QualifiedName {
+                package: current_pkg.clone(),
+                name: alias.clone(),
+            };
+
+            if !self.model.has_type(&import.name) && !self.model.has_constant(&import.name) {
+                errors.push(TypeCheckError::ImportNotDefined(import.name.clone(), import.span.clone()));
+            }
+            else if local_name != import.name && (self.model.has_type(&local_name) || self.model.has_constant(&local_name)) {
+                errors.push(TypeCheckError::ImportConflict(alias.clone(), local_name, import.span.clone()));
+            }
+        }
+    }
+
+    fn is_const_param(&self, param: &str) -> bool {
+        self.extern_type.is_some_and(|extern_type| extern_type.is_const_param(param))
+    }
+
+    // Const parameters of an extern type may be passed along to the const parameters of the types used by its literals.
+    fn is_integer_arg(&self, arg: &TypeArgument) -> bool {
+        match arg {
+            TypeArgument::Integer(_) => true,
+            TypeArgument::Type(t) => t.args.is_empty() && matches!(self.scope.lookup(t.name.clone()), ScopeLookup::TypeParameter(param) if self.is_const_param(&param)),
+        }
+    }
+
+    fn check_type(&self, version: &BigUint, t: &Type, span: &Span) -> Result<(), TypeCheckError> {
         match self.scope.lookup(t.name.clone()) {
             ScopeLookup::NamedType(name) => {
                 let named_type_def = match self.model.get_type(&name) {
                     Some(t) => t,
-                    None => return Err(TypeCheckError::TypeNotDefined(name)),
+                    None => return Err(TypeCheckError::TypeNotDefined(name, span.clone())),
                 };
 
                 if !named_type_def.has_version(version) {
-                    return Err(TypeCheckError::TypeNotInVersion(name, version.clone()));
+                    return Err(TypeCheckError::TypeNotInVersion(name, version.clone(), span.clone()));
                 }
     
                 let arity = named_type_def.arity();
                 if arity != t.args.len() {
-                    return Err(TypeCheckError::ArityMismatch(arity, t.args.len()));
+                    return Err(TypeCheckError::ArityMismatch(name, arity, t.args.len(), span.clone()));
                 }
     
-                for arg in &t.args {
-                    self.check_type(version, &arg)?;
+                for (param, arg) in named_type_def.type_params().iter().zip(t.args.iter()) {
+                    if named_type_def.is_const_param(param) {
+                        if !self.is_integer_arg(arg) {
+                            return Err(TypeCheckError::TypeArgumentNotInteger(name, param.clone(), span.clone()));
+                        }
+                    }
+                    else {
+                        match arg {
+                            TypeArgument::Type(arg) => self.check_type(version, arg, span)?,
+                            TypeArgument::Integer(_) => return Err(TypeCheckError::TypeArgumentNotType(name, param.clone(), span.clone())),
+                        }
+                    }
+                }
+
+                // Keys are compared by value, which the runtimes only support for primitive types.
+                if matches!(named_type_def, NamedTypeDefinition::ExternType(_)) && name.package.package.is_empty() && (name.name == "map" || name.name == "set") {
+                    if let Some(TypeArgument::Type(key_type)) = t.args.first() {
+                        if !self.is_key_type(key_type) {
+                            return Err(TypeCheckError::KeyTypeNotAllowed(name, key_type.name.to_string(), span.clone()));
+                        }
+                    }
                 }
 
                 Ok(())
             },
-            ScopeLookup::TypeParameter(_) => {
+            ScopeLookup::TypeParameter(param) => {
+                if self.is_const_param(&param) {
+                    return Err(TypeCheckError::ConstParameterUsedAsType(param, span.clone()));
+                }
+
                 if t.args.len() != 0 {
-                    return Err(TypeCheckError::ArityMismatch(0, t.args.len()));
+                    return Err(TypeCheckError::ArityMismatch(t.name.clone(), 0, t.args.len(), span.clone()));
                 }
 
                 Ok(())
@@ -115,17 +350,27 @@ impl <'model> TypeCheck<'model> {
         }
     }
 
-    fn check_type_excludes_interfaces(&self, t: &Type) -> Result<(), TypeCheckError> {
+    fn is_key_type(&self, t: &Type) -> bool {
+        match self.scope.lookup(t.name.clone()) {
+            ScopeLookup::NamedType(name) =>
+                matches!(self.model.get_type(&name), Some(NamedTypeDefinition::ExternType(_))) &&
+                    name.package.package.is_empty() &&
+                    KEY_TYPES.contains(&name.name.as_str()),
+            ScopeLookup::TypeParameter(_) => false,
+        }
+    }
+
+    fn check_type_excludes_interfaces(&self, t: &Type, span: &Span) -> Result<(), TypeCheckError> {
         match self.scope.lookup(t.name.clone()) {
             ScopeLookup::NamedType(name) => {
                 match self.model.get_type(&name) {
-                    Some(NamedTypeDefinition::InterfaceType(..)) => return Err(TypeCheckError::InterfaceTypeNotAllowed(name)),
+                    Some(NamedTypeDefinition::InterfaceType(..)) => return Err(TypeCheckError::InterfaceTypeNotAllowed(name, span.clone())),
                     Some(_) => (),
-                    None => return Err(TypeCheckError::TypeNotDefined(name)),
+                    None => return Err(TypeCheckError::TypeNotDefined(name, span.clone())),
                 };
 
-                for arg in &t.args {
-                    self.check_type_excludes_interfaces(&arg)?;
+                for arg in t.args.iter().filter_map(TypeArgument::as_type) {
+                    self.check_type_excludes_interfaces(arg, span)?;
                 }
 
                 Ok(())
@@ -136,16 +381,16 @@ impl <'model> TypeCheck<'model> {
         }
     }
 
-    fn check_is_final(&self, version: &BigUint, t: &Type) -> Result<bool, TypeCheckError> {
+    fn check_is_final(&self, version: &BigUint, t: &Type, span: &Span) -> Result<bool, TypeCheckError> {
         Ok(match self.scope.lookup(t.name.clone()) {
             ScopeLookup::NamedType(name) => {
-                match self.model.get_type(&name).ok_or_else(|| TypeCheckError::TypeNotDefined(name.clone()))? {
+                match self.model.get_type(&name).ok_or_else(|| TypeCheckError::TypeNotDefined(name.clone(), span.clone()))? {
                     NamedTypeDefinition::StructType(type_def) | NamedTypeDefinition::EnumType(type_def) => {
                         if !type_def.is_final() {
                             return Ok(false);
                         }
                         
-                        if !(type_def.last_explicit_version().ok_or_else(|| TypeCheckError::CouldNotFindLastVersion(name.clone()))? <= version) {
+                        if !(type_def.last_explicit_version().ok_or_else(|| TypeCheckError::CouldNotFindLastVersion(name.clone(), span.clone()))? <= version) {
                             return Ok(false);
                         }
                     },
@@ -157,14 +402,14 @@ impl <'model> TypeCheck<'model> {
                             return Ok(false);
                         }
                         
-                        if !(type_def.last_explicit_version().ok_or_else(|| TypeCheckError::CouldNotFindLastVersion(name.clone()))? <= version) {
+                        if !(type_def.last_explicit_version().ok_or_else(|| TypeCheckError::CouldNotFindLastVersion(name.clone(), span.clone()))? <= version) {
                             return Ok(false);
                         }
                     },
                 }
 
-                for arg in &t.args {
-                    if !self.check_is_final(version, &arg)? {
+                for arg in t.args.iter().filter_map(TypeArgument::as_type) {
+                    if !self.check_is_final(version, arg, span)? {
                         return Ok(false);
                     }
                 }
@@ -175,11 +420,11 @@ impl <'model> TypeCheck<'model> {
         })
     }
 
-    fn check_value_type(&self, version: &BigUint, t: &Type, value: &ConstantValue) -> Result<bool, TypeCheckError> {
+    fn check_value_type(&self, version: &BigUint, t: &Type, value: &ConstantValue, span: &Span) -> Result<bool, TypeCheckError> {
         let (type_name, named_type_def) = match self.scope.lookup(t.name.clone()) {
             ScopeLookup::NamedType(name) => match self.model.get_type(&name) {
                 Some(t) => (name, t),
-                None => return Err(TypeCheckError::TypeNotDefined(name)),
+                None => return Err(TypeCheckError::TypeNotDefined(name, span.clone())),
             },
             ScopeLookup::TypeParameter(_) => return Ok(false)
         };
@@ -216,34 +461,67 @@ impl <'model> TypeCheck<'model> {
 
             (ConstantValue::String(_), _) => Ok(false),
 
+            (ConstantValue::Boolean(_), NamedTypeDefinition::ExternType(extern_type)) =>
+                Ok(extern_type.literals().iter().any(|literal| matches!(literal, ExternLiteralSpecifier::Boolean))),
+
+            (ConstantValue::Boolean(_), _) => Ok(false),
+
+            (ConstantValue::Float(_), NamedTypeDefinition::ExternType(extern_type)) =>
+                Ok(extern_type.literals().iter().any(|literal| matches!(literal, ExternLiteralSpecifier::Float))),
+
+            (ConstantValue::Float(_), _) => Ok(false),
+
             (ConstantValue::Sequence(seq), NamedTypeDefinition::ExternType(extern_type)) =>
                 try_any(extern_type.literals().iter(), |literal| match literal {
-                    ExternLiteralSpecifier::Sequence(elem_type) => {
-                        try_all(seq.iter(), |elem| self.check_value_type(version, elem_type, elem))
+                    ExternLiteralSpecifier::Sequence(elem_type, length) => {
+                        let has_length = match length.as_ref().map(|length| extern_literal_type_arg(extern_type, &t.args, length)) {
+                            None => true,
+                            Some(TypeArgument::Integer(n)) => BigUint::from(seq.len()) == n,
+                            Some(TypeArgument::Type(_)) => false,
+                        };
+
+                        let elem_type = extern_literal_type(extern_type, &t.args, elem_type);
+                        Ok(has_length && try_all(seq.iter(), |elem| self.check_value_type(version, &elem_type, elem, span))?)
                     },
+                    ExternLiteralSpecifier::Map(_, _) => Ok(seq.is_empty()),
                     _ => Ok(false),
                 }),
 
             (ConstantValue::Sequence(_), _) => Ok(false),
 
+            (ConstantValue::Map(entries), NamedTypeDefinition::ExternType(extern_type)) =>
+                try_any(extern_type.literals().iter(), |literal| match literal {
+                    ExternLiteralSpecifier::Map(key_type, value_type) => {
+                        let key_type = extern_literal_type(extern_type, &t.args, key_type);
+                        let value_type = extern_literal_type(extern_type, &t.args, value_type);
+                        try_all(entries.iter(), |(key, value)| Ok(
+                            self.check_value_type(version, &key_type, key, span)? &&
+                                self.check_value_type(version, &value_type, value, span)?
+                        ))
+                    },
+                    _ => Ok(false),
+                }),
+
+            (ConstantValue::Map(_), _) => Ok(false),
+
             (ConstantValue::Case(name, args), NamedTypeDefinition::ExternType(extern_type)) =>
                 try_any(extern_type.literals().iter(), |literal| match literal {
                     ExternLiteralSpecifier::Case(name2, param_types) if name == name2 && args.len() == param_types.len() =>
-                        try_all(args.iter().zip(param_types.iter()), |(arg, param_type)| self.check_value_type(version, param_type, arg)),
+                        try_all(args.iter().zip(param_types.iter()), |(arg, param_type)| self.check_value_type(version, &extern_literal_type(extern_type, &t.args, param_type), arg, span)),
                     _ => Ok(false),
                 }),
 
             (ConstantValue::Case(name, args), NamedTypeDefinition::EnumType(enum_type)) if args.len() == 1 => {
                 if let Some(type_ver) = enum_type.versioned(version) {
                     if let Some(field) = type_ver.ver_type.fields().iter().find_map(|(field_name, field)| if field_name == name { Some(field) } else { None }) {
-                        self.check_value_type(version, &field.field_type, &args[0])
+                        self.check_value_type(version, &field.field_type, &args[0], span)
                     }
                     else {
                         Ok(false)
                     }
                 }
                 else {
-                    Err(TypeCheckError::TypeNotInVersion(type_name, version.clone()))
+                    Err(TypeCheckError::TypeNotInVersion(type_name, version.clone(), span.clone()))
                 }
             },
 
@@ -251,16 +529,16 @@ impl <'model> TypeCheck<'model> {
 
             (ConstantValue::Record(record), NamedTypeDefinition::ExternType(extern_type)) =>
                 try_any(extern_type.literals().iter(), |literal| match literal {
-                    ExternLiteralSpecifier::Record(record_def) => check_record(self, version, record, record_def),
+                    ExternLiteralSpecifier::Record(record_def) => check_record(self, version, record, record_def, span),
                     _ => Ok(false),
                 }),
 
             (ConstantValue::Record(record), NamedTypeDefinition::StructType(struct_type)) => {
                 if let Some(type_ver) = struct_type.versioned(version) {
-                    check_record(self, version, record, type_ver.ver_type.fields())
+                    check_record(self, version, record, type_ver.ver_type.fields(), span)
                 }
                 else {
-                    Err(TypeCheckError::TypeNotInVersion(type_name, version.clone()))
+                    Err(TypeCheckError::TypeNotInVersion(type_name, version.clone(), span.clone()))
                 }
             },
 
@@ -270,11 +548,11 @@ impl <'model> TypeCheck<'model> {
                 let constant_name = self.scope.lookup_constant(constant_name.clone());
                 let constant = match self.model.get_constant(&constant_name) {
                     Some(constant) => constant,
-                    None => return Err(TypeCheckError::ConstantNotDefined(constant_name)),
+                    None => return Err(TypeCheckError::ConstantNotDefined(constant_name, span.clone())),
                 };
 
                 if !constant.has_version(version) {
-                    return Err(TypeCheckError::ConstantNotInVersion(constant_name, version.clone()))
+                    return Err(TypeCheckError::ConstantNotInVersion(constant_name, version.clone(), span.clone()))
                 }
 
                 Ok(same_types(t, &self.scope, constant.value_type(), &constant.scope()))
@@ -287,149 +565,342 @@ impl <'model> TypeCheck<'model> {
     
 }
 
-fn type_check_versioned_type<'model>(model: &'model Verilization, t: Named<'model, VersionedTypeDefinitionData>) -> Result<(), TypeCheckError> {
+fn type_check_versioned_type<'model>(model: &'model Verilization, t: Named<'model, VersionedTypeDefinitionData>, is_enum: bool, errors: &mut Vec<TypeCheckError>) {
     let tc = TypeCheck {
-        model: model,
+        model,
         scope: t.scope(),
+        extern_type: None,
     };
 
+    // Later versions reuse the fields of earlier versions, so only report the first error for each field.
+    let mut failed_fields = HashSet::new();
+    let mut prev_ver_type: Option<&TypeVersionDefinition> = None;
+
     for ver in t.versions() {
-        for (_, field) in ver.ver_type.fields() {
-            tc.check_type(&ver.version, &field.field_type)?;
-            tc.check_type_excludes_interfaces(&field.field_type)?;
+        for (field_name, field) in ver.ver_type.fields() {
+            if failed_fields.contains(field_name) {
+                continue;
+            }
+
+            let result = tc.check_type(&ver.version, &field.field_type, &field.span)
+                .and_then(|_| tc.check_type_excludes_interfaces(&field.field_type, &field.span))
+                .and_then(|_| match &field.default_value {
+                    // Default values are only used when upgrading to the version that declares them.
+                    Some(value) if ver.explicit_version => {
+                        if is_enum {
+                            Err(TypeCheckError::DefaultValueNotAllowed(t.name().clone(), field_name.clone(), field.span.clone()))
+                        }
+                        else if !tc.check_value_type(&ver.version, &field.field_type, value, &field.span)? {
+                            Err(TypeCheckError::DefaultValueHasIncorrectType(t.name().clone(), ver.version.clone(), field_name.clone(), field.span.clone()))
+                        }
+                        else {
+                            Ok(())
+                        }
+                    },
+                    _ => Ok(()),
+                });
+
+            if let Err(err) = result {
+                failed_fields.insert(field_name);
+                errors.push(err);
+            }
+        }
+
+        // Renamed and removed fields refer to the fields of the previous version.
+        if ver.explicit_version {
+            let has_prev_field = |name: &String| prev_ver_type.is_some_and(|prev| prev.fields().iter().any(|(prev_name, _)| prev_name == name));
+
+            let is_removed = |name: &String| ver.ver_type.removed_fields().iter().any(|(removed_name, _)| removed_name == name);
+
+            // Each field of the previous version may be the source of only one field of this version.
+            let mut copied_fields = HashSet::new();
+            for (field_name, field) in ver.ver_type.fields() {
+                if field.renamed_from.is_none() && has_prev_field(field_name) {
+                    copied_fields.insert(field_name);
+                }
+            }
+
+            for (field_name, field) in ver.ver_type.fields() {
+                let old_name = match &field.renamed_from {
+                    Some(old_name) => old_name,
+                    None => continue,
+                };
+
+                if !has_prev_field(old_name) {
+                    errors.push(TypeCheckError::RenamedFieldNotFound(t.name().clone(), Box::new((ver.version.clone(), field_name.clone())), old_name.clone(), field.span.clone()));
+                }
+                else if is_removed(old_name) {
+                    errors.push(TypeCheckError::RenamedFieldRemoved(t.name().clone(), ver.version.clone(), old_name.clone(), field.span.clone()));
+                }
+                else if ver.ver_type.fields().iter().any(|(other_name, other)| other_name == old_name && other.renamed_from.is_none()) {
+                    errors.push(TypeCheckError::RenamedFieldStillExists(t.name().clone(), Box::new((ver.version.clone(), field_name.clone())), old_name.clone(), field.span.clone()));
+                }
+                else if !copied_fields.insert(old_name) {
+                    errors.push(TypeCheckError::FieldRenamedTwice(t.name().clone(), ver.version.clone(), old_name.clone(), field.span.clone()));
+                }
+            }
+
+            // Cases of an enum that are not kept are handled by a converter instead.
+            if !is_enum {
+                for (prev_name, _) in prev_ver_type.map(|prev| prev.fields().as_slice()).unwrap_or_default() {
+                    if !copied_fields.contains(prev_name) && !is_removed(prev_name) {
+                        errors.push(TypeCheckError::FieldNotRemoved(t.name().clone(), ver.version.clone(), prev_name.clone(), ver.ver_type.span().clone()));
+                    }
+                }
+            }
+
+            for (field_name, span) in ver.ver_type.removed_fields() {
+                if is_enum {
+                    errors.push(TypeCheckError::RemovedCaseNotAllowed(t.name().clone(), field_name.clone(), span.clone()));
+                }
+                else if !has_prev_field(field_name) {
+                    errors.push(TypeCheckError::RemovedFieldNotFound(t.name().clone(), ver.version.clone(), field_name.clone(), span.clone()));
+                }
+            }
         }
+
+        prev_ver_type = Some(ver.ver_type);
     }
 
     if t.is_final() {
         if let Some(last_ver) = t.versions().last() {
-            for (_, field) in last_ver.ver_type.fields() {
-                if !tc.check_is_final(&last_ver.version, &field.field_type)? {
-                    return Err(TypeCheckError::TypeNotFinal(t.name().clone()))
+            for (field_name, field) in last_ver.ver_type.fields() {
+                if failed_fields.contains(field_name) {
+                    continue;
+                }
+
+                match tc.check_is_final(&last_ver.version, &field.field_type, &field.span) {
+                    Ok(true) => (),
+                    Ok(false) => errors.push(TypeCheckError::TypeNotFinal(t.name().clone(), field.span.clone())),
+                    Err(err) => errors.push(err),
                 }
             }
         }
     }
-
-    Ok(())
 }
 
-fn type_check_extern_type<'model>(model: &'model Verilization, t: Named<'model, ExternTypeDefinitionData>) -> Result<(), TypeCheckError> {
+fn type_check_extern_type<'model>(model: &'model Verilization, t: Named<'model, ExternTypeDefinitionData>, errors: &mut Vec<TypeCheckError>) {
     let tc = TypeCheck {
-        model: model,
+        model,
         scope: t.scope(),
+        extern_type: Some(t),
     };
 
     let mut has_integer = false;
     let mut has_string = false;
+    let mut has_boolean = false;
+    let mut has_float = false;
     let mut has_sequence = false;
+    let mut has_map = false;
     let mut literal_cases = HashSet::new();
     let mut has_record = false;
 
     for literal in t.literals() {
-        match literal {
-            ExternLiteralSpecifier::Integer(_, _, _, _) if has_integer => return Err(TypeCheckError::DuplicateLiteral(t.name().clone())),
-            ExternLiteralSpecifier::Integer(_, _, _, _) => has_integer = true,
-            ExternLiteralSpecifier::String if has_string => return Err(TypeCheckError::DuplicateLiteral(t.name().clone())),
-            ExternLiteralSpecifier::String => has_string = true,
-            ExternLiteralSpecifier::Sequence(_) if has_sequence => return Err(TypeCheckError::DuplicateLiteral(t.name().clone())),
-            ExternLiteralSpecifier::Sequence(inner) => {
-                has_sequence = true;
-                tc.check_type(&BigUint::one(), inner)?;
+        let is_duplicate = match literal {
+            ExternLiteralSpecifier::Integer(_, _, _, _) => std::mem::replace(&mut has_integer, true),
+            ExternLiteralSpecifier::String => std::mem::replace(&mut has_string, true),
+            ExternLiteralSpecifier::Boolean => std::mem::replace(&mut has_boolean, true),
+            ExternLiteralSpecifier::Float => std::mem::replace(&mut has_float, true),
+            ExternLiteralSpecifier::Sequence(inner, length) => {
+                if let Err(err) = tc.check_type(&BigUint::one(), inner, t.span()) {
+                    errors.push(err);
+                }
+                if length.as_ref().is_some_and(|length| !tc.is_integer_arg(length)) {
+                    errors.push(TypeCheckError::InvalidSequenceLength(t.name().clone(), t.span().clone()));
+                }
+                std::mem::replace(&mut has_sequence, true)
             },
-            ExternLiteralSpecifier::Case(name, params) => {
-                if !literal_cases.insert(name) {
-                    return Err(TypeCheckError::DuplicateLiteral(t.name().clone()));
+            ExternLiteralSpecifier::Map(key_type, value_type) => {
+                for inner in [key_type, value_type] {
+                    if let Err(err) = tc.check_type(&BigUint::one(), inner, t.span()) {
+                        errors.push(err);
+                    }
                 }
-
+                std::mem::replace(&mut has_map, true)
+            },
+            ExternLiteralSpecifier::Case(name, params) => {
                 for param in params {
-                    tc.check_type(&BigUint::one(), param)?;
+                    if let Err(err) = tc.check_type(&BigUint::one(), param, t.span()) {
+                        errors.push(err);
+                    }
                 }
+                !literal_cases.insert(name)
             },
-            ExternLiteralSpecifier::Record(_) if has_record => return Err(TypeCheckError::DuplicateLiteral(t.name().clone())),
             ExternLiteralSpecifier::Record(fields) => {
-                has_record = true;
                 for (_, field) in fields {
-                    tc.check_type(&BigUint::one(), &field.field_type)?;
+                    if let Err(err) = tc.check_type(&BigUint::one(), &field.field_type, &field.span) {
+                        errors.push(err);
+                    }
                 }
+                std::mem::replace(&mut has_record, true)
             },
+        };
+
+        if is_duplicate {
+            errors.push(TypeCheckError::DuplicateLiteral(t.name().clone(), t.span().clone()));
         }
     }
-
-    Ok(())
 }
 
-fn each_method_sig_type(method: OfInterface<InterfaceMethod>, f: impl Fn(&Type) -> Result<(), TypeCheckError>) -> Result<(), TypeCheckError> {
+fn each_method_sig_type(method: OfInterface<InterfaceMethod>, mut f: impl FnMut(&Type, &Span) -> Result<(), TypeCheckError>) -> Result<(), TypeCheckError> {
     for param in method.parameters() {
-        f(&param.param_type)?;
+        f(&param.param_type, &param.span)?;
     }
     
-    f(method.return_type())
+    f(method.return_type(), method.span())
 }
 
-fn type_check_interface_type<'model>(model: &'model Verilization, t: Named<'model, InterfaceTypeDefinitionData>) -> Result<(), TypeCheckError> {
+fn type_check_interface_type<'model>(model: &'model Verilization, t: Named<'model, InterfaceTypeDefinitionData>, errors: &mut Vec<TypeCheckError>) {
+    // Like fields, methods are reused by later versions, so only report the first error for each method.
+    let mut failed_methods = HashSet::new();
 
     for ver in t.versions() {
-        for (_, method) in ver.ver_type.methods() {
+        for (method_name, method) in ver.ver_type.methods() {
+            if failed_methods.contains(method_name) {
+                continue;
+            }
+
             let tc = TypeCheck {
-                model: model,
+                model,
                 scope: method.scope(),
+                extern_type: None,
             };
 
-            each_method_sig_type(method, |sig_type| tc.check_type(&ver.version, sig_type))?;
+            if let Err(err) = each_method_sig_type(method, |sig_type, span| tc.check_type(&ver.version, sig_type, span)) {
+                failed_methods.insert(method_name);
+                errors.push(err);
+            }
         }
     }
 
     if t.is_final() {
         if let Some(last_ver) = t.versions().last() {
-            for (_, method) in last_ver.ver_type.methods() {
+            for (method_name, method) in last_ver.ver_type.methods() {
+                if failed_methods.contains(method_name) {
+                    continue;
+                }
+
                 let tc = TypeCheck {
-                    model: model,
+                    model,
                     scope: method.scope(),
+                    extern_type: None,
                 };
 
-                each_method_sig_type(method, |sig_type| {
-                    if !tc.check_is_final(&last_ver.version, sig_type)? {
-                        return Err(TypeCheckError::TypeNotFinal(t.name().clone()))
+                let result = each_method_sig_type(method, |sig_type, span| {
+                    if !tc.check_is_final(&last_ver.version, sig_type, span)? {
+                        return Err(TypeCheckError::TypeNotFinal(t.name().clone(), span.clone()))
                     }
                     Ok(())
-                })?;
+                });
+
+                if let Err(err) = result {
+                    errors.push(err);
+                }
             }
         }
     }
-
-    Ok(())
 }
 
-fn type_check_constant<'model>(model: &'model Verilization, c: Named<'model, Constant>) -> Result<(), TypeCheckError> {
+fn type_check_constant<'model>(model: &'model Verilization, c: Named<'model, Constant>, errors: &mut Vec<TypeCheckError>) {
     let tc = TypeCheck {
-        model: model,
+        model,
         scope: c.scope(),
+        extern_type: None,
     };
 
+    // Values are reused by later versions, so stop at the first error.
     for ver in c.versions() {
-        if !tc.check_value_type(&ver.version, c.value_type(), ver.value)? {
-            return Err(TypeCheckError::ConstantHasIncorrectType(c.name().clone(), ver.version.clone()))
-        }
+        let err = match tc.check_value_type(&ver.version, c.value_type(), ver.value, ver.span) {
+            Ok(true) => continue,
+            Ok(false) => TypeCheckError::ConstantHasIncorrectType(c.name().clone(), ver.version.clone(), ver.span.clone()),
+            Err(err) => err,
+        };
+
+        errors.push(err);
+        break;
     }
+}
+
+fn type_check_format<'model>(model: &'model Verilization, format: Named<'model, FileFormat>, errors: &mut Vec<TypeCheckError>) {
+    let root_type = format.root_type();
 
-    Ok(())
+    let type_def = match format.scope().lookup(root_type.name.clone()) {
+        ScopeLookup::NamedType(name) => match model.get_type(&name) {
+            Some(NamedTypeDefinition::StructType(type_def)) | Some(NamedTypeDefinition::EnumType(type_def)) if type_def.type_params().is_empty() && root_type.args.is_empty() => type_def,
+            Some(_) => {
+                errors.push(TypeCheckError::FormatRootNotVersioned(format.name().clone(), format.span().clone()));
+                return;
+            },
+            None => {
+                errors.push(TypeCheckError::TypeNotDefined(name, format.span().clone()));
+                return;
+            },
+        },
+        ScopeLookup::TypeParameter(_) => {
+            errors.push(TypeCheckError::FormatRootNotVersioned(format.name().clone(), format.span().clone()));
+            return;
+        },
+    };
+
+    // Every version of the root type must fit in the header.
+    if let (Some(max_version), Some(last_ver)) = (format.version_encoding().max_version(), type_def.versions().last()) {
+        if last_ver.version > max_version {
+            errors.push(TypeCheckError::FormatVersionOutOfRange(format.name().clone(), last_ver.version, format.span().clone()));
+        }
+    }
 }
 
-pub fn type_check_verilization(model: &Verilization) -> Result<(), TypeCheckError> {
+
+/// Checks every definition in the model, returning all of the errors that were found sorted by location.
+pub fn type_check_verilization(model: &Verilization) -> Result<(), Vec<TypeCheckError>> {
+    let mut errors = Vec::new();
+    let mut checked_imports = HashSet::new();
     
     for t in model.types() {
+        let tc = TypeCheck {
+            model,
+            scope: t.scope(),
+            extern_type: None,
+        };
+        tc.check_imports(&t.name().package, &mut checked_imports, &mut errors);
+
         match t {
-            NamedTypeDefinition::StructType(t) | NamedTypeDefinition::EnumType(t) => type_check_versioned_type(model, t)?,
-            NamedTypeDefinition::ExternType(t) => type_check_extern_type(model, t)?,
-            NamedTypeDefinition::InterfaceType(t) => type_check_interface_type(model, t)?,
+            NamedTypeDefinition::StructType(t) => type_check_versioned_type(model, t, false, &mut errors),
+            NamedTypeDefinition::EnumType(t) => type_check_versioned_type(model, t, true, &mut errors),
+            NamedTypeDefinition::ExternType(t) => type_check_extern_type(model, t, &mut errors),
+            NamedTypeDefinition::InterfaceType(t) => type_check_interface_type(model, t, &mut errors),
         }
     }
 
     for c in model.constants() {
-        type_check_constant(model, c)?
+        let tc = TypeCheck {
+            model,
+            scope: c.scope(),
+            extern_type: None,
+        };
+        tc.check_imports(&c.name().package, &mut checked_imports, &mut errors);
+
+        type_check_constant(model, c, &mut errors);
     }
 
-    Ok(())
-}
+    for format in model.formats() {
+        let tc = TypeCheck {
+            model,
+            scope: format.scope(),
+            extern_type: None,
+        };
+        tc.check_imports(&format.name().package, &mut checked_imports, &mut errors);
 
+        type_check_format(model, format, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    }
+    else {
+        // Definitions are stored in hash maps, so errors are sorted to report them in the order of the source.
+        errors.sort_by(|a, b| a.span().cmp_location(b.span()));
+        Err(errors)
+    }
+}
 
@@ -2,16 +2,19 @@
 use crate::TypeCheckError;
 use crate::type_check::type_check_verilization;
 use crate::parser::parse_model;
+use crate::source::SourceFile;
 
-fn run_type_check_test(file_data: &[&str]) -> Result<(), TypeCheckError> {
-    let (_, model) = parse_model(&file_data[0]).unwrap();
-    let mut model = model().unwrap();
-    for data in &file_data[1..] {
-        let (_, other) = parse_model(data).unwrap();
-        model.merge(other().unwrap()).unwrap();
+fn run_type_check_test(file_data: &[&str]) -> Vec<TypeCheckError> {
+    let mut files = file_data.iter()
+        .enumerate()
+        .map(|(index, data)| SourceFile::new(format!("file{}.verilization", index + 1), String::from(*data)));
+
+    let mut model = parse_model(&files.next().unwrap()).unwrap();
+    for file in files {
+        model.merge(parse_model(&file).unwrap()).unwrap();
     }
 
-    type_check_verilization(&model)
+    type_check_verilization(&model).err().unwrap_or_default()
 }
 
 #[test]
@@ -37,8 +40,8 @@ struct B {
 "
     ];
 
-    match run_type_check_test(file_data) {
-        Err(TypeCheckError::TypeNotInVersion(_, _)) => (),
+    match &run_type_check_test(file_data)[..] {
+        [TypeCheckError::TypeNotInVersion(_, _, _)] => (),
         _ => assert!(false)
     }
 }
@@ -66,8 +69,8 @@ struct B {
 "
     ];
 
-    match run_type_check_test(file_data) {
-        Ok(()) => (),
+    match &run_type_check_test(file_data)[..] {
+        [] => (),
         _ => assert!(false)
     }
 }
@@ -95,9 +98,607 @@ final struct B {
 "
     ];
 
-    match run_type_check_test(file_data) {
-        Err(TypeCheckError::TypeNotFinal(_)) => (),
+    match &run_type_check_test(file_data)[..] {
+        [TypeCheckError::TypeNotFinal(_, _)] => (),
         _ => assert!(false)
     }
 }
 
+
+#[test]
+fn import_type() {
+    let file_data = &[
+"
+version 1;
+package a.b;
+
+struct A {
+    version 1 {
+
+    }
+}
+",
+
+"
+version 1;
+package c;
+import a.b.A;
+import a.b.A as Other;
+
+struct B {
+    version 1 {
+        a: A;
+        other: Other;
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [] => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn import_not_defined() {
+    let file_data = &[
+"
+version 1;
+package c;
+import a.b.A;
+
+struct B {
+    version 1 {
+
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [TypeCheckError::ImportNotDefined(_, span)] => assert_eq!(span.to_string(), "file1.verilization:4:1"),
+        errors => panic!("{:?}", errors)
+    }
+}
+
+#[test]
+fn import_errors_are_reported_once_at_each_import() {
+    let file_data = &[
+"
+version 1;
+package c;
+import a.b.A;
+import a.b.B;
+
+struct C {
+    version 1 {
+
+    }
+}
+
+struct D {
+    version 1 {
+
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [TypeCheckError::ImportNotDefined(_, a), TypeCheckError::ImportNotDefined(_, b)] => {
+            assert_eq!(a.to_string(), "file1.verilization:4:1");
+            assert_eq!(b.to_string(), "file1.verilization:5:1");
+        },
+        errors => panic!("{:?}", errors)
+    }
+}
+
+#[test]
+fn import_conflicts_with_definition() {
+    let file_data = &[
+"
+version 1;
+package a.b;
+
+struct A {
+    version 1 {
+
+    }
+}
+",
+
+"
+version 1;
+package c;
+import a.b.A;
+
+struct A {
+    version 1 {
+
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [TypeCheckError::ImportConflict(_, _, span)] => assert_eq!(span.to_string(), "file2.verilization:4:1"),
+        errors => panic!("{:?}", errors)
+    }
+}
+
+#[test]
+fn error_span_is_field() {
+    let file_data = &[
+"
+version 2;
+
+struct A {
+    version 2 {
+
+    }
+}
+
+struct B {
+    version 1 {
+        a: A;
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [err @ TypeCheckError::TypeNotInVersion(_, _, _)] => {
+            assert_eq!(err.span().to_string(), "file1.verilization:12:9");
+            assert_eq!(err.span().snippet(), "   |\n12 |         a: A;\n   |         ^^^^\n");
+        },
+        _ => panic!()
+    }
+}
+
+#[test]
+fn reports_all_errors() {
+    let file_data = &[
+"
+version 2;
+
+struct A {
+    version 1 {
+        a: Missing;
+    }
+}
+
+struct B {
+    version 1 {
+        b: AlsoMissing;
+    }
+}
+
+const c: A {
+    version 1 = 5;
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [a, b, c] => {
+            let spans = vec!(a.span().to_string(), b.span().to_string(), c.span().to_string());
+            assert_eq!(spans, vec!("file1.verilization:6:9", "file1.verilization:12:9", "file1.verilization:17:5"));
+        },
+        errors => panic!("{:?}", errors)
+    }
+}
+
+#[test]
+fn error_message_names_type() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+struct A {
+    version 1 {
+
+    }
+}
+
+struct B {
+    version 1 {
+        a: A(A);
+    }
+}
+"
+    ];
+
+    match &run_type_check_test(file_data)[..] {
+        [err @ TypeCheckError::ArityMismatch(_, _, _, _)] => {
+            assert_eq!(err.code(), "T0007");
+            assert_eq!(err.to_string(), "Type a.A expects 0 type arguments, but 1 were given.");
+        },
+        errors => panic!("{:?}", errors)
+    }
+}
+
+#[test]
+fn default_values() {
+    let file_data = &[
+"
+version 2;
+package a;
+
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+
+struct Point {
+    version 1 {
+        x: nat;
+    }
+    version 2 {
+        x: nat;
+        y: nat = 0;
+        z: nat = \"zero\";
+    }
+}
+
+enum Shape {
+    version 1 {
+        circle: nat = 1;
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0014", String::from("Default value of field z in version 2 of type a.Point does not match its type.")),
+        ("T0013", String::from("Field circle of type a.Shape cannot have a default value. Only fields of structs can have default values.")),
+    ));
+}
+
+#[test]
+fn boolean_values() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+extern bool {
+    literal {
+        boolean;
+    }
+}
+
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+
+struct Flags {
+    version 1 {
+        enabled: bool = true;
+        count: nat = false;
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0014", String::from("Default value of field count in version 1 of type a.Flags does not match its type.")),
+    ));
+}
+
+#[test]
+fn float_values() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+extern f64 {
+    literal {
+        float;
+    }
+}
+
+struct Reading {
+    version 1 {
+        value: f64 = 0.5;
+        scale: f64 = 1;
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0014", String::from("Default value of field scale in version 1 of type a.Reading does not match its type.")),
+    ));
+}
+
+#[test]
+fn map_values() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+extern string {
+    literal {
+        string;
+    }
+}
+
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+
+extern list<A> {
+    literal {
+        sequence A;
+    }
+}
+
+extern map<K, V> {
+    literal {
+        map K V;
+    }
+}
+
+struct Index {
+    version 1 {
+        counts: map(string, nat) = [\"a\" => 1];
+        empty: map(string, nat) = [];
+        names: list(string) = [\"a\", \"b\"];
+        wrong: map(string, nat) = [1 => \"a\"];
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0014", String::from("Default value of field wrong in version 1 of type a.Index does not match its type.")),
+    ));
+}
+
+#[test]
+fn map_and_set_keys() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+struct Point {
+    version 1 {
+        x: string;
+    }
+}
+
+struct Index<A> {
+    version 1 {
+        names: map(string, Point);
+        points: map(Point, string);
+        values: set(f64);
+        params: set(A);
+    }
+}
+",
+"
+version 1;
+
+extern string {
+    literal {
+        string;
+    }
+}
+
+extern f64 {
+    literal {
+        float;
+    }
+}
+
+extern map<K, V> {
+    literal {
+        map K V;
+    }
+}
+
+extern set<A> {
+    literal {
+        sequence A;
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0024", String::from("Type Point cannot be used as a key of map. Keys must be integer, bool or string types.")),
+        ("T0024", String::from("Type f64 cannot be used as a key of set. Keys must be integer, bool or string types.")),
+        ("T0024", String::from("Type A cannot be used as a key of set. Keys must be integer, bool or string types.")),
+    ));
+}
+
+#[test]
+fn fixed_length_arrays() {
+    let file_data = &[
+"
+version 1;
+package a;
+
+extern u8 {
+    literal {
+        integer [0, 255];
+    }
+}
+
+extern list<A> {
+    literal {
+        sequence A;
+    }
+}
+
+extern array<A, const N> {
+    literal {
+        sequence A [N];
+    }
+}
+
+extern matrix<A, const N> {
+    literal {
+        sequence array(A, N) [N];
+    }
+}
+
+extern invalid<A, const N> {
+    literal {
+        sequence N [A];
+    }
+}
+
+struct Hash {
+    version 1 {
+        digest: array(u8, 4) = [1, 2, 3, 4];
+        identity: matrix(u8, 2) = [[1, 0], [0, 1]];
+        short: array(u8, 4) = [1, 2, 3];
+        notType: list(4);
+        notInteger: array(u8, u8);
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0022", String::from("Const parameter N cannot be used as a type.")),
+        ("T0023", String::from("The length of the sequence literal of type a.invalid must be an integer or a const parameter.")),
+        ("T0014", String::from("Default value of field short in version 1 of type a.Hash does not match its type.")),
+        ("T0020", String::from("Parameter A of type a.list expects a type, but an integer was given.")),
+        ("T0021", String::from("Parameter N of type a.array is a const parameter and expects an integer.")),
+    ));
+}
+
+#[test]
+fn renamed_and_removed_fields() {
+    let file_data = &[
+"
+version 4;
+package a;
+
+extern nat {
+    literal {
+        integer [0, );
+    }
+}
+
+struct Point {
+    version 1 {
+        x: nat;
+        y: nat;
+        z: nat;
+    }
+    version 2 {
+        left: nat renamed from x;
+        top: nat renamed from w;
+        removed y;
+        removed w;
+        z: nat;
+    }
+    version 3 {
+        left: nat;
+        top: nat;
+        z: nat renamed from left;
+    }
+    version 4 {
+        a: nat renamed from left;
+        b: nat renamed from left;
+        c: nat renamed from top;
+        removed top;
+        removed z;
+    }
+}
+
+enum Shape {
+    version 1 {
+        circle: nat;
+    }
+    version 2 {
+        removed circle;
+    }
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0015", String::from("Field top in version 2 of type a.Point is renamed from w, but the previous version does not have that field.")),
+        ("T0016", String::from("Version 2 of type a.Point removes field w, but the previous version does not have that field.")),
+        ("T0028", String::from("Version 3 of type a.Point does not have field z of the previous version. Fields that are no longer used must be marked as removed.")),
+        ("T0025", String::from("Field z in version 3 of type a.Point is renamed from left, but that field is still in version 3.")),
+        ("T0026", String::from("Field left is renamed more than once in version 4 of type a.Point.")),
+        ("T0027", String::from("Version 4 of type a.Point both renames and removes field top.")),
+        ("T0017", String::from("Case circle of enum a.Shape cannot be marked as removed. Cases that are removed must be handled by a converter.")),
+    ));
+}
+
+#[test]
+fn format_root_and_version() {
+    let file_data = &[
+"
+version 256;
+package a;
+
+struct Point<T> {
+    version 1 {
+        x: T;
+    }
+}
+
+struct Line {
+    version 256 {
+        length: Line;
+    }
+}
+
+format PointFile {
+    root Point;
+    magic [1, 2];
+    version nat;
+}
+
+format LineFile {
+    root Line;
+    magic [3];
+    version u8;
+}
+
+format WideLineFile {
+    root Line;
+    magic [4];
+    version u16;
+}
+"
+    ];
+
+    let errors: Vec<(&str, String)> = run_type_check_test(file_data).iter().map(|err| (err.code(), err.to_string())).collect();
+
+    assert_eq!(errors, vec!(
+        ("T0018", String::from("The root type of format a.PointFile must be a struct or enum without type parameters.")),
+        ("T0019", String::from("Format a.LineFile cannot encode version 256 in its header.")),
+    ));
+}
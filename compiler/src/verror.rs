@@ -1,32 +1,123 @@
-use crate::{lang, model, TypeCheckError};
+use crate::{dynamic, lang, lockfile, model, parser, TypeCheckError};
+use std::fmt;
 use std::io;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum VError {
-	ParseError(nom::error::Error<String>),
+	ParseError(parser::ParseError),
 	ParseIncompleteError,
 	TypeCheckError(TypeCheckError),
 	IOError(io::Error),
+	FileReadError(PathBuf, io::Error),
 	ModelError(model::ModelError),
 	GeneratorError(lang::GeneratorError),
+	DynamicError(dynamic::DynamicError),
+	LockfileError(lockfile::LockfileError),
     NoInputFiles,
+	Multiple(Vec<VError>),
 }
 
-impl From<nom::error::Error<&str>> for VError {
-	fn from(err: nom::error::Error<&str>) -> Self {
-		VError::ParseError(nom::error::Error {
-			input: String::from(err.input),
-			code: err.code,
+impl VError {
+	/// Combines a list of errors into a single error.
+	/// 
+	/// Nested lists are flattened so that `errors` returns every error directly.
+	/// Errors are sorted by location, followed by the errors that do not have a location.
+	pub fn from_errors(errors: Vec<VError>) -> VError {
+		let mut errors: Vec<VError> = errors.into_iter().flat_map(VError::into_errors).collect();
+		errors.sort_by(|a, b| match (a.span(), b.span()) {
+			(Some(a), Some(b)) => a.cmp_location(b),
+			(a, b) => a.is_none().cmp(&b.is_none()),
+		});
+		if errors.len() == 1 {
+			errors.remove(0)
+		}
+		else {
+			VError::Multiple(errors)
+		}
+	}
+
+	/// Gets each of the errors that this error contains.
+	pub fn errors(&self) -> &[VError] {
+		match self {
+			VError::Multiple(errors) => errors,
+			_ => std::slice::from_ref(self),
+		}
+	}
+
+	/// Splits this error into each of the errors that it contains.
+	pub fn into_errors(self) -> Vec<VError> {
+		match self {
+			VError::Multiple(errors) => errors,
+			_ => vec!(self),
+		}
+	}
+
+	/// Gets the location in the source that caused the error, if there is one.
+	pub fn span(&self) -> Option<&model::Span> {
+		match self {
+			VError::ParseError(err) => Some(&err.span),
+			VError::TypeCheckError(err) => Some(err.span()),
+			VError::ModelError(err) => err.span(),
+			VError::LockfileError(err) => err.span(),
+			_ => None,
+		}
+	}
+
+	/// Gets a stable code that identifies the kind of error.
+	/// 
+	/// Lists of errors do not have a code; each error in the list has its own.
+	pub fn code(&self) -> Option<&'static str> {
+		Some(match self {
+			VError::ParseError(err) => err.code(),
+			VError::ParseIncompleteError => "P0003",
+			VError::TypeCheckError(err) => err.code(),
+			VError::IOError(_) => "E0001",
+			VError::FileReadError(_, _) => "E0002",
+			VError::ModelError(err) => err.code(),
+			VError::GeneratorError(err) => err.code(),
+			VError::DynamicError(err) => err.code(),
+			VError::LockfileError(err) => err.code(),
+			VError::NoInputFiles => "E0003",
+			VError::Multiple(_) => return None,
 		})
 	}
 }
 
-impl <E> From<nom::Err<E>> for VError where Self : From<E> {
-	fn from(err: nom::Err<E>) -> Self {
-		match err {
-			nom::Err::Incomplete(_) => VError::ParseIncompleteError,
-			nom::Err::Error(err) => VError::from(err),
-			nom::Err::Failure(err) => VError::from(err),
+impl fmt::Display for VError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			VError::ParseError(err) => err.fmt(f),
+			VError::ParseIncompleteError => write!(f, "Unexpected end of input."),
+			VError::TypeCheckError(err) => err.fmt(f),
+			VError::IOError(err) => err.fmt(f),
+			VError::FileReadError(path, err) => write!(f, "Could not read file {}. {}", path.display(), err),
+			VError::ModelError(err) => err.fmt(f),
+			VError::GeneratorError(err) => err.fmt(f),
+			VError::DynamicError(err) => err.fmt(f),
+			VError::LockfileError(err) => err.fmt(f),
+			VError::NoInputFiles => write!(f, "No input files were specified."),
+			VError::Multiple(errors) => {
+				for (index, err) in errors.iter().enumerate() {
+					if index > 0 {
+						writeln!(f)?;
+					}
+					err.fmt(f)?;
+				}
+				Ok(())
+			},
+		}
+	}
+}
+
+// Display already includes the message of the wrapped error, so its source is forwarded instead.
+impl std::error::Error for VError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			VError::IOError(err) => err.source(),
+			VError::FileReadError(_, err) => err.source(),
+			VError::GeneratorError(err) => err.source(),
+			_ => None,
 		}
 	}
 }
@@ -54,3 +145,15 @@ impl From<lang::GeneratorError> for VError {
 		VError::GeneratorError(error)
 	}
 }
+
+impl From<dynamic::DynamicError> for VError {
+	fn from(error: dynamic::DynamicError) -> Self {
+		VError::DynamicError(error)
+	}
+}
+
+impl From<lockfile::LockfileError> for VError {
+	fn from(error: lockfile::LockfileError) -> Self {
+		VError::LockfileError(error)
+	}
+}
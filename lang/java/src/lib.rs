@@ -21,6 +21,7 @@ pub struct JavaOptionsBuilder {
 	package_mapping: PackageMap,
 	library_mapping: PackageMap,
 	extern_mapping: ExternMap,
+	downgrade: bool,
 }
 
 pub struct JavaOptions {
@@ -28,6 +29,7 @@ pub struct JavaOptions {
 	pub package_mapping: PackageMap,
 	pub library_mapping: PackageMap,
 	pub extern_mapping: ExternMap,
+	pub downgrade: bool,
 }
 
 fn make_type_name(name: &str) -> String {
@@ -117,7 +119,9 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 		Ok(())
 	}
 	
+	// Integer arguments are not types in Java, so they are only passed to codecs.
 	fn write_type_args(&mut self, args: &Vec<LangType<'a>>) -> Result<(), GeneratorError> {
+		let args = args.iter().filter(|arg| !matches!(arg, LangType::Integer(_))).collect::<Vec<_>>();
 		if !args.is_empty() {
 			write!(self.file(), "<")?;
 			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
@@ -182,6 +186,8 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 				write!(self.file(), "{}", name)?;
 			},
 
+			LangType::Integer(_) => return Err(GeneratorError::CouldNotGenerateType),
+
 			LangType::Converter(from, to) => {
 				write!(self.file(), "{}.Converter<", RUNTIME_PACKAGE)?;
 				self.write_type(&*from, true)?;
@@ -203,27 +209,47 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 
 	fn write_args(&mut self, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
 		if !args.is_empty() {
-			write!(self.file(), "(")?;
-			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
-				self.write_expr(&arg)?;
-			});
-			write!(self.file(), ")")?;
+			self.write_call_args(args)?;
 		}
 
 		Ok(())
 	}
 
+	fn write_call_args(&mut self, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
+		write!(self.file(), "(")?;
+		for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+			self.write_expr(&arg)?;
+		});
+		write!(self.file(), ")")?;
+
+		Ok(())
+	}
+
+	// Literal constructors are functions even without arguments, but codecs and converters without arguments are values.
+	fn write_operation_args(&mut self, op: &Operation, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
+		match op {
+			Operation::FromInteger | Operation::FromString | Operation::FromBoolean | Operation::FromFloat |
+			Operation::FromSequence | Operation::FromMap | Operation::FromCase(_) => self.write_call_args(args),
+			_ => self.write_args(args),
+		}
+	}
+
 	fn write_operation_name(&mut self, op: &Operation) -> Result<(), GeneratorError> {
 		match op {
 			Operation::FromPreviousVersion(prev_ver) => write!(self.file(), "fromV{}", prev_ver)?,
+			Operation::ToPreviousVersion(prev_ver) => write!(self.file(), "toV{}", prev_ver)?,
 			Operation::FinalTypeConverter => write!(self.file(), "converter")?,
 			Operation::TypeCodec => write!(self.file(), "codec")?,
 			Operation::FromInteger => write!(self.file(), "fromInteger")?,
 			Operation::FromString => write!(self.file(), "fromString")?,
+			Operation::FromBoolean => write!(self.file(), "fromBoolean")?,
+			Operation::FromFloat => write!(self.file(), "fromFloat")?,
 			Operation::FromSequence => write!(self.file(), "fromSequence")?,
+			Operation::FromMap => write!(self.file(), "fromMap")?,
 			Operation::FromRecord(_) => write!(self.file(), "fromRecord")?,
 			Operation::FromCase(name) => write!(self.file(), "fromCase{}", make_type_name(name))?,
 			Operation::CreateRemoteWrapper => write!(self.file(), "createRemoteWrapper")?,
+			Operation::ReadAnyVersion => write!(self.file(), "readAnyVersion")?,
 		}
 
 		Ok(())
@@ -240,7 +266,7 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 					write!(self.file(), "{}L", n)?;
 				}
 				else {
-					write!(self.file(), "java.math.BigInteger.valueOf(\"{}\")", n)?;
+					write!(self.file(), "new java.math.BigInteger(\"{}\")", n)?;
 				}
 			},
 			LangExpr::StringLiteral(s) => {
@@ -256,6 +282,15 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 				}
 				write!(self.file(), "\"")?;
 			},
+			LangExpr::BooleanLiteral(b) => write!(self.file(), "{}", b)?,
+			LangExpr::FloatLiteral(f) => write!(self.file(), "{:?}", f)?,
+			LangExpr::MapEntry(key, value) => {
+				write!(self.file(), "java.util.Map.entry(")?;
+				self.write_expr(key)?;
+				write!(self.file(), ", ")?;
+				self.write_expr(value)?;
+				write!(self.file(), ")")?;
+			},
 			LangExpr::InvokeConverter { converter, value } => {
 				self.write_expr(&*converter)?;
 				write!(self.file(), ".convert(")?;
@@ -284,13 +319,13 @@ pub trait JavaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 				write!(self.file(), ".")?;
 				self.write_type_args(type_args)?;
 				self.write_operation_name(op)?;
-				self.write_args(args)?;
+				self.write_operation_args(op, args)?;
 			},
-			LangExpr::InvokeUserConverter { name, prev_ver, version, type_args, args } => {
+			LangExpr::InvokeUserConverter { name, from_ver, to_ver, type_args, args } => {
 				self.write_qual_name(name)?;
 				write!(self.file(), "_Conversions.")?;
 				self.write_type_args(type_args)?;
-				write!(self.file(), "v{}ToV{}", prev_ver, version)?;
+				write!(self.file(), "v{}ToV{}", from_ver, to_ver)?;
 				self.write_args(args)?;
 			},
 			LangExpr::ConstantValue(name, version) => {
@@ -370,6 +405,10 @@ impl GeneratorNameMapping for JavaLanguage {
 		"reader"
 	}
 
+	fn version_param_name() -> &'static str {
+		"version"
+	}
+
 	fn connection_name() -> &'static str {
 		"connection"
 	}
@@ -465,6 +504,129 @@ impl <'a, Output: OutputHandler<'a>> JavaConstGenerator<'a, Output> {
 	}
 }
 
+struct JavaFormatGenerator<'a, Output: OutputHandler<'a>> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a JavaOptions,
+	format: Named<'a, model::FileFormat>,
+	scope: model::Scope<'a>,
+}
+
+impl <'a, Output: OutputHandler<'a>> Generator<'a> for JavaFormatGenerator<'a, Output> {
+	type Lang = JavaLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> GeneratorWithFile for JavaFormatGenerator<'a, Output> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> JavaGenerator<'a> for JavaFormatGenerator<'a, Output> {
+	fn options(&self) -> &'a JavaOptions {
+		self.options
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> FormatGenerator<'a> for JavaFormatGenerator<'a, Output> {
+	fn format(&self) -> Named<'a, model::FileFormat> {
+		self.format
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		self.write_package(&self.format.name().package)?;
+
+		writeln!(self.file, "public final class {} {{", make_type_name(&self.format.name().name))?;
+		writeln!(self.file, "\tprivate {}() {{}}", make_type_name(&self.format.name().name))?;
+
+		write!(self.file, "\tprivate static final byte[] magic = new byte[] {{ ")?;
+		for_sep!(b, self.format.magic(), { write!(self.file, ", ")?; }, {
+			write!(self.file, "(byte){}", b)?;
+		});
+		writeln!(self.file, " }};")?;
+
+		Ok(())
+	}
+
+	fn write_read_file(&mut self, t: LangType<'a>, read_value: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "\tpublic static ")?;
+		self.write_type(&t, false)?;
+		writeln!(self.file, " readFile({}.FormatReader {}) throws java.io.IOException {{", RUNTIME_PACKAGE, JavaLanguage::format_reader_name())?;
+
+		writeln!(self.file, "\t\tif(!java.util.Arrays.equals({}.readBytes(magic.length), magic)) {{", JavaLanguage::format_reader_name())?;
+		writeln!(self.file, "\t\t\tthrow new java.io.IOException(\"Invalid magic number.\");")?;
+		writeln!(self.file, "\t\t}}")?;
+
+		write!(self.file, "\t\tjava.math.BigInteger {} = ", JavaLanguage::version_param_name())?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => write!(self.file, "{}.Nat.codec.read({})", RUNTIME_PACKAGE, JavaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "java.math.BigInteger.valueOf(Byte.toUnsignedInt({}.readByte()))", JavaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "java.math.BigInteger.valueOf(Short.toUnsignedInt({}.readShort()))", JavaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "java.math.BigInteger.valueOf(Integer.toUnsignedLong({}.readInt()))", JavaLanguage::format_reader_name())?,
+		}
+		writeln!(self.file, ";")?;
+
+		write!(self.file, "\t\treturn ")?;
+		self.write_expr(&read_value)?;
+		writeln!(self.file, ";")?;
+		writeln!(self.file, "\t}}")?;
+
+		Ok(())
+	}
+
+	fn write_write_file(&mut self, t: LangType<'a>, version: &BigUint, codec: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "\tpublic static void writeFile({}.FormatWriter {}, ", RUNTIME_PACKAGE, JavaLanguage::format_writer_name())?;
+		self.write_type(&t, false)?;
+		writeln!(self.file, " {}) throws java.io.IOException {{", JavaLanguage::codec_write_value_name())?;
+
+		writeln!(self.file, "\t\t{}.writeBytes(magic);", JavaLanguage::format_writer_name())?;
+
+		write!(self.file, "\t\t")?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => write!(self.file, "{}.Nat.codec.write({}, java.math.BigInteger.valueOf({}))", RUNTIME_PACKAGE, JavaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "{}.writeByte((byte){})", JavaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "{}.writeShort((short){})", JavaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "{}.writeInt((int){}L)", JavaLanguage::format_writer_name(), version)?,
+		}
+		writeln!(self.file, ";")?;
+
+		write!(self.file, "\t\t")?;
+		self.write_expr(&codec)?;
+		writeln!(self.file, ".write({}, {});", JavaLanguage::format_writer_name(), JavaLanguage::codec_write_value_name())?;
+		writeln!(self.file, "\t}}")?;
+
+		Ok(())
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		writeln!(self.file, "}}")?;
+		Ok(())
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> JavaFormatGenerator<'a, Output> {
+
+	fn open(model: &'a model::Verilization, options: &'a JavaOptions, output: &'a mut Output, format: Named<'a, model::FileFormat>) -> Result<Self, GeneratorError> {
+		let file = open_java_file(options, output, format.name())?;
+		Ok(JavaFormatGenerator {
+			file,
+			model,
+			options,
+			format,
+			scope: format.scope(),
+		})
+	}
+}
+
 struct JavaTypeGenerator<'a, Output: OutputHandler<'a>, TypeDef> {
 	file: Output::FileHandle,
 	model: &'a model::Verilization,
@@ -601,6 +763,31 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 	}
 
 	fn write_operation(&mut self, operation: OperationInfo<'a>) -> Result<(), GeneratorError> {
+		if let LangExprStmt::ReadAnyVersion { t, read } = &operation.implementation {
+			self.write_indent()?;
+			write!(self.file, "public static ")?;
+			self.write_type_params(&operation.type_params)?;
+			if !operation.type_params.is_empty() {
+				write!(self.file, " ")?;
+			}
+			self.write_type(t, false)?;
+			write!(self.file, " ")?;
+			self.write_operation_name(&operation.operation)?;
+			write!(self.file, "(")?;
+			for (param_name, param) in &operation.params {
+				self.write_type(param, true)?;
+				write!(self.file, " {}, ", param_name)?;
+			}
+			writeln!(self.file, "java.math.BigInteger {}, {}.FormatReader {}) throws java.io.IOException {{", JavaLanguage::version_param_name(), RUNTIME_PACKAGE, JavaLanguage::format_reader_name())?;
+			self.indent_increase();
+			self.write_statement(read, ResultHandling::Return)?;
+			self.indent_decrease();
+			self.write_indent()?;
+			writeln!(self.file, "}}")?;
+
+			return Ok(());
+		}
+
 		let is_func = !operation.type_params.is_empty() || !operation.params.is_empty();
 
 		self.write_indent()?;
@@ -656,6 +843,10 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 		Ok(())
 	}
 
+	fn generate_downgrades(&self) -> bool {
+		self.options.downgrade
+	}
+
 }
 
 
@@ -872,6 +1063,8 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> JavaTy
 
 			},
 
+			LangExprStmt::ReadAnyVersion { .. } => return Err(GeneratorError::CouldNotGenerateType),
+
 		}
 
 		Ok(())
@@ -1041,6 +1234,7 @@ impl LanguageOptions for JavaOptions {
 			package_mapping: builder.package_mapping,
 			library_mapping: builder.library_mapping,
 			extern_mapping: builder.extern_mapping,
+			downgrade: builder.downgrade,
 		})
 	}
 }
@@ -1052,6 +1246,7 @@ impl LanguageOptionsBuilder for JavaOptionsBuilder {
 			package_mapping: HashMap::new(),
 			library_mapping: HashMap::new(),
 			extern_mapping: HashMap::new(),
+			downgrade: false,
 		}
 	}
 
@@ -1095,6 +1290,14 @@ impl LanguageOptionsBuilder for JavaOptionsBuilder {
 
 			Ok(())
 		}
+		else if name == "downgrade" {
+			self.downgrade = match value.to_str() {
+				Some("true") => true,
+				Some("false") => false,
+				_ => return Err(GeneratorError::InvalidOptions(format!("Invalid value for downgrade: {}", value.to_string_lossy()))),
+			};
+			Ok(())
+		}
 		else {
 			Err(GeneratorError::InvalidOptions(format!("Unknown option: {}", name)))
 		}
@@ -1111,6 +1314,7 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for JavaCodeGener
 	type ConstGen = JavaConstGenerator<'a, Output>;
 	type VersionedTypeGen = JavaTypeGenerator<'a, Output, model::VersionedTypeDefinitionData>;
 	type InterfaceTypeGen = JavaTypeGenerator<'a, Output, model::InterfaceTypeDefinitionData>;
+	type FormatGen = JavaFormatGenerator<'a, Output>;
 
 	fn create_constant_generator(&'a mut self, constant: Named<'a, model::Constant>) -> Result<Self::ConstGen, GeneratorError> {
 		JavaConstGenerator::open(self.model, self.options, self.output, constant)
@@ -1123,5 +1327,9 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for JavaCodeGener
 	fn create_interface_type_generator(&'a mut self, t: Named<'a, model::InterfaceTypeDefinitionData>) -> Result<Self::InterfaceTypeGen, GeneratorError> {
 		JavaTypeGenerator::open(self.model, self.options, self.output, t)
 	}
+
+	fn create_format_generator(&'a mut self, format: Named<'a, model::FileFormat>) -> Result<Self::FormatGen, GeneratorError> {
+		JavaFormatGenerator::open(self.model, self.options, self.output, format)
+	}
 }
 
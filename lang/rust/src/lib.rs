@@ -0,0 +1,1313 @@
+use verilization_compiler::{lang, model, util, for_sep};
+
+use model::Named;
+use lang::{GeneratorError, Language, LanguageOptions, LanguageOptionsBuilder, OutputHandler};
+use std::ffi::OsString;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use lang::generator::*;
+use util::capitalize_identifier;
+
+pub struct RustOptionsBuilder {
+	output_dir: Option<OsString>,
+	package_mapping: HashMap<model::PackageName, String>,
+	library_mapping: HashMap<model::PackageName, String>,
+}
+
+pub struct RustOptions {
+	pub output_dir: OsString,
+	pub package_mapping: HashMap<model::PackageName, String>,
+	pub library_mapping: HashMap<model::PackageName, String>,
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+	"abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+	"do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+	"let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+	"return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+	"typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn escape_identifier(mut name: String) -> String {
+	if RUST_KEYWORDS.contains(&name.as_str()) {
+		name.push('_');
+	}
+	name
+}
+
+fn make_type_name(name: &str) -> String {
+	let mut name = String::from(name);
+	capitalize_identifier(&mut name);
+	escape_identifier(name)
+}
+
+fn make_snake_case_name(name: &str) -> String {
+	let chars: Vec<char> = name.chars().collect();
+	let mut result = String::new();
+
+	for (i, ch) in chars.iter().enumerate() {
+		if ch.is_ascii_uppercase() {
+			if i > 0 {
+				let prev = chars[i - 1];
+				let ends_acronym = prev.is_ascii_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_ascii_lowercase());
+				if prev.is_ascii_lowercase() || prev.is_ascii_digit() || ends_acronym {
+					result.push('_');
+				}
+			}
+			result.push(ch.to_ascii_lowercase());
+		}
+		else {
+			result.push(*ch);
+		}
+	}
+
+	escape_identifier(result)
+}
+
+fn make_field_name(field_name: &str) -> String {
+	make_snake_case_name(field_name)
+}
+
+fn make_module_name(name: &str) -> String {
+	make_snake_case_name(name)
+}
+
+
+fn package_module_path(options: &RustOptions, package: &model::PackageName) -> Result<String, GeneratorError> {
+	if let Some(path) = options.package_mapping.get(package) {
+		Ok(format!("crate::{}", path))
+	}
+	else if let Some(path) = options.library_mapping.get(package) {
+		Ok(format!("::{}", path))
+	}
+	else {
+		Err(GeneratorError::UnmappedPackage(package.clone()))
+	}
+}
+
+fn package_dir(options: &RustOptions, package: &model::PackageName) -> Result<PathBuf, GeneratorError> {
+	let module_path = options.package_mapping.get(package).ok_or_else(|| GeneratorError::UnmappedPackage(package.clone()))?;
+	let mut path = PathBuf::from(&options.output_dir);
+	for part in module_path.split("::") {
+		path.push(part);
+	}
+	Ok(path)
+}
+
+fn open_rust_file<'a, Output: OutputHandler<'a>>(options: &RustOptions, output: &'a mut Output, name: &model::QualifiedName) -> Result<Output::FileHandle, GeneratorError> {
+	let mut path = package_dir(options, &name.package)?;
+	path.push(make_module_name(&name.name) + ".rs");
+	output.create_file(path)
+}
+
+
+pub trait RustGenerator<'model> : Generator<'model> + GeneratorWithFile {
+	fn options(&self) -> &RustOptions;
+	fn add_user_converter(&mut self);
+
+	fn write_module_path(&mut self, name: &model::QualifiedName) -> Result<(), GeneratorError> {
+		let pkg_path = package_module_path(self.options(), &name.package)?;
+		write!(self.file(), "{}::{}", pkg_path, make_module_name(&name.name))?;
+		Ok(())
+	}
+
+	fn write_operation_target(&mut self, target: &OperationTarget) -> Result<(), GeneratorError> {
+		match target {
+			OperationTarget::VersionedType(name, version) | OperationTarget::InterfaceType(name, version) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::v{}", version)?;
+			},
+			OperationTarget::ExternType(name) => {
+				self.write_module_path(name)?;
+			},
+		}
+
+		Ok(())
+	}
+
+	fn write_type_args(&mut self, args: &Vec<LangType<'model>>) -> Result<(), GeneratorError> {
+		if !args.is_empty() {
+			write!(self.file(), "<")?;
+			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+				self.write_type(arg)?;
+			});
+			write!(self.file(), ">")?;
+		}
+
+		Ok(())
+	}
+
+	fn write_args(&mut self, args: &Vec<LangExpr<'model>>) -> Result<(), GeneratorError> {
+		write!(self.file(), "(")?;
+		for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+			self.write_expr(arg)?;
+		});
+		write!(self.file(), ")")?;
+
+		Ok(())
+	}
+
+	fn write_type_parameter(&mut self, name: &str) -> Result<(), GeneratorError> {
+		write!(self.file(), "{}", name)?;
+		Ok(())
+	}
+
+	fn write_type(&mut self, t: &LangType<'model>) -> Result<(), GeneratorError> {
+		match t {
+			LangType::Versioned(_, name, version, args, _) | LangType::Interface(name, version, args, _) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::V{}", version)?;
+				self.write_type_args(args)?;
+			},
+
+			LangType::Extern(name, args, _) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::{}", make_type_name(&name.name))?;
+				self.write_type_args(args)?;
+			},
+
+			LangType::TypeParameter(name) => self.write_type_parameter(name)?,
+
+			LangType::Integer(n) => {
+				write!(self.file(), "{}", n)?;
+			},
+
+			LangType::Converter(from, to) => {
+				write!(self.file(), "impl Fn(")?;
+				self.write_type(from)?;
+				write!(self.file(), ") -> ")?;
+				self.write_type(to)?;
+				write!(self.file(), " + Copy")?;
+			},
+
+			LangType::Codec(_) | LangType::RemoteObjectId | LangType::RemoteConnection => return Err(GeneratorError::CouldNotGenerateType),
+		}
+
+		Ok(())
+	}
+
+	fn write_codec_args(&mut self, args: &Vec<LangExpr<'model>>) -> Result<(), GeneratorError> {
+		if !args.is_empty() {
+			write!(self.file(), "<")?;
+			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+				self.write_codec_type(arg)?;
+			});
+			write!(self.file(), ">")?;
+		}
+
+		Ok(())
+	}
+
+	// Codecs are types that implement Codec, so a codec expression is written as the type of the codec.
+	fn write_codec_type(&mut self, codec: &LangExpr<'model>) -> Result<(), GeneratorError> {
+		match codec {
+			LangExpr::InvokeOperation(Operation::TypeCodec, OperationTarget::VersionedType(name, version), _, codec_args) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::V{}Codec", version)?;
+				self.write_codec_args(codec_args)?;
+			},
+			LangExpr::InvokeOperation(Operation::TypeCodec, OperationTarget::ExternType(name), _, codec_args) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::Codec")?;
+				self.write_codec_args(codec_args)?;
+			},
+			LangExpr::Identifier(codec_param) => write!(self.file(), "{}", codec_param)?,
+			// Integer arguments, such as the length of an array, are passed as const arguments.
+			LangExpr::IntegerLiteral(n) => write!(self.file(), "{}", n)?,
+			_ => return Err(GeneratorError::InvalidTypeForCodec),
+		}
+
+		Ok(())
+	}
+
+	fn write_operation_name(&mut self, op: &Operation) -> Result<(), GeneratorError> {
+		match op {
+			Operation::FromPreviousVersion(prev_ver) => write!(self.file(), "from_v{}", prev_ver)?,
+			Operation::ToPreviousVersion(prev_ver) => write!(self.file(), "to_v{}", prev_ver)?,
+			Operation::FinalTypeConverter => write!(self.file(), "converter")?,
+			Operation::TypeCodec => write!(self.file(), "codec")?,
+			Operation::FromInteger => write!(self.file(), "from_integer")?,
+			Operation::FromString => write!(self.file(), "from_string")?,
+			Operation::FromBoolean => write!(self.file(), "from_boolean")?,
+			Operation::FromFloat => write!(self.file(), "from_float")?,
+			Operation::FromSequence => write!(self.file(), "from_sequence")?,
+			Operation::FromMap => write!(self.file(), "from_map")?,
+			Operation::FromRecord(_) => write!(self.file(), "from_record")?,
+			Operation::FromCase(name) => write!(self.file(), "from_case_{}", make_snake_case_name(name).trim_end_matches('_'))?,
+			Operation::CreateRemoteWrapper => write!(self.file(), "create_remote_wrapper")?,
+			Operation::ReadAnyVersion => write!(self.file(), "read_any_version")?,
+		}
+
+		Ok(())
+	}
+
+	fn write_expr(&mut self, expr: &LangExpr<'model>) -> Result<(), GeneratorError> {
+		match expr {
+			LangExpr::Identifier(name) => write!(self.file(), "{}", escape_identifier(name.clone()))?,
+			LangExpr::IntegerLiteral(n) => {
+				// Literals that fit a primitive integer are converted directly; only larger values are parsed.
+				if let Some(n) = n.to_i128() {
+					if n < 0 {
+						write!(self.file(), "({}i128).into()", n)?
+					}
+					else {
+						write!(self.file(), "{}i128.into()", n)?
+					}
+				}
+				else if let Some(n) = n.to_u128() {
+					write!(self.file(), "{}u128.into()", n)?
+				}
+				else {
+					write!(self.file(), "\"{}\".parse().unwrap()", n)?
+				}
+			},
+			LangExpr::StringLiteral(s) => write!(self.file(), "{:?}", s)?,
+			LangExpr::BooleanLiteral(b) => write!(self.file(), "{}", b)?,
+			LangExpr::FloatLiteral(f) => write!(self.file(), "{:?}", f)?,
+			LangExpr::MapEntry(key, value) => {
+				write!(self.file(), "(")?;
+				self.write_expr(key)?;
+				write!(self.file(), ", ")?;
+				self.write_expr(value)?;
+				write!(self.file(), ")")?;
+			},
+			LangExpr::InvokeConverter { converter, value } => {
+				self.write_expr(converter)?;
+				write!(self.file(), "(")?;
+				self.write_expr(value)?;
+				write!(self.file(), ")")?;
+			},
+			LangExpr::IdentityConverter(t) => {
+				write!(self.file(), "std::convert::identity::<")?;
+				self.write_type(t)?;
+				write!(self.file(), ">")?;
+			},
+			LangExpr::ReadDiscriminator => write!(self.file(), "::verilization_runtime::read_discriminator(reader)?")?,
+			LangExpr::WriteDiscriminator(value) => write!(self.file(), "::verilization_runtime::write_discriminator(writer, {})?", value)?,
+			LangExpr::CodecRead { codec } => {
+				write!(self.file(), "<")?;
+				self.write_codec_type(codec)?;
+				write!(self.file(), " as ::verilization_runtime::Codec>::read_verilization(reader)?")?;
+			},
+			LangExpr::CodecWrite { codec, value } => {
+				write!(self.file(), "<")?;
+				self.write_codec_type(codec)?;
+				write!(self.file(), " as ::verilization_runtime::Codec>::write_verilization(&")?;
+				self.write_expr(value)?;
+				write!(self.file(), ", writer)?")?;
+			},
+			LangExpr::InvokeOperation(op @ (Operation::FromSequence | Operation::FromMap), target, _, args) => {
+				self.write_operation_target(target)?;
+				write!(self.file(), "::")?;
+				self.write_operation_name(op)?;
+				write!(self.file(), "(vec!")?;
+				self.write_args(args)?;
+				write!(self.file(), ")")?;
+			},
+			// read_any_version is defined in the module of the type rather than the module of the last version.
+			LangExpr::InvokeOperation(op @ Operation::ReadAnyVersion, OperationTarget::VersionedType(name, _), _, args) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::")?;
+				self.write_operation_name(op)?;
+				self.write_args(args)?;
+			},
+			LangExpr::InvokeOperation(op, target, _, args) => {
+				self.write_operation_target(target)?;
+				write!(self.file(), "::")?;
+				self.write_operation_name(op)?;
+				self.write_args(args)?;
+			},
+			LangExpr::InvokeUserConverter { name: _, from_ver, to_ver, type_args: _, args } => {
+				write!(self.file(), "super::conv::v{}_to_v{}", from_ver, to_ver)?;
+				self.add_user_converter();
+				if !args.is_empty() {
+					self.write_args(args)?;
+				}
+			},
+			LangExpr::ConstantValue(name, version) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::{}()", RustLanguage::constant_version_name(version))?;
+			},
+			LangExpr::CreateStruct(name, version, _, fields) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::V{} {{ ", version)?;
+				for (field_name, value) in fields {
+					write!(self.file(), "{}: ", make_field_name(field_name))?;
+					self.write_expr(value)?;
+					write!(self.file(), ", ")?;
+				}
+				write!(self.file(), "}}")?;
+			},
+			LangExpr::CreateEnum(name, version, _, field_name, value) => {
+				self.write_module_path(name)?;
+				write!(self.file(), "::V{}::{}(", version, make_type_name(field_name))?;
+				self.write_expr(value)?;
+				write!(self.file(), ")")?;
+			},
+			LangExpr::StructField(_, _, field_name, value) => {
+				self.write_expr(value)?;
+				write!(self.file(), ".{}", make_field_name(field_name))?;
+			},
+			LangExpr::ReadRemoteObject { .. } | LangExpr::WriteRemoteObject { .. } => return Err(GeneratorError::CouldNotGenerateType),
+		}
+
+		Ok(())
+	}
+}
+
+impl GeneratorNameMapping for RustLanguage {
+	fn convert_prev_type_param(param: &str) -> String {
+		format!("{}_1", param)
+	}
+
+	fn convert_current_type_param(param: &str) -> String {
+		format!("{}_2", param)
+	}
+
+	fn convert_conv_param_name(param: &str) -> String {
+		format!("{}_conv", param)
+	}
+
+	fn convert_prev_param_name() -> &'static str {
+		"prev"
+	}
+
+	fn codec_write_value_name() -> &'static str {
+		"value"
+	}
+
+	fn format_writer_name() -> &'static str {
+		"writer"
+	}
+
+	fn format_reader_name() -> &'static str {
+		"reader"
+	}
+
+	fn version_param_name() -> &'static str {
+		"version"
+	}
+
+	fn connection_name() -> &'static str {
+		"connection"
+	}
+
+	fn object_id_name() -> &'static str {
+		"object_id"
+	}
+
+	fn codec_codec_param_name(param: &str) -> String {
+		format!("{}_codec", param)
+	}
+
+	fn constant_version_name(version: &BigUint) -> String {
+		format!("v{}", version)
+	}
+}
+
+fn write_file_attributes<Gen: GeneratorWithFile>(gen: &mut Gen) -> Result<(), GeneratorError> {
+	writeln!(gen.file(), "#![allow(non_snake_case, non_camel_case_types, unused_variables, unreachable_code)]")?;
+	writeln!(gen.file())?;
+	Ok(())
+}
+
+
+
+struct RustConstGenerator<'a, Output: OutputHandler<'a>> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a RustOptions,
+	constant: Named<'a, model::Constant>,
+	scope: model::Scope<'a>,
+}
+
+impl <'a, Output: OutputHandler<'a>> Generator<'a> for RustConstGenerator<'a, Output> {
+	type Lang = RustLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> GeneratorWithFile for RustConstGenerator<'a, Output> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> RustGenerator<'a> for RustConstGenerator<'a, Output> {
+	fn options(&self) -> &RustOptions {
+		self.options
+	}
+
+	fn add_user_converter(&mut self) {}
+}
+
+impl <'a, Output: OutputHandler<'a>> ConstGenerator<'a> for RustConstGenerator<'a, Output> {
+	fn constant(&self) -> Named<'a, model::Constant> {
+		self.constant
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		write_file_attributes(self)
+	}
+
+	fn write_constant(&mut self, version_name: String, t: LangType<'a>, value: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "pub fn {}() -> ", version_name)?;
+		self.write_type(&t)?;
+		writeln!(self.file, " {{")?;
+		write!(self.file, "\t")?;
+		self.write_expr(&value)?;
+		writeln!(self.file)?;
+		writeln!(self.file, "}}")?;
+
+		Ok(())
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		Ok(())
+	}
+}
+
+
+
+impl <'a, Output: OutputHandler<'a>> RustConstGenerator<'a, Output> {
+
+	fn open(model: &'a model::Verilization, options: &'a RustOptions, output: &'a mut Output, constant: Named<'a, model::Constant>) -> Result<Self, GeneratorError> {
+		let file = open_rust_file(options, output, constant.name())?;
+		Ok(RustConstGenerator {
+			file,
+			model,
+			options,
+			constant,
+			scope: constant.scope(),
+		})
+	}
+
+}
+
+struct RustFormatGenerator<'a, Output: OutputHandler<'a>> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a RustOptions,
+	format: Named<'a, model::FileFormat>,
+	scope: model::Scope<'a>,
+}
+
+impl <'a, Output: OutputHandler<'a>> Generator<'a> for RustFormatGenerator<'a, Output> {
+	type Lang = RustLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> GeneratorWithFile for RustFormatGenerator<'a, Output> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> RustGenerator<'a> for RustFormatGenerator<'a, Output> {
+	fn options(&self) -> &RustOptions {
+		self.options
+	}
+
+	fn add_user_converter(&mut self) {}
+}
+
+impl <'a, Output: OutputHandler<'a>> FormatGenerator<'a> for RustFormatGenerator<'a, Output> {
+	fn format(&self) -> Named<'a, model::FileFormat> {
+		self.format
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		write_file_attributes(self)?;
+
+		write!(self.file, "pub const MAGIC: &[u8] = &[")?;
+		for_sep!(b, self.format.magic(), { write!(self.file, ", ")?; }, {
+			write!(self.file, "{}", b)?;
+		});
+		writeln!(self.file, "];")?;
+		writeln!(self.file)?;
+
+		Ok(())
+	}
+
+	fn write_read_file(&mut self, t: LangType<'a>, read_value: LangExpr<'a>) -> Result<(), GeneratorError> {
+		let reader = RustLanguage::format_reader_name();
+
+		write!(self.file, "pub fn read_file<R: ::verilization_runtime::FormatReader>({}: &mut R) -> Result<", reader)?;
+		self.write_type(&t)?;
+		writeln!(self.file, ", R::Error> {{")?;
+
+		writeln!(self.file, "	if {}.read_bytes(MAGIC.len())? != MAGIC {{", reader)?;
+		writeln!(self.file, "		return Err(R::invalid_data(\"Invalid magic number\"));")?;
+		writeln!(self.file, "	}}")?;
+
+		write!(self.file, "	let {} = ", RustLanguage::version_param_name())?;
+		match self.format.version_encoding() {
+			// A nat version is encoded in the same way as an enum tag.
+			model::FormatVersionEncoding::Nat => write!(self.file, "::verilization_runtime::read_discriminator({})?.ok_or_else(|| R::invalid_data(\"Unknown version\"))?", reader)?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "u64::from({}.read_u8()?)", reader)?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "u64::from({}.read_u16()?)", reader)?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "u64::from({}.read_u32()?)", reader)?,
+		}
+		writeln!(self.file, ";")?;
+
+		write!(self.file, "	")?;
+		match (&read_value, self.build_codec(t)?) {
+			// The codecs of a generic root type cannot be inferred, so they are passed explicitly.
+			(
+				LangExpr::InvokeOperation(op @ Operation::ReadAnyVersion, OperationTarget::VersionedType(name, _), _, args),
+				LangExpr::InvokeOperation(Operation::TypeCodec, _, _, codec_args),
+			) if !codec_args.is_empty() => {
+				self.write_module_path(name)?;
+				write!(self.file, "::")?;
+				self.write_operation_name(op)?;
+				write!(self.file, "::<")?;
+				for codec_arg in &codec_args {
+					self.write_codec_type(codec_arg)?;
+					write!(self.file, ", ")?;
+				}
+				write!(self.file, "R>")?;
+				self.write_args(args)?;
+			},
+			_ => self.write_expr(&read_value)?,
+		}
+		writeln!(self.file)?;
+		writeln!(self.file, "}}")?;
+		writeln!(self.file)?;
+
+		Ok(())
+	}
+
+	fn write_write_file(&mut self, t: LangType<'a>, version: &BigUint, codec: LangExpr<'a>) -> Result<(), GeneratorError> {
+		let writer = RustLanguage::format_writer_name();
+		let value = RustLanguage::codec_write_value_name();
+
+		write!(self.file, "pub fn write_file<W: ::verilization_runtime::FormatWriter>({}: &mut W, {}: &", writer, value)?;
+		self.write_type(&t)?;
+		writeln!(self.file, ") -> Result<(), W::Error> {{")?;
+
+		writeln!(self.file, "	{}.write_bytes(MAGIC)?;", writer)?;
+
+		write!(self.file, "	")?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => write!(self.file, "::verilization_runtime::write_discriminator({}, {})", writer, version)?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "{}.write_u8({})", writer, version)?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "{}.write_u16({})", writer, version)?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "{}.write_u32({})", writer, version)?,
+		}
+		writeln!(self.file, "?;")?;
+
+		write!(self.file, "	<")?;
+		self.write_codec_type(&codec)?;
+		writeln!(self.file, " as ::verilization_runtime::Codec>::write_verilization({}, {})", value, writer)?;
+		writeln!(self.file, "}}")?;
+
+		Ok(())
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		Ok(())
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> RustFormatGenerator<'a, Output> {
+
+	fn open(model: &'a model::Verilization, options: &'a RustOptions, output: &'a mut Output, format: Named<'a, model::FileFormat>) -> Result<Self, GeneratorError> {
+		let file = open_rust_file(options, output, format.name())?;
+		Ok(RustFormatGenerator {
+			file,
+			model,
+			options,
+			format,
+			scope: format.scope(),
+		})
+	}
+
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum StatementContext {
+	CodecRead,
+	CodecWrite,
+	Converter,
+}
+
+struct RustTypeGenerator<'a, Output: OutputHandler<'a>, TypeDef> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a RustOptions,
+	type_def: Named<'a, TypeDef>,
+	scope: model::Scope<'a>,
+	version: Option<BigUint>,
+	version_module_open: bool,
+	uses_user_converters: bool,
+	indentation_level: u32,
+	// Within codecs, type parameters are replaced by the values of codec type parameters.
+	codec_type_params: bool,
+}
+
+impl <'a, Output: OutputHandler<'a>, TypeDef> Generator<'a> for RustTypeGenerator<'a, Output, TypeDef> {
+	type Lang = RustLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>, TypeDef> GeneratorWithFile for RustTypeGenerator<'a, Output, TypeDef> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>, TypeDef> Indentation for RustTypeGenerator<'a, Output, TypeDef> {
+	fn indentation_size(&mut self) -> &mut u32 {
+		&mut self.indentation_level
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> RustGenerator<'a> for RustTypeGenerator<'a, Output, TypeDef> {
+	fn options(&self) -> &RustOptions {
+		self.options
+	}
+
+	fn add_user_converter(&mut self) {
+		self.uses_user_converters = true;
+	}
+
+	fn write_type_parameter(&mut self, name: &str) -> Result<(), GeneratorError> {
+		if self.codec_type_params {
+			write!(self.file, "<{} as ::verilization_runtime::Codec>::Value", RustLanguage::codec_codec_param_name(name))?;
+		}
+		else {
+			write!(self.file, "{}", name)?;
+		}
+		Ok(())
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> TypeGenerator<'a> for RustTypeGenerator<'a, Output, TypeDef> {
+	type TypeDefinition = TypeDef;
+
+	fn type_def(&self) -> Named<'a, TypeDef> {
+		self.type_def
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		write_file_attributes(self)
+	}
+
+	fn write_version_header(&mut self, t: LangType<'a>) -> Result<(), GeneratorError> {
+		let version;
+
+		match t {
+			LangType::Versioned(VersionedTypeKind::Struct, _, ver, _, fields) => {
+				version = ver;
+
+				writeln!(self.file, "#[derive(Clone, Debug, PartialEq)]")?;
+				write!(self.file, "pub struct V{}", version)?;
+				self.write_type_params(self.type_def().type_params())?;
+				writeln!(self.file, " {{")?;
+				self.indent_increase();
+				for field in fields.build()? {
+					self.write_indent()?;
+					write!(self.file, "pub {}: ", make_field_name(field.name))?;
+					self.write_type(&field.field_type)?;
+					writeln!(self.file, ",")?;
+				}
+				self.indent_decrease();
+				writeln!(self.file, "}}")?;
+			},
+			LangType::Versioned(VersionedTypeKind::Enum, _, ver, _, fields) => {
+				version = ver;
+
+				writeln!(self.file, "#[derive(Clone, Debug, PartialEq)]")?;
+				write!(self.file, "pub enum V{}", version)?;
+				self.write_type_params(self.type_def().type_params())?;
+				writeln!(self.file, " {{")?;
+				self.indent_increase();
+				for field in fields.build()? {
+					self.write_indent()?;
+					write!(self.file, "{}(", make_type_name(field.name))?;
+					self.write_type(&field.field_type)?;
+					writeln!(self.file, "),")?;
+				}
+				self.indent_decrease();
+				writeln!(self.file, "}}")?;
+			},
+			LangType::Interface(_, ver, _, methods) => {
+				version = ver;
+
+				write!(self.file, "pub trait V{}", version)?;
+				self.write_type_params(self.type_def().type_params())?;
+				writeln!(self.file, " {{")?;
+				self.indent_increase();
+
+				for method in methods.build()? {
+					self.write_indent()?;
+					write!(self.file, "fn {}", make_field_name(method.name))?;
+					if !method.type_params.is_empty() {
+						write!(self.file, "<")?;
+						for_sep!(type_param, method.type_params, { write!(self.file, ", ")?; }, {
+							write!(self.file, "{}", type_param)?;
+						});
+						write!(self.file, ">")?;
+					}
+					write!(self.file, "(&self")?;
+					for param in &method.parameters {
+						write!(self.file, ", {}: ", make_field_name(param.name))?;
+						self.write_type(&param.param_type)?;
+					}
+					write!(self.file, ") -> ")?;
+					self.write_type(&method.return_type)?;
+					writeln!(self.file, ";")?;
+				}
+
+				self.indent_decrease();
+				writeln!(self.file, "}}")?;
+			},
+
+			_ => return Err(GeneratorError::CouldNotGenerateType)
+		}
+
+		writeln!(self.file)?;
+
+		self.version = Some(version);
+		self.version_module_open = false;
+
+		Ok(())
+	}
+
+	fn write_operation(&mut self, operation: OperationInfo<'a>) -> Result<(), GeneratorError> {
+		match &operation.implementation {
+			LangExprStmt::CreateCodec { t, read, write } => {
+				self.close_version_module()?;
+				self.write_codec_impl(&operation.type_params, t, read, write)
+			},
+
+			// The Rust runtime does not support remote objects, so only the interface trait is generated.
+			LangExprStmt::CreateRemoteWrapper { .. } => Ok(()),
+
+			// The codec closes the module of the last version, so read_any_version is written in the module of the type.
+			LangExprStmt::ReadAnyVersion { t, read } => {
+				self.close_version_module()?;
+				self.write_read_any_version(&operation, t, read)
+			},
+
+			_ => {
+				self.open_version_module()?;
+
+				self.write_indent()?;
+				write!(self.file, "pub fn ")?;
+				self.write_operation_name(&operation.operation)?;
+				self.write_type_params(&operation.type_params)?;
+				write!(self.file, "(")?;
+				for_sep!((param_name, param), &operation.params, { write!(self.file, ", ")?; }, {
+					write!(self.file, "{}: ", escape_identifier(param_name.clone()))?;
+					self.write_type(param)?;
+				});
+				write!(self.file, ") -> ")?;
+				self.write_type(&operation.result)?;
+				writeln!(self.file, " {{")?;
+				self.indent_increase();
+
+				self.write_expr_statement(&operation.implementation)?;
+
+				self.indent_decrease();
+				self.write_indent()?;
+				writeln!(self.file, "}}")?;
+
+				Ok(())
+			},
+		}
+	}
+
+	fn write_version_footer(&mut self) -> Result<(), GeneratorError> {
+		self.close_version_module()
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		if self.uses_user_converters {
+			writeln!(self.file, "#[path = \"{}_conv.rs\"]", make_module_name(&self.type_def.name().name))?;
+			writeln!(self.file, "mod conv;")?;
+		}
+
+		Ok(())
+	}
+
+}
+
+
+
+impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> RustTypeGenerator<'a, Output, TypeDef> {
+
+	fn open(model: &'a model::Verilization, options: &'a RustOptions, output: &'a mut Output, type_def: Named<'a, TypeDef>) -> Result<Self, GeneratorError> {
+		let file = open_rust_file(options, output, type_def.name())?;
+		Ok(RustTypeGenerator {
+			file,
+			model,
+			options,
+			type_def,
+			scope: type_def.scope(),
+			version: None,
+			version_module_open: false,
+			uses_user_converters: false,
+			indentation_level: 0,
+			codec_type_params: false,
+		})
+	}
+
+	fn open_version_module(&mut self) -> Result<(), GeneratorError> {
+		if !self.version_module_open {
+			let version = self.version.clone().ok_or(GeneratorError::CouldNotGenerateType)?;
+			writeln!(self.file, "pub mod v{} {{", version)?;
+			self.indent_increase();
+			self.version_module_open = true;
+		}
+
+		Ok(())
+	}
+
+	fn close_version_module(&mut self) -> Result<(), GeneratorError> {
+		if self.version_module_open {
+			self.indent_decrease();
+			writeln!(self.file, "}}")?;
+			writeln!(self.file)?;
+			self.version_module_open = false;
+		}
+
+		Ok(())
+	}
+
+	fn write_codec_params(&mut self, type_params: &Vec<String>) -> Result<(), GeneratorError> {
+		if !type_params.is_empty() {
+			write!(self.file, "<")?;
+			for_sep!(param, type_params, { write!(self.file, ", ")?; }, {
+				write!(self.file, "{}", RustLanguage::codec_codec_param_name(param))?;
+			});
+			write!(self.file, ">")?;
+		}
+
+		Ok(())
+	}
+
+	fn write_codec_impl(&mut self, type_params: &Vec<String>, t: &LangType<'a>, read: &LangStmt<'a>, write: &LangStmt<'a>) -> Result<(), GeneratorError> {
+		let version = self.version.clone().ok_or(GeneratorError::CouldNotGenerateType)?;
+
+		write!(self.file, "pub struct V{}Codec", version)?;
+		match type_params.len() {
+			0 => {},
+			1 => {
+				self.write_codec_params(type_params)?;
+				write!(self.file, "(::std::marker::PhantomData<{}>)", RustLanguage::codec_codec_param_name(&type_params[0]))?;
+			},
+			_ => {
+				self.write_codec_params(type_params)?;
+				write!(self.file, "(::std::marker::PhantomData<(")?;
+				for_sep!(param, type_params, { write!(self.file, ", ")?; }, {
+					write!(self.file, "{}", RustLanguage::codec_codec_param_name(param))?;
+				});
+				write!(self.file, ")>)")?;
+			},
+		}
+		writeln!(self.file, ";")?;
+		writeln!(self.file)?;
+
+		self.codec_type_params = true;
+
+		write!(self.file, "impl")?;
+		if !type_params.is_empty() {
+			write!(self.file, "<")?;
+			for_sep!(param, type_params, { write!(self.file, ", ")?; }, {
+				write!(self.file, "{}: ::verilization_runtime::Codec", RustLanguage::codec_codec_param_name(param))?;
+			});
+			write!(self.file, ">")?;
+		}
+		write!(self.file, " ::verilization_runtime::Codec for V{}Codec", version)?;
+		self.write_codec_params(type_params)?;
+		writeln!(self.file, " {{")?;
+		self.indent_increase();
+
+		self.write_indent()?;
+		write!(self.file, "type Value = ")?;
+		self.write_type(t)?;
+		writeln!(self.file, ";")?;
+		writeln!(self.file)?;
+
+		self.write_indent()?;
+		writeln!(self.file, "fn read_verilization<R: ::verilization_runtime::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {{")?;
+		self.indent_increase();
+		self.write_statement(read, StatementContext::CodecRead)?;
+		self.indent_decrease();
+		self.write_indent()?;
+		writeln!(self.file, "}}")?;
+
+		writeln!(self.file)?;
+
+		self.write_indent()?;
+		writeln!(self.file, "fn write_verilization<W: ::verilization_runtime::FormatWriter>({}: &Self::Value, writer: &mut W) -> Result<(), W::Error> {{", RustLanguage::codec_write_value_name())?;
+		self.indent_increase();
+		self.write_statement(write, StatementContext::CodecWrite)?;
+		self.write_indent()?;
+		writeln!(self.file, "Ok(())")?;
+		self.indent_decrease();
+		self.write_indent()?;
+		writeln!(self.file, "}}")?;
+
+		self.indent_decrease();
+		writeln!(self.file, "}}")?;
+		writeln!(self.file)?;
+
+		self.codec_type_params = false;
+
+		Ok(())
+	}
+
+	// The version is a u64 rather than an Option like a discriminator, so the cases are written here instead of with write_statement.
+	fn write_read_any_version(&mut self, operation: &OperationInfo<'a>, t: &LangType<'a>, read: &LangStmt<'a>) -> Result<(), GeneratorError> {
+		let cases = match read {
+			LangStmt::MatchDiscriminator { cases, .. } => cases,
+			_ => return Err(GeneratorError::CouldNotGenerateType),
+		};
+
+		self.write_indent()?;
+		write!(self.file, "pub fn ")?;
+		self.write_operation_name(&operation.operation)?;
+		write!(self.file, "<")?;
+		for param in &operation.type_params {
+			write!(self.file, "{}: ::verilization_runtime::Codec, ", RustLanguage::codec_codec_param_name(param))?;
+		}
+		self.codec_type_params = true;
+		write!(self.file, "R: ::verilization_runtime::FormatReader>({}: u64, {}: &mut R) -> Result<", RustLanguage::version_param_name(), RustLanguage::format_reader_name())?;
+		self.write_type(t)?;
+		writeln!(self.file, ", R::Error> {{")?;
+		self.indent_increase();
+
+		self.write_indent()?;
+		writeln!(self.file, "match {} {{", RustLanguage::version_param_name())?;
+		self.indent_increase();
+
+		for (n, body) in cases {
+			self.write_indent()?;
+			writeln!(self.file, "{} => {{", n)?;
+
+			self.indent_increase();
+			self.write_statement(body, StatementContext::CodecRead)?;
+			self.indent_decrease();
+
+			self.write_indent()?;
+			writeln!(self.file, "}},")?;
+		}
+
+		self.write_indent()?;
+		writeln!(self.file, "_ => return Err(R::invalid_data(\"Unknown version\")),")?;
+
+		self.indent_decrease();
+		self.write_indent()?;
+		writeln!(self.file, "}}")?;
+
+		self.indent_decrease();
+		self.write_indent()?;
+		writeln!(self.file, "}}")?;
+		writeln!(self.file)?;
+
+		self.codec_type_params = false;
+
+		Ok(())
+	}
+
+	fn write_expr_statement(&mut self, stmt: &LangExprStmt<'a>) -> Result<(), GeneratorError> {
+		match stmt {
+			LangExprStmt::Expr(expr) => {
+				self.write_indent()?;
+				self.write_expr(expr)?;
+				writeln!(self.file)?;
+			},
+
+			LangExprStmt::CreateConverter { from_type, to_type, body } => {
+				self.write_indent()?;
+				write!(self.file, "move |{}: ", RustLanguage::convert_prev_param_name())?;
+				self.write_type(from_type)?;
+				write!(self.file, "| -> ")?;
+				self.write_type(to_type)?;
+				writeln!(self.file, " {{")?;
+				self.indent_increase();
+				self.write_statement(body, StatementContext::Converter)?;
+				self.indent_decrease();
+				self.write_indent()?;
+				writeln!(self.file, "}}")?;
+			},
+
+			LangExprStmt::CreateCodec { .. } | LangExprStmt::CreateRemoteWrapper { .. } | LangExprStmt::ReadAnyVersion { .. } => return Err(GeneratorError::CouldNotGenerateType),
+		}
+
+		Ok(())
+	}
+
+	fn write_statement(&mut self, stmt: &LangStmt<'a>, context: StatementContext) -> Result<(), GeneratorError> {
+		match stmt {
+			LangStmt::Expr(exprs, result_expr) => {
+				for expr in exprs {
+					self.write_indent()?;
+					self.write_expr(expr)?;
+					writeln!(self.file, ";")?;
+				}
+
+				if let Some(result_expr) = result_expr {
+					self.write_indent()?;
+					if context == StatementContext::CodecRead {
+						write!(self.file, "return Ok(")?;
+						self.write_expr(result_expr)?;
+						writeln!(self.file, ");")?;
+					}
+					else {
+						write!(self.file, "return ")?;
+						self.write_expr(result_expr)?;
+						writeln!(self.file, ";")?;
+					}
+				}
+			},
+
+			LangStmt::MatchEnum { value, value_type, cases } => {
+				let (type_name, type_version) = match value_type {
+					LangType::Versioned(_, name, version, _, _) => (*name, version),
+					_ => return Err(GeneratorError::CouldNotGenerateType),
+				};
+
+				self.write_indent()?;
+				write!(self.file, "match ")?;
+				// A reference to an enum with no cases must be dereferenced to match on it.
+				if cases.is_empty() && context == StatementContext::CodecWrite {
+					write!(self.file, "*")?;
+				}
+				self.write_expr(value)?;
+				writeln!(self.file, " {{")?;
+
+				self.indent_increase();
+
+				for MatchCase { binding_name, case_name, body } in cases {
+					self.write_indent()?;
+					self.write_module_path(type_name)?;
+					writeln!(self.file, "::V{}::{}({}) => {{", type_version, make_type_name(case_name), escape_identifier(binding_name.clone()))?;
+
+					self.indent_increase();
+					self.write_statement(body, context)?;
+					self.indent_decrease();
+
+					self.write_indent()?;
+					writeln!(self.file, "}},")?;
+				}
+
+				self.indent_decrease();
+
+				self.write_indent()?;
+				writeln!(self.file, "}}")?;
+			},
+
+			LangStmt::MatchDiscriminator { value, cases } => {
+				self.write_indent()?;
+				write!(self.file, "match ")?;
+				self.write_expr(value)?;
+				writeln!(self.file, " {{")?;
+
+				self.indent_increase();
+
+				for (n, body) in cases {
+					self.write_indent()?;
+					writeln!(self.file, "Some({}) => {{", n)?;
+
+					self.indent_increase();
+					self.write_statement(body, context)?;
+					self.indent_decrease();
+
+					self.write_indent()?;
+					writeln!(self.file, "}},")?;
+				}
+
+				self.write_indent()?;
+				writeln!(self.file, "_ => return Err(R::invalid_data(\"Unknown tag\")),")?;
+
+				self.indent_decrease();
+
+				self.write_indent()?;
+				writeln!(self.file, "}}")?;
+			},
+		}
+
+		Ok(())
+	}
+
+	fn write_type_params(&mut self, params: &Vec<String>) -> Result<(), GeneratorError> {
+		if !params.is_empty() {
+			write!(self.file, "<")?;
+			for_sep!(param, params, { write!(self.file, ", ")?; }, {
+				write!(self.file, "{}", param)?;
+			});
+			write!(self.file, ">")?;
+		}
+
+		Ok(())
+	}
+}
+
+
+// Each mapped package directory gets a mod.rs declaring the modules of the types and constants in the package.
+fn write_package_modules<Output: for<'output> OutputHandler<'output>>(model: &model::Verilization, options: &RustOptions, output: &mut Output) -> Result<(), GeneratorError> {
+	let mut package_modules: HashMap<&model::PackageName, Vec<String>> = HashMap::new();
+
+	let names = model.constants().map(|constant| constant.name())
+		.chain(model.types().map(|t| t.name()))
+		.chain(model.formats().map(|format| format.name()));
+
+	for name in names {
+		if options.package_mapping.contains_key(&name.package) {
+			package_modules.entry(&name.package).or_default().push(make_module_name(&name.name));
+		}
+	}
+
+	for (package, mut modules) in package_modules {
+		modules.sort();
+
+		let mut path = package_dir(options, package)?;
+		path.push("mod.rs");
+
+		let mut file = output.create_file(path)?;
+		for module in modules {
+			writeln!(file, "pub mod {};", module)?;
+		}
+	}
+
+	Ok(())
+}
+
+
+pub struct RustLanguage {}
+
+impl Language for RustLanguage {
+	type Options = RustOptions;
+
+	fn name() -> &'static str {
+		"rust"
+	}
+
+	fn generate<Output: for<'output> OutputHandler<'output>>(model: &model::Verilization, options: Self::Options, output: &mut Output) -> Result<(), GeneratorError> {
+		{
+			let mut codegen = RustCodeGenerator {
+				model,
+				options: &options,
+				output,
+			};
+			codegen.generate(model)?;
+		}
+
+		write_package_modules(model, &options, output)
+	}
+
+}
+
+impl LanguageOptions for RustOptions {
+	type Builder = RustOptionsBuilder;
+
+	fn build(builder: Self::Builder) -> Result<Self, GeneratorError> {
+		Ok(RustOptions {
+			output_dir: builder.output_dir.ok_or_else(|| GeneratorError::InvalidOptions(String::from("Output directory not specified")))?,
+			package_mapping: builder.package_mapping,
+			library_mapping: builder.library_mapping,
+		})
+	}
+}
+
+impl LanguageOptionsBuilder for RustOptionsBuilder {
+	fn empty() -> RustOptionsBuilder {
+		RustOptionsBuilder {
+			output_dir: None,
+			package_mapping: HashMap::new(),
+			library_mapping: HashMap::new(),
+		}
+	}
+
+	fn add(&mut self, name: &str, value: OsString) -> Result<(), GeneratorError> {
+		if name == "out_dir" {
+			if self.output_dir.is_some() {
+				return Err(GeneratorError::InvalidOptions(String::from("Output directory already specified")))
+			}
+
+			self.output_dir = Some(value);
+			Ok(())
+		}
+		else if let Some(pkg) = name.strip_prefix("pkg:") {
+			let package = model::PackageName::from_str(pkg);
+
+			let rust_module = value.into_string().map_err(|_| GeneratorError::InvalidOptions(format!("Invalid Rust module path for package: {}", pkg)))?;
+
+			if self.library_mapping.contains_key(&package) || self.package_mapping.insert(package, rust_module).is_some() {
+				return Err(GeneratorError::InvalidOptions(format!("Package already mapped: {}", pkg)))
+			}
+			Ok(())
+		}
+		else if let Some(pkg) = name.strip_prefix("lib:") {
+			let package = model::PackageName::from_str(pkg);
+
+			let rust_module = value.into_string().map_err(|_| GeneratorError::InvalidOptions(format!("Invalid Rust module path for package: {}", pkg)))?;
+
+			if self.package_mapping.contains_key(&package) || self.library_mapping.insert(package, rust_module).is_some() {
+				return Err(GeneratorError::InvalidOptions(format!("Package already mapped: {}", pkg)))
+			}
+			Ok(())
+		}
+		else {
+			Err(GeneratorError::InvalidOptions(format!("Unknown option: {}", name)))
+		}
+	}
+}
+
+struct RustCodeGenerator<'a, Output> {
+	model: &'a model::Verilization,
+	options: &'a RustOptions,
+	output: &'a mut Output,
+}
+
+impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for RustCodeGenerator<'b, Output> {
+	type ConstGen = RustConstGenerator<'a, Output>;
+	type VersionedTypeGen = RustTypeGenerator<'a, Output, model::VersionedTypeDefinitionData>;
+	type InterfaceTypeGen = RustTypeGenerator<'a, Output, model::InterfaceTypeDefinitionData>;
+	type FormatGen = RustFormatGenerator<'a, Output>;
+
+	fn create_constant_generator(&'a mut self, constant: Named<'a, model::Constant>) -> Result<Self::ConstGen, GeneratorError> {
+		RustConstGenerator::open(self.model, self.options, self.output, constant)
+	}
+
+	fn create_versioned_type_generator(&'a mut self, t: Named<'a, model::VersionedTypeDefinitionData>) -> Result<Self::VersionedTypeGen, GeneratorError> {
+		RustTypeGenerator::open(self.model, self.options, self.output, t)
+	}
+
+	fn create_interface_type_generator(&'a mut self, t: Named<'a, model::InterfaceTypeDefinitionData>) -> Result<Self::InterfaceTypeGen, GeneratorError> {
+		RustTypeGenerator::open(self.model, self.options, self.output, t)
+	}
+
+	fn create_format_generator(&'a mut self, format: Named<'a, model::FileFormat>) -> Result<Self::FormatGen, GeneratorError> {
+		RustFormatGenerator::open(self.model, self.options, self.output, format)
+	}
+}
@@ -19,12 +19,14 @@ pub struct ScalaOptionsBuilder {
 	output_dir: Option<OsString>,
 	package_mapping: PackageMap,
 	library_mapping: PackageMap,
+	downgrade: bool,
 }
 
 pub struct ScalaOptions {
 	pub output_dir: OsString,
 	pub package_mapping: PackageMap,
 	pub library_mapping: PackageMap,
+	pub downgrade: bool,
 }
 
 fn make_type_name(name: &str) -> String {
@@ -113,7 +115,9 @@ pub trait ScalaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 		Ok(())
 	}
 
+	// Integer arguments are only passed to codecs, so they are not written as type arguments.
 	fn write_type_args(&mut self, args: &Vec<LangType<'a>>) -> Result<(), GeneratorError> {
+		let args = args.iter().filter(|arg| !matches!(arg, LangType::Integer(_))).collect::<Vec<_>>();
 		if !args.is_empty() {
 			write!(self.file(), "[")?;
 			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
@@ -153,6 +157,8 @@ pub trait ScalaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 				write!(self.file(), "{}", name)?;
 			},
 
+			LangType::Integer(_) => return Err(GeneratorError::CouldNotGenerateType),
+
 			LangType::Converter(from, to) => {
 				write!(self.file(), "{}.Converter[", RUNTIME_PACKAGE)?;
 				self.write_type(&*from)?;
@@ -174,27 +180,47 @@ pub trait ScalaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 
 	fn write_args(&mut self, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
 		if !args.is_empty() {
-			write!(self.file(), "(")?;
-			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
-				self.write_expr(&arg)?;
-			});
-			write!(self.file(), ")")?;
+			self.write_call_args(args)?;
 		}
 
 		Ok(())
 	}
 
+	fn write_call_args(&mut self, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
+		write!(self.file(), "(")?;
+		for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+			self.write_expr(&arg)?;
+		});
+		write!(self.file(), ")")?;
+
+		Ok(())
+	}
+
+	// Literal constructors are functions even without arguments, but codecs and converters without arguments are values.
+	fn write_operation_args(&mut self, op: &Operation, args: &Vec<LangExpr<'a>>) -> Result<(), GeneratorError> {
+		match op {
+			Operation::FromInteger | Operation::FromString | Operation::FromBoolean | Operation::FromFloat |
+			Operation::FromSequence | Operation::FromMap | Operation::FromCase(_) => self.write_call_args(args),
+			_ => self.write_args(args),
+		}
+	}
+
 	fn write_operation_name(&mut self, op: &Operation) -> Result<(), GeneratorError> {
 		match op {
 			Operation::FromPreviousVersion(prev_ver) => write!(self.file(), "fromV{}", prev_ver)?,
+			Operation::ToPreviousVersion(prev_ver) => write!(self.file(), "toV{}", prev_ver)?,
 			Operation::FinalTypeConverter => write!(self.file(), "converter")?,
 			Operation::TypeCodec => write!(self.file(), "codec")?,
 			Operation::FromInteger => write!(self.file(), "fromInteger")?,
 			Operation::FromString => write!(self.file(), "fromString")?,
+			Operation::FromBoolean => write!(self.file(), "fromBoolean")?,
+			Operation::FromFloat => write!(self.file(), "fromFloat")?,
 			Operation::FromSequence => write!(self.file(), "fromSequence")?,
+			Operation::FromMap => write!(self.file(), "fromMap")?,
 			Operation::FromRecord(_) => write!(self.file(), "fromRecord")?,
 			Operation::FromCase(name) => write!(self.file(), "fromCase{}", make_type_name(name))?,
 			Operation::CreateRemoteWrapper => write!(self.file(), "createRemoteWrapper")?,
+			Operation::ReadAnyVersion => write!(self.file(), "readAnyVersion")?,
 		}
 
 		Ok(())
@@ -227,6 +253,15 @@ pub trait ScalaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 				}
 				write!(self.file(), "\"")?;
 			},
+			LangExpr::BooleanLiteral(b) => write!(self.file(), "{}", b)?,
+			LangExpr::FloatLiteral(f) => write!(self.file(), "{:?}", f)?,
+			LangExpr::MapEntry(key, value) => {
+				write!(self.file(), "(")?;
+				self.write_expr(key)?;
+				write!(self.file(), ", ")?;
+				self.write_expr(value)?;
+				write!(self.file(), ")")?;
+			},
 			LangExpr::InvokeConverter { converter, value } => {
 				self.write_expr(&*converter)?;
 				write!(self.file(), ".convert(")?;
@@ -264,12 +299,12 @@ pub trait ScalaGenerator<'a> : Generator<'a> + GeneratorWithFile {
 						});
 						write!(self.file(), ")")?;
 					},
-					_ => self.write_args(args)?,
+					_ => self.write_operation_args(op, args)?,
 				}
 			},
-			LangExpr::InvokeUserConverter { name, prev_ver, version, type_args, args } => {
+			LangExpr::InvokeUserConverter { name, from_ver, to_ver, type_args, args } => {
 				self.write_qual_name(name)?;
-				write!(self.file(), "_Conversions.v{}ToV{}", prev_ver, version)?;
+				write!(self.file(), "_Conversions.v{}ToV{}", from_ver, to_ver)?;
 				self.write_type_args(type_args)?;
 				self.write_args(args)?;
 			},
@@ -351,6 +386,10 @@ impl GeneratorNameMapping for ScalaLanguage {
 		"reader"
 	}
 
+	fn version_param_name() -> &'static str {
+		"version"
+	}
+
 	fn connection_name() -> &'static str {
 		"connection"
 	}
@@ -446,6 +485,128 @@ impl <'a, Output: OutputHandler<'a>> ScalaConstGenerator<'a, Output> {
 
 }
 
+struct ScalaFormatGenerator<'a, Output: OutputHandler<'a>> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a ScalaOptions,
+	format: Named<'a, model::FileFormat>,
+	scope: model::Scope<'a>,
+}
+
+impl <'a, Output: OutputHandler<'a>> Generator<'a> for ScalaFormatGenerator<'a, Output> {
+	type Lang = ScalaLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> GeneratorWithFile for ScalaFormatGenerator<'a, Output> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> ScalaGenerator<'a> for ScalaFormatGenerator<'a, Output> {
+	fn options(&self) -> &'a ScalaOptions {
+		self.options
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> FormatGenerator<'a> for ScalaFormatGenerator<'a, Output> {
+	fn format(&self) -> Named<'a, model::FileFormat> {
+		self.format
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		self.write_package(&self.format.name().package)?;
+
+		writeln!(self.file, "object {} {{", make_type_name(&self.format.name().name))?;
+
+		write!(self.file, "\tval magic: zio.Chunk[Byte] = zio.Chunk[Byte](")?;
+		for_sep!(b, self.format.magic(), { write!(self.file, ", ")?; }, {
+			write!(self.file, "{}.toByte", b)?;
+		});
+		writeln!(self.file, ")")?;
+
+		Ok(())
+	}
+
+	fn write_read_file(&mut self, t: LangType<'a>, read_value: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "\tdef readFile[R, E]({}: {}.FormatReader[R, E]): zio.ZIO[R, E, ", ScalaLanguage::format_reader_name(), RUNTIME_PACKAGE)?;
+		self.write_type(&t)?;
+		writeln!(self.file, "] =")?;
+
+		writeln!(self.file, "\t\tfor {{")?;
+		writeln!(self.file, "\t\t\theader <- {}.readBytes(magic.size)", ScalaLanguage::format_reader_name())?;
+		writeln!(self.file, "\t\t\t_ <- if(header == magic) zio.ZIO.unit else zio.ZIO.dieMessage(\"Invalid magic number.\")")?;
+
+		write!(self.file, "\t\t\t{} <- ", ScalaLanguage::version_param_name())?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => writeln!(self.file, "{}.Nat.codec.read({})", RUNTIME_PACKAGE, ScalaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U8 => writeln!(self.file, "{}.readByte().map(b => BigInt(b & 0xFF))", ScalaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U16 => writeln!(self.file, "{}.readShort().map(s => BigInt(s & 0xFFFF))", ScalaLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U32 => writeln!(self.file, "{}.readInt().map(i => BigInt(i & 0xFFFFFFFFL))", ScalaLanguage::format_reader_name())?,
+		}
+
+		write!(self.file, "\t\t\tvalue <- ")?;
+		self.write_expr(&read_value)?;
+		writeln!(self.file)?;
+		writeln!(self.file, "\t\t}} yield value")?;
+
+		Ok(())
+	}
+
+	fn write_write_file(&mut self, t: LangType<'a>, version: &BigUint, codec: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "\tdef writeFile[R, E]({}: {}.FormatWriter[R, E], {}: ", ScalaLanguage::format_writer_name(), RUNTIME_PACKAGE, ScalaLanguage::codec_write_value_name())?;
+		self.write_type(&t)?;
+		writeln!(self.file, "): zio.ZIO[R, E, Unit] =")?;
+
+		writeln!(self.file, "\t\tfor {{")?;
+		writeln!(self.file, "\t\t\t_ <- {}.writeBytes(magic)", ScalaLanguage::format_writer_name())?;
+
+		write!(self.file, "\t\t\t_ <- ")?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => writeln!(self.file, "{}.Nat.codec.write({}, {})", RUNTIME_PACKAGE, ScalaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U8 => writeln!(self.file, "{}.writeByte({}.toByte)", ScalaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U16 => writeln!(self.file, "{}.writeShort({}.toShort)", ScalaLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U32 => writeln!(self.file, "{}.writeInt({}L.toInt)", ScalaLanguage::format_writer_name(), version)?,
+		}
+
+		write!(self.file, "\t\t\t_ <- ")?;
+		self.write_expr(&codec)?;
+		writeln!(self.file, ".write({}, {})", ScalaLanguage::format_writer_name(), ScalaLanguage::codec_write_value_name())?;
+		writeln!(self.file, "\t\t}} yield ()")?;
+
+		Ok(())
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		writeln!(self.file, "}}")?;
+		Ok(())
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> ScalaFormatGenerator<'a, Output> {
+
+	fn open(model: &'a model::Verilization, options: &'a ScalaOptions, output: &'a mut Output, format: Named<'a, model::FileFormat>) -> Result<Self, GeneratorError> {
+		let file = open_scala_file(options, output, format.name())?;
+		Ok(ScalaFormatGenerator {
+			file,
+			model,
+			options,
+			format,
+			scope: format.scope(),
+		})
+	}
+
+}
+
 struct ScalaTypeGenerator<'a, Output: OutputHandler<'a>, TypeDef> {
 	options: &'a ScalaOptions,
 	model: &'a model::Verilization,
@@ -595,6 +756,30 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 	}
 
 	fn write_operation(&mut self, operation: OperationInfo<'a>) -> Result<(), GeneratorError> {
+		if let LangExprStmt::ReadAnyVersion { t, read } = operation.implementation {
+			let mut type_params = vec!("R", "E");
+			for type_param in &operation.type_params {
+				type_params.push(type_param);
+			}
+
+			self.write_indent()?;
+			write!(self.file, "def ")?;
+			self.write_operation_name(&operation.operation)?;
+			self.write_type_params(&type_params)?;
+			write!(self.file, "(")?;
+			for (param_name, param) in &operation.params {
+				write!(self.file, "{}: ", param_name)?;
+				self.write_type(param)?;
+				write!(self.file, ", ")?;
+			}
+			write!(self.file, "{}: BigInt, {}: {}.FormatReader[R, E]): zio.ZIO[R, E, ", ScalaLanguage::version_param_name(), ScalaLanguage::format_reader_name(), RUNTIME_PACKAGE)?;
+			self.write_type(&t)?;
+			write!(self.file, "] = ")?;
+			self.write_statement(*read, true)?;
+
+			return Ok(());
+		}
+
 		let mut type_params = Vec::new();
 		if operation.params.iter().any(|(_, param)| match param { LangType::RemoteConnection => true, _ => false, }) {
 			type_params.push("R");
@@ -649,6 +834,10 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 		Ok(())
 	}
 
+	fn generate_downgrades(&self) -> bool {
+		self.options.downgrade
+	}
+
 }
 
 impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> ScalaTypeGenerator<'a, Output, TypeDef> {
@@ -776,6 +965,8 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> ScalaT
 				self.write_indent()?;
 				writeln!(self.file, "}}")?;
 			},
+
+			LangExprStmt::ReadAnyVersion { .. } => return Err(GeneratorError::CouldNotGenerateType),
 		}
 
 		Ok(())
@@ -829,6 +1020,10 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> ScalaT
 			LangExpr::StructField(_, _, _, value) => {
 				self.gather_io_exprs(value, ops);
 			},
+			LangExpr::MapEntry(key, value) => {
+				self.gather_io_exprs(key, ops);
+				self.gather_io_exprs(value, ops);
+			},
 			_ => (),
 		}
 	}
@@ -1052,6 +1247,7 @@ impl LanguageOptions for ScalaOptions {
 			output_dir: builder.output_dir.ok_or_else(|| GeneratorError::InvalidOptions(String::from("Output directory not specified")))?,
 			package_mapping: builder.package_mapping,
 			library_mapping: builder.library_mapping,
+			downgrade: builder.downgrade,
 		})
 	}
 }
@@ -1062,6 +1258,7 @@ impl LanguageOptionsBuilder for ScalaOptionsBuilder {
 			output_dir: None,
 			package_mapping: HashMap::new(),
 			library_mapping: HashMap::new(),
+			downgrade: false,
 		}
 	}
 
@@ -1094,6 +1291,14 @@ impl LanguageOptionsBuilder for ScalaOptionsBuilder {
 			}
 			Ok(())
 		}
+		else if name == "downgrade" {
+			self.downgrade = match value.to_str() {
+				Some("true") => true,
+				Some("false") => false,
+				_ => return Err(GeneratorError::InvalidOptions(format!("Invalid value for downgrade: {}", value.to_string_lossy()))),
+			};
+			Ok(())
+		}
 		else {
 			Err(GeneratorError::InvalidOptions(format!("Unknown option: {}", name)))
 		}
@@ -1110,6 +1315,7 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for ScalaCodeGene
 	type ConstGen = ScalaConstGenerator<'a, Output>;
 	type VersionedTypeGen = ScalaTypeGenerator<'a, Output, model::VersionedTypeDefinitionData>;
 	type InterfaceTypeGen = ScalaTypeGenerator<'a, Output, model::InterfaceTypeDefinitionData>;
+	type FormatGen = ScalaFormatGenerator<'a, Output>;
 
 	fn create_constant_generator(&'a mut self, constant: Named<'a, model::Constant>) -> Result<Self::ConstGen, GeneratorError> {
 		ScalaConstGenerator::open(self.model, self.options, self.output, constant)
@@ -1122,5 +1328,9 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for ScalaCodeGene
 	fn create_interface_type_generator(&'a mut self, t: Named<'a, model::InterfaceTypeDefinitionData>) -> Result<Self::InterfaceTypeGen, GeneratorError> {
 		ScalaTypeGenerator::open(self.model, self.options, self.output, t)
 	}
+
+	fn create_format_generator(&'a mut self, format: Named<'a, model::FileFormat>) -> Result<Self::FormatGen, GeneratorError> {
+		ScalaFormatGenerator::open(self.model, self.options, self.output, format)
+	}
 }
 
@@ -3,7 +3,7 @@ use verilization_compiler::{lang, model, util, for_sep};
 use model::Named;
 use lang::{GeneratorError, Language, LanguageOptions, LanguageOptionsBuilder, OutputHandler};
 use std::ffi::OsString;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use num_bigint::BigUint;
@@ -14,12 +14,14 @@ pub struct TSOptionsBuilder {
 	output_dir: Option<OsString>,
 	package_mapping: HashMap<model::PackageName, OsString>,
 	library_mapping: HashMap<model::PackageName, OsString>,
+	downgrade: bool,
 }
 
 pub struct TSOptions {
 	pub output_dir: OsString,
 	pub package_mapping: HashMap<model::PackageName, OsString>,
 	pub library_mapping: HashMap<model::PackageName, OsString>,
+	pub downgrade: bool,
 }
 
 
@@ -63,6 +65,16 @@ fn write_operation_target<'a, Gen : TSGenerator<'a>>(gen: &mut Gen, target: &Ope
 	Ok(())
 }
 
+/// The modules imported by a generated file.
+///
+/// Values can use types that are not part of any field type, such as the types of nested literals.
+/// These are recorded when they are written and imported afterwards.
+#[derive(Default)]
+pub struct TSImports {
+	imported: HashSet<model::QualifiedName>,
+	unimported: BTreeSet<model::QualifiedName>,
+}
+
 pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 	type ReferencedTypeIterator : Iterator<Item = &'model model::QualifiedName>;
 
@@ -72,8 +84,16 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 	fn current_dir(&self) -> Result<PathBuf, GeneratorError>;
 
 	fn add_user_converter(&mut self, name: String);
+	fn imports(&mut self) -> &mut TSImports;
 
 	fn write_import_name(&mut self, name: &model::QualifiedName) -> Result<(), GeneratorError> {
+		if self.generator_element_name() != Some(name) {
+			let imports = self.imports();
+			if !imports.imported.contains(name) {
+				imports.unimported.insert(name.clone());
+			}
+		}
+
 		write!(self.file(), "sym_")?;
 
 		for part in &name.package.package {
@@ -134,13 +154,29 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 				model::ScopeLookup::NamedType(t) => t,
 			};
 
-			self.write_import(&t, &current_path)?;
+			if self.imports().imported.insert(t.clone()) {
+				self.write_import(&t, &current_path)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	fn write_unimported(&mut self) -> Result<(), GeneratorError> {
+		let current_path = self.current_dir()?;
+
+		for t in std::mem::take(&mut self.imports().unimported) {
+			if self.imports().imported.insert(t.clone()) {
+				self.write_import(&t, &current_path)?;
+			}
 		}
 
 		Ok(())
 	}
 
+	// Integer arguments are not types in TypeScript, so they are only passed to codecs.
 	fn write_type_args(&mut self, args: &Vec<LangType<'model>>) -> Result<(), GeneratorError> {
+		let args = args.iter().filter(|arg| !matches!(arg, LangType::Integer(_))).collect::<Vec<_>>();
 		if !args.is_empty() {
 			write!(self.file(), "<")?;
 			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
@@ -154,16 +190,31 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 
 	fn write_args(&mut self, args: &Vec<LangExpr<'model>>) -> Result<(), GeneratorError> {
 		if !args.is_empty() {
-			write!(self.file(), "(")?;
-			for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
-				self.write_expr(&arg)?;
-			});
-			write!(self.file(), ")")?;
+			self.write_call_args(args)?;
 		}
 
 		Ok(())
 	}
 
+	fn write_call_args(&mut self, args: &Vec<LangExpr<'model>>) -> Result<(), GeneratorError> {
+		write!(self.file(), "(")?;
+		for_sep!(arg, args, { write!(self.file(), ", ")?; }, {
+			self.write_expr(&arg)?;
+		});
+		write!(self.file(), ")")?;
+
+		Ok(())
+	}
+
+	// Literal constructors are functions even without arguments, but codecs and converters without arguments are values.
+	fn write_operation_args(&mut self, op: &Operation, args: &Vec<LangExpr<'model>>) -> Result<(), GeneratorError> {
+		match op {
+			Operation::FromInteger | Operation::FromString | Operation::FromBoolean | Operation::FromFloat |
+			Operation::FromSequence | Operation::FromMap | Operation::FromCase(_) => self.write_call_args(args),
+			_ => self.write_args(args),
+		}
+	}
+
 	fn write_type(&mut self, t: &LangType<'model>) -> Result<(), GeneratorError> {
 		Ok(match t {	
 			LangType::Versioned(_, name, version, args, _) | LangType::Interface(name, version, args, _) => {
@@ -188,6 +239,8 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 				write!(self.file(), "{}", name)?;
 			},
 
+			LangType::Integer(_) => return Err(GeneratorError::CouldNotGenerateType),
+
 			LangType::Converter(from, to) => {
 				write!(self.file(), "Converter<")?;
 				self.write_type(&*from)?;
@@ -210,14 +263,19 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 	fn write_operation_name(&mut self, op: &Operation) -> Result<(), GeneratorError> {
 		match op {
 			Operation::FromPreviousVersion(prev_ver) => write!(self.file(), "fromV{}", prev_ver)?,
+			Operation::ToPreviousVersion(prev_ver) => write!(self.file(), "toV{}", prev_ver)?,
 			Operation::FinalTypeConverter => write!(self.file(), "converter")?,
 			Operation::TypeCodec => write!(self.file(), "codec")?,
 			Operation::FromInteger => write!(self.file(), "fromInteger")?,
 			Operation::FromString => write!(self.file(), "fromString")?,
+			Operation::FromBoolean => write!(self.file(), "fromBoolean")?,
+			Operation::FromFloat => write!(self.file(), "fromFloat")?,
 			Operation::FromSequence => write!(self.file(), "fromSequence")?,
+			Operation::FromMap => write!(self.file(), "fromMap")?,
 			Operation::FromRecord(_) => write!(self.file(), "fromRecord")?,
 			Operation::FromCase(name) => write!(self.file(), "fromCase{}", make_type_name(name))?,
 			Operation::CreateRemoteWrapper => write!(self.file(), "createRemoteWrapper")?,
+			Operation::ReadAnyVersion => write!(self.file(), "readAnyVersion")?,
 		}
 
 		Ok(())
@@ -242,6 +300,15 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 				}
 				write!(self.file(), "\"")?;
 			},
+			LangExpr::BooleanLiteral(b) => write!(self.file(), "{}", b)?,
+			LangExpr::FloatLiteral(f) => write!(self.file(), "{:?}", f)?,
+			LangExpr::MapEntry(key, value) => {
+				write!(self.file(), "[")?;
+				self.write_expr(key)?;
+				write!(self.file(), ", ")?;
+				self.write_expr(value)?;
+				write!(self.file(), "]")?;
+			},
 
 			LangExpr::InvokeConverter { converter, value } => {
 				self.write_expr(&*converter)?;
@@ -284,11 +351,11 @@ pub trait TSGenerator<'model> : Generator<'model> + GeneratorWithFile {
 						}
 						write!(self.file(), "}})")?;
 					},
-					_ => self.write_args(args)?,
+					_ => self.write_operation_args(op, args)?,
 				}
 			},
-			LangExpr::InvokeUserConverter { name: _, prev_ver, version, type_args, args } => {
-				let name = format!("v{}_to_v{}", prev_ver, version);
+			LangExpr::InvokeUserConverter { name: _, from_ver, to_ver, type_args, args } => {
+				let name = format!("v{}_to_v{}", from_ver, to_ver);
 				write!(self.file(), "{}", name)?;
 				self.add_user_converter(name);
 				self.write_type_args(type_args)?;
@@ -370,6 +437,10 @@ impl GeneratorNameMapping for TypeScriptLanguage {
 		"reader"
 	}
 
+	fn version_param_name() -> &'static str {
+		"version"
+	}
+
 	fn connection_name() -> &'static str {
 		"connection"
 	}
@@ -404,6 +475,7 @@ struct TSConstGenerator<'a, Output: OutputHandler<'a>> {
 	options: &'a TSOptions,
 	constant: Named<'a, model::Constant>,
 	scope: model::Scope<'a>,
+	imports: TSImports,
 }
 
 impl <'a, Output: OutputHandler<'a>> Generator<'a> for TSConstGenerator<'a, Output> {
@@ -445,6 +517,10 @@ impl <'a, Output: OutputHandler<'a>> TSGenerator<'a> for TSConstGenerator<'a, Ou
 	}
 
 	fn add_user_converter(&mut self, _name: String) {}
+
+	fn imports(&mut self) -> &mut TSImports {
+		&mut self.imports
+	}
 }
 
 impl <'a, Output: OutputHandler<'a>> ConstGenerator<'a> for TSConstGenerator<'a, Output> {
@@ -467,7 +543,7 @@ impl <'a, Output: OutputHandler<'a>> ConstGenerator<'a> for TSConstGenerator<'a,
 	}
 
 	fn write_footer(&mut self) -> Result<(), GeneratorError> {
-		Ok(())
+		self.write_unimported()
 	}
 }
 
@@ -483,6 +559,151 @@ impl <'a, Output: OutputHandler<'a>> TSConstGenerator<'a, Output> {
 			options: options,
 			constant: constant,
 			scope: constant.scope(),
+			imports: TSImports::default(),
+		})
+	}
+
+}
+
+struct TSFormatGenerator<'a, Output: OutputHandler<'a>> {
+	file: Output::FileHandle,
+	model: &'a model::Verilization,
+	options: &'a TSOptions,
+	format: Named<'a, model::FileFormat>,
+	scope: model::Scope<'a>,
+	imports: TSImports,
+}
+
+impl <'a, Output: OutputHandler<'a>> Generator<'a> for TSFormatGenerator<'a, Output> {
+	type Lang = TypeScriptLanguage;
+
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> GeneratorWithFile for TSFormatGenerator<'a, Output> {
+	type GeneratorFile = Output::FileHandle;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		&mut self.file
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> TSGenerator<'a> for TSFormatGenerator<'a, Output> {
+	type ReferencedTypeIterator = model::ReferencedTypeIteratorVersionedType<'a>;
+	
+	fn generator_element_name(&self) -> Option<&'a model::QualifiedName> {
+		Some(self.format.name())
+	}
+
+	fn options(&self) -> &TSOptions {
+		self.options
+	}
+
+	fn referenced_types(&self) -> Self::ReferencedTypeIterator {
+		self.format.referenced_types()
+	}
+
+	fn current_dir(&self) -> Result<PathBuf, GeneratorError> {
+		current_dir_of_name(self, self.format.name())
+	}
+
+	fn add_user_converter(&mut self, _name: String) {}
+
+	fn imports(&mut self) -> &mut TSImports {
+		&mut self.imports
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> FormatGenerator<'a> for TSFormatGenerator<'a, Output> {
+	fn format(&self) -> Named<'a, model::FileFormat> {
+		self.format
+	}
+
+	fn write_header(&mut self) -> Result<(), GeneratorError> {
+		writeln!(self.file, "import {{FormatWriter, FormatReader, natCodec}} from \"@verilization/runtime\";")?;
+		self.write_imports()?;
+
+		write!(self.file, "export const magic: Uint8Array = new Uint8Array([")?;
+		for_sep!(b, self.format.magic(), { write!(self.file, ", ")?; }, {
+			write!(self.file, "{}", b)?;
+		});
+		writeln!(self.file, "]);")?;
+
+		Ok(())
+	}
+
+	fn write_read_file(&mut self, t: LangType<'a>, read_value: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "export async function readFile({}: FormatReader): Promise<", TypeScriptLanguage::format_reader_name())?;
+		self.write_type(&t)?;
+		writeln!(self.file, "> {{")?;
+
+		writeln!(self.file, "\tconst header = await {}.readBytes(magic.length);", TypeScriptLanguage::format_reader_name())?;
+		writeln!(self.file, "\tif(header.length !== magic.length || header.some((b, i) => b !== magic[i])) {{")?;
+		writeln!(self.file, "\t\tthrow new Error(\"Invalid magic number.\");")?;
+		writeln!(self.file, "\t}}")?;
+
+		write!(self.file, "\tconst {} = ", TypeScriptLanguage::version_param_name())?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => write!(self.file, "await natCodec.read({})", TypeScriptLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "BigInt(await {}.readU8())", TypeScriptLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "BigInt(await {}.readU16())", TypeScriptLanguage::format_reader_name())?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "BigInt(await {}.readU32())", TypeScriptLanguage::format_reader_name())?,
+		}
+		writeln!(self.file, ";")?;
+
+		write!(self.file, "\treturn await ")?;
+		self.write_expr(&read_value)?;
+		writeln!(self.file, ";")?;
+		writeln!(self.file, "}}")?;
+
+		Ok(())
+	}
+
+	fn write_write_file(&mut self, t: LangType<'a>, version: &BigUint, codec: LangExpr<'a>) -> Result<(), GeneratorError> {
+		write!(self.file, "export async function writeFile({}: FormatWriter, {}: ", TypeScriptLanguage::format_writer_name(), TypeScriptLanguage::codec_write_value_name())?;
+		self.write_type(&t)?;
+		writeln!(self.file, "): Promise<void> {{")?;
+
+		writeln!(self.file, "\tawait {}.writeBytes(magic);", TypeScriptLanguage::format_writer_name())?;
+
+		write!(self.file, "\tawait ")?;
+		match self.format.version_encoding() {
+			model::FormatVersionEncoding::Nat => write!(self.file, "natCodec.write({}, {}n)", TypeScriptLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U8 => write!(self.file, "{}.writeU8({})", TypeScriptLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U16 => write!(self.file, "{}.writeU16({})", TypeScriptLanguage::format_writer_name(), version)?,
+			model::FormatVersionEncoding::U32 => write!(self.file, "{}.writeU32({})", TypeScriptLanguage::format_writer_name(), version)?,
+		}
+		writeln!(self.file, ";")?;
+
+		write!(self.file, "\tawait ")?;
+		self.write_expr(&codec)?;
+		writeln!(self.file, ".write({}, {});", TypeScriptLanguage::format_writer_name(), TypeScriptLanguage::codec_write_value_name())?;
+		writeln!(self.file, "}}")?;
+
+		Ok(())
+	}
+
+	fn write_footer(&mut self) -> Result<(), GeneratorError> {
+		self.write_unimported()
+	}
+}
+
+impl <'a, Output: OutputHandler<'a>> TSFormatGenerator<'a, Output> {
+
+	fn open(model: &'a model::Verilization, options: &'a TSOptions, output: &'a mut Output, format: Named<'a, model::FileFormat>) -> Result<Self, GeneratorError> {
+		let file = open_ts_file(options, output, format.name())?;
+		Ok(TSFormatGenerator {
+			file,
+			model,
+			options,
+			format,
+			scope: format.scope(),
+			imports: TSImports::default(),
 		})
 	}
 
@@ -497,6 +718,7 @@ struct TSTypeGenerator<'a, Output: OutputHandler<'a>, TypeDef> {
 	versions: HashSet<BigUint>,
 	imported_user_converters: HashSet<String>,
 	unimported_user_converters: Vec<String>,
+	imports: TSImports,
 	indentation_level: u32,
 }
 
@@ -547,6 +769,10 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> TSGene
 	fn add_user_converter(&mut self, name: String) {
 		self.unimported_user_converters.push(name);
 	}
+
+	fn imports(&mut self) -> &mut TSImports {
+		&mut self.imports
+	}
 }
 
 impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> TypeGenerator<'a> for TSTypeGenerator<'a, Output, TypeDef> {
@@ -659,6 +885,29 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 	}
 
 	fn write_operation(&mut self, operation: OperationInfo<'a>) -> Result<(), GeneratorError> {
+		if let LangExprStmt::ReadAnyVersion { t, read } = &operation.implementation {
+			self.write_indent()?;
+			write!(self.file, "export async function ")?;
+			self.write_operation_name(&operation.operation)?;
+			self.write_type_params(&operation.type_params)?;
+			write!(self.file, "(")?;
+			for (param_name, param) in &operation.params {
+				write!(self.file, "{}: ", param_name)?;
+				self.write_type(param)?;
+				write!(self.file, ", ")?;
+			}
+			write!(self.file, "{}: bigint, {}: FormatReader): Promise<", TypeScriptLanguage::version_param_name(), TypeScriptLanguage::format_reader_name())?;
+			self.write_type(t)?;
+			writeln!(self.file, "> {{")?;
+			self.indent_increase();
+			self.write_statement(read)?;
+			self.indent_decrease();
+			self.write_indent()?;
+			writeln!(self.file, "}}")?;
+
+			return Ok(());
+		}
+
 		let is_func = !operation.type_params.is_empty() || !operation.params.is_empty();
 
 		self.write_indent()?;
@@ -718,6 +967,8 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 			}
 		}
 
+		self.write_unimported()?;
+
 		Ok(())
 	}
 
@@ -726,6 +977,10 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: 'a + model::GeneratableType<'a>> T
 		Ok(())
 	}
 
+	fn generate_downgrades(&self) -> bool {
+		self.options.downgrade
+	}
+
 }
 
 
@@ -743,6 +998,7 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> TSType
 			versions: HashSet::new(),
 			imported_user_converters: HashSet::new(),
 			unimported_user_converters: Vec::new(),
+			imports: TSImports::default(),
 			indentation_level: 0,
 		})
 	}
@@ -873,6 +1129,8 @@ impl <'a, Output: OutputHandler<'a>, TypeDef: model::GeneratableType<'a>> TSType
 				self.write_indent()?;
 				writeln!(self.file, "}}))();")?;
 			},
+
+			LangExprStmt::ReadAnyVersion { .. } => return Err(GeneratorError::CouldNotGenerateType),
 		}
 
 		Ok(())
@@ -1022,6 +1280,7 @@ impl LanguageOptions for TSOptions {
 			output_dir: builder.output_dir.ok_or_else(|| GeneratorError::InvalidOptions(String::from("Output directory not specified")))?,
 			package_mapping: builder.package_mapping,
 			library_mapping: builder.library_mapping,
+			downgrade: builder.downgrade,
 		})
 	}
 }
@@ -1032,6 +1291,7 @@ impl LanguageOptionsBuilder for TSOptionsBuilder {
 			output_dir: None,
 			package_mapping: HashMap::new(),
 			library_mapping: HashMap::new(),
+			downgrade: false,
 		}
 	}
 
@@ -1060,6 +1320,14 @@ impl LanguageOptionsBuilder for TSOptionsBuilder {
 			}
 			Ok(())
 		}
+		else if name == "downgrade" {
+			self.downgrade = match value.to_str() {
+				Some("true") => true,
+				Some("false") => false,
+				_ => return Err(GeneratorError::InvalidOptions(format!("Invalid value for downgrade: {}", value.to_string_lossy()))),
+			};
+			Ok(())
+		}
 		else {
 			Err(GeneratorError::InvalidOptions(format!("Unknown option: {}", name)))
 		}
@@ -1076,6 +1344,7 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for TSCodeGenerat
 	type ConstGen = TSConstGenerator<'a, Output>;
 	type VersionedTypeGen = TSTypeGenerator<'a, Output, model::VersionedTypeDefinitionData>;
 	type InterfaceTypeGen = TSTypeGenerator<'a, Output, model::InterfaceTypeDefinitionData>;
+	type FormatGen = TSFormatGenerator<'a, Output>;
 
 	fn create_constant_generator(&'a mut self, constant: Named<'a, model::Constant>) -> Result<Self::ConstGen, GeneratorError> {
 		TSConstGenerator::open(self.model, self.options, self.output, constant)
@@ -1088,5 +1357,9 @@ impl <'a, 'b, Output : OutputHandler<'a>> GeneratorFactory<'a> for TSCodeGenerat
 	fn create_interface_type_generator(&'a mut self, t: Named<'a, model::InterfaceTypeDefinitionData>) -> Result<Self::InterfaceTypeGen, GeneratorError> {
 		TSTypeGenerator::open(self.model, self.options, self.output, t)
 	}
+
+	fn create_format_generator(&'a mut self, format: Named<'a, model::FileFormat>) -> Result<Self::FormatGen, GeneratorError> {
+		TSFormatGenerator::open(self.model, self.options, self.output, format)
+	}
 }
 
@@ -0,0 +1,219 @@
+//! A language server for verilization source files.
+//!
+//! The server communicates over stdio and loads every `.verilization` file in the workspace folders into a single model.
+
+mod workspace;
+
+use verilization_compiler::{model, symbols};
+use workspace::{Workspace, offset_of, range_of};
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+	notification::{self, Notification as _},
+	request::{self, Request as _},
+	CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, GotoDefinitionParams, GotoDefinitionResponse,
+	Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams, Location, MarkupContent, MarkupKind,
+	OneOf, PublishDiagnosticsParams, ReferenceParams, ServerCapabilities, TextDocumentPositionParams,
+	TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+use std::error::Error;
+
+type ServerResult<A> = Result<A, Box<dyn Error + Send + Sync>>;
+
+fn capabilities() -> ServerCapabilities {
+	ServerCapabilities {
+		text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+		definition_provider: Some(OneOf::Left(true)),
+		references_provider: Some(OneOf::Left(true)),
+		hover_provider: Some(HoverProviderCapability::Simple(true)),
+		completion_provider: Some(CompletionOptions {
+			trigger_characters: Some(vec!(String::from("."))),
+			..CompletionOptions::default()
+		}),
+		..ServerCapabilities::default()
+	}
+}
+
+fn publish_diagnostics(connection: &Connection, workspace: &Workspace) -> ServerResult<()> {
+	for (uri, diagnostics) in workspace.diagnostics() {
+		let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+		connection.sender.send(Message::Notification(Notification::new(notification::PublishDiagnostics::METHOD.to_string(), params)))?;
+	}
+	Ok(())
+}
+
+fn symbol_at(workspace: &Workspace, position: &TextDocumentPositionParams) -> Option<symbols::Symbol> {
+	let file = workspace.file(&position.text_document.uri)?;
+	let offset = offset_of(file, position.position)?;
+	let file_symbols = workspace.symbols(&position.text_document.uri)?;
+	file_symbols.symbol_at(offset).cloned()
+}
+
+fn goto_definition(workspace: &Workspace, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+	let symbol = symbol_at(workspace, &params.text_document_position_params)?;
+	let span = symbols::definition_span(workspace.model()?, &symbol.name, symbol.kind)?;
+	Some(GotoDefinitionResponse::Scalar(workspace.location(span)?))
+}
+
+fn find_references(workspace: &Workspace, params: ReferenceParams) -> Option<Vec<Location>> {
+	let symbol = symbol_at(workspace, &params.text_document_position)?;
+
+	let mut locations = Vec::new();
+	for uri in workspace.uris() {
+		let file_symbols = match workspace.symbols(uri) {
+			Some(file_symbols) => file_symbols,
+			None => continue,
+		};
+
+		let references = file_symbols.symbols.iter()
+			.filter(|other| other.name == symbol.name && other.kind == symbol.kind)
+			.filter(|other| params.context.include_declaration || !other.is_definition);
+
+		locations.extend(references.filter_map(|other| workspace.location(&other.span)));
+	}
+
+	Some(locations)
+}
+
+fn hover(workspace: &Workspace, params: HoverParams) -> Option<Hover> {
+	let symbol = symbol_at(workspace, &params.text_document_position_params)?;
+	let description = symbols::describe(workspace.model()?, &symbol.name, symbol.kind)?;
+
+	Some(Hover {
+		contents: HoverContents::Markup(MarkupContent {
+			kind: MarkupKind::Markdown,
+			value: description,
+		}),
+		range: Some(range_of(&symbol.span)),
+	})
+}
+
+// Types in the package of the file and the root package can be used without their package.
+fn completion(workspace: &Workspace, params: CompletionParams) -> Vec<CompletionItem> {
+	let mut items: Vec<CompletionItem> = symbols::KEYWORDS.iter()
+		.map(|keyword| CompletionItem {
+			label: keyword.to_string(),
+			kind: Some(CompletionItemKind::KEYWORD),
+			..CompletionItem::default()
+		})
+		.collect();
+
+	let mut type_items = Vec::new();
+	let uri = &params.text_document_position.text_document.uri;
+	if let (Some(model), Some(file_symbols)) = (workspace.model(), workspace.symbols(uri)) {
+		for t in model.types() {
+			let name = t.name();
+			let label =
+				if name.package == file_symbols.package || name.package.package.is_empty() { name.name.clone() }
+				else { name.to_string() };
+
+			let kind = match t {
+				model::NamedTypeDefinition::StructType(_) => CompletionItemKind::STRUCT,
+				model::NamedTypeDefinition::EnumType(_) => CompletionItemKind::ENUM,
+				model::NamedTypeDefinition::ExternType(_) => CompletionItemKind::CLASS,
+				model::NamedTypeDefinition::InterfaceType(_) => CompletionItemKind::INTERFACE,
+			};
+
+			type_items.push(CompletionItem {
+				label,
+				kind: Some(kind),
+				detail: Some(name.to_string()),
+				..CompletionItem::default()
+			});
+		}
+	}
+
+	type_items.sort_by(|a, b| a.label.cmp(&b.label));
+	items.extend(type_items);
+	items
+}
+
+fn handle_request(connection: &Connection, workspace: &Workspace, req: Request) -> ServerResult<()> {
+	fn respond<R: serde::Serialize>(connection: &Connection, id: RequestId, result: R) -> ServerResult<()> {
+		connection.sender.send(Message::Response(Response::new_ok(id, result)))?;
+		Ok(())
+	}
+
+	let id = req.id.clone();
+	match req.method.as_str() {
+		request::GotoDefinition::METHOD => respond(connection, id, goto_definition(workspace, serde_json::from_value(req.params)?)),
+		request::References::METHOD => respond(connection, id, find_references(workspace, serde_json::from_value(req.params)?)),
+		request::HoverRequest::METHOD => respond(connection, id, hover(workspace, serde_json::from_value(req.params)?)),
+		request::Completion::METHOD => respond(connection, id, completion(workspace, serde_json::from_value(req.params)?)),
+		_ => {
+			let response = Response::new_err(id, lsp_server::ErrorCode::MethodNotFound as i32, format!("Unknown method {}", req.method));
+			connection.sender.send(Message::Response(response))?;
+			Ok(())
+		},
+	}
+}
+
+fn handle_notification(connection: &Connection, workspace: &mut Workspace, not: Notification) -> ServerResult<()> {
+	match not.method.as_str() {
+		notification::DidOpenTextDocument::METHOD => {
+			let params: lsp_types::DidOpenTextDocumentParams = serde_json::from_value(not.params)?;
+			workspace.update_file(params.text_document.uri, params.text_document.text);
+		},
+
+		notification::DidChangeTextDocument::METHOD => {
+			let params: lsp_types::DidChangeTextDocumentParams = serde_json::from_value(not.params)?;
+			// Changes always contain the whole document because the server only supports full synchronization.
+			if let Some(change) = params.content_changes.into_iter().last() {
+				workspace.update_file(params.text_document.uri, change.text);
+			}
+		},
+
+		notification::DidCloseTextDocument::METHOD => {
+			let params: lsp_types::DidCloseTextDocumentParams = serde_json::from_value(not.params)?;
+			workspace.close_file(&params.text_document.uri);
+		},
+
+		_ => return Ok(()),
+	}
+
+	workspace.analyze();
+	publish_diagnostics(connection, workspace)
+}
+
+#[allow(deprecated)]
+fn add_workspace_roots(workspace: &mut Workspace, params: &InitializeParams) {
+	let roots = match &params.workspace_folders {
+		Some(folders) => folders.iter().map(|folder| folder.uri.clone()).collect(),
+		None => params.root_uri.iter().cloned().collect::<Vec<_>>(),
+	};
+
+	for root in roots {
+		if let Ok(path) = root.to_file_path() {
+			workspace.add_root(&path);
+		}
+	}
+}
+
+fn main() -> ServerResult<()> {
+	let (connection, io_threads) = Connection::stdio();
+
+	let capabilities = serde_json::to_value(capabilities())?;
+	let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+
+	let mut workspace = Workspace::new();
+	add_workspace_roots(&mut workspace, &params);
+	workspace.analyze();
+	publish_diagnostics(&connection, &workspace)?;
+
+	for msg in &connection.receiver {
+		match msg {
+			Message::Request(req) => {
+				if connection.handle_shutdown(&req)? {
+					break;
+				}
+				handle_request(&connection, &workspace, req)?;
+			},
+			Message::Notification(not) => handle_notification(&connection, &mut workspace, not)?,
+			Message::Response(_) => (),
+		}
+	}
+
+	drop(connection);
+	io_threads.join()?;
+	Ok(())
+}
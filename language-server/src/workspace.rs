@@ -0,0 +1,181 @@
+use verilization_compiler::{model, parser, source::SourceFile, symbols, VError, load_models_with_errors};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Location, NumberOrString, Position, Range, Url};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+/// The source files of the workspace and the model loaded from them.
+pub struct Workspace {
+	files: BTreeMap<Url, Arc<SourceFile>>,
+	file_uris: HashMap<String, Url>,
+	model: Option<model::Verilization>,
+	errors: Vec<VError>,
+}
+
+fn file_name(uri: &Url) -> String {
+	match uri.to_file_path() {
+		Ok(path) => path.to_string_lossy().into_owned(),
+		Err(_) => uri.to_string(),
+	}
+}
+
+fn find_source_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+	let entries = match std::fs::read_dir(dir) {
+		Ok(entries) => entries,
+		Err(_) => return,
+	};
+
+	for entry in entries.flatten() {
+		let path = entry.path();
+		let is_hidden = path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.'));
+		if is_hidden {
+			continue;
+		}
+
+		if path.is_dir() {
+			find_source_files(&path, files);
+		}
+		else if path.extension().is_some_and(|ext| ext == "verilization") {
+			files.push(path);
+		}
+	}
+}
+
+/// Converts a position in a file to a byte offset.
+///
+/// Characters in positions are counted in UTF-16 code units.
+pub fn offset_of(file: &SourceFile, position: Position) -> Option<usize> {
+	let content = file.content();
+	let mut line_start = 0;
+	for _ in 0..position.line {
+		line_start += content[line_start..].find('\n')? + 1;
+	}
+
+	let mut units = 0;
+	for (index, ch) in content[line_start..].char_indices() {
+		if units >= position.character || ch == '\n' {
+			return Some(line_start + index);
+		}
+		units += ch.len_utf16() as u32;
+	}
+
+	Some(content.len())
+}
+
+/// Converts a byte offset in a file to a position.
+pub fn position_of(file: &SourceFile, offset: usize) -> Position {
+	let (line, _) = file.line_col(offset);
+	let content = file.content();
+	let line_start = content[..offset].rfind('\n').map_or(0, |index| index + 1);
+	let character = content[line_start..offset].encode_utf16().count();
+	Position::new((line - 1) as u32, character as u32)
+}
+
+/// Gets the range of a span.
+pub fn range_of(span: &model::Span) -> Range {
+	Range::new(position_of(span.file(), span.start()), position_of(span.file(), span.end()))
+}
+
+impl Workspace {
+	pub fn new() -> Self {
+		Workspace {
+			files: BTreeMap::new(),
+			file_uris: HashMap::new(),
+			model: None,
+			errors: Vec::new(),
+		}
+	}
+
+	/// Adds every source file in a directory and its subdirectories.
+	pub fn add_root(&mut self, root: &Path) {
+		let mut paths = Vec::new();
+		find_source_files(root, &mut paths);
+
+		for path in paths {
+			if let (Ok(uri), Ok(content)) = (Url::from_file_path(&path), std::fs::read_to_string(&path)) {
+				self.set_file(uri, content);
+			}
+		}
+	}
+
+	fn set_file(&mut self, uri: Url, content: String) {
+		let name = file_name(&uri);
+		self.file_uris.insert(name.clone(), uri.clone());
+		self.files.insert(uri, SourceFile::new(name, content));
+	}
+
+	/// Uses the text of an open document instead of the file on disk.
+	pub fn update_file(&mut self, uri: Url, content: String) {
+		self.set_file(uri, content);
+	}
+
+	/// Returns to the file on disk when a document is closed.
+	pub fn close_file(&mut self, uri: &Url) {
+		let content = uri.to_file_path().ok().and_then(|path| std::fs::read_to_string(path).ok());
+		match content {
+			Some(content) => self.set_file(uri.clone(), content),
+			None => {
+				self.files.remove(uri);
+			},
+		}
+	}
+
+	/// Loads the model from the current text of every file.
+	pub fn analyze(&mut self) {
+		let models = self.files.values().map(parser::parse_model);
+		let (model, errors) = load_models_with_errors(models);
+		self.model = model;
+		self.errors = errors;
+	}
+
+	/// Gets the diagnostics for each file, including files without any so that old diagnostics are cleared.
+	pub fn diagnostics(&self) -> BTreeMap<Url, Vec<Diagnostic>> {
+		let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = self.files.keys().map(|uri| (uri.clone(), Vec::new())).collect();
+
+		for err in &self.errors {
+			let span = match err.span() {
+				Some(span) => span,
+				None => {
+					eprintln!("{}", err);
+					continue;
+				},
+			};
+
+			if let Some(file_diagnostics) = self.file_uris.get(span.file().name()).and_then(|uri| diagnostics.get_mut(uri)) {
+				file_diagnostics.push(Diagnostic {
+					range: range_of(span),
+					severity: Some(DiagnosticSeverity::ERROR),
+					code: err.code().map(|code| NumberOrString::String(code.to_string())),
+					source: Some(String::from("verilization")),
+					message: err.to_string(),
+					..Diagnostic::default()
+				});
+			}
+		}
+
+		diagnostics
+	}
+
+	pub fn model(&self) -> Option<&model::Verilization> {
+		self.model.as_ref()
+	}
+
+	pub fn file(&self, uri: &Url) -> Option<&Arc<SourceFile>> {
+		self.files.get(uri)
+	}
+
+	pub fn uris(&self) -> impl Iterator<Item = &Url> {
+		self.files.keys()
+	}
+
+	/// Gets the symbols of a file.
+	pub fn symbols(&self, uri: &Url) -> Option<symbols::FileSymbols> {
+		Some(symbols::find_symbols(self.model()?, self.file(uri)?))
+	}
+
+	pub fn location(&self, span: &model::Span) -> Option<Location> {
+		let uri = self.file_uris.get(span.file().name())?;
+		Some(Location::new(uri.clone(), range_of(span)))
+	}
+}
@@ -0,0 +1,55 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Collections;
+
+public final class Array {
+    private Array() {}
+
+    @SafeVarargs
+    public static <A> java.util.List<A> fromSequence(A... values) {
+        return java.util.List.of(values);
+    }
+
+    public static <A, B> Converter<java.util.List<A>, java.util.List<B>> converter(Converter<A, B> elementConverter) {
+        if(elementConverter instanceof IdentityConverter<?>) {
+            @SuppressWarnings("unchecked")
+            var converter = (Converter<java.util.List<A>, java.util.List<B>>)(Object)new IdentityConverter<java.util.List<A>>();
+            return converter;
+        }
+
+        return prev -> {
+            java.util.List<B> list = new ArrayList<>(prev.size());
+            for(A value : prev) {
+                list.add(elementConverter.convert(value));
+            }
+            return Collections.unmodifiableList(list);
+        };
+    }
+
+    // The length is part of the type, so it is not written.
+    public static <A> Codec<java.util.List<A>> codec(Codec<A> elementCodec, int length) {
+        return new Codec<java.util.List<A>>() {
+            @Override
+            public java.util.List<A> read(FormatReader reader) throws IOException {
+                java.util.List<A> list = new ArrayList<>(length);
+                for(int i = 0; i < length; ++i) {
+                    list.add(elementCodec.read(reader));
+                }
+                return Collections.unmodifiableList(list);
+            }
+
+            @Override
+            public void write(FormatWriter writer, java.util.List<A> value) throws IOException {
+                if(value.size() != length) {
+                    throw new IOException("Array has the wrong length");
+                }
+
+                for(A element : value) {
+                    elementCodec.write(writer, element);
+                }
+            }
+        };
+    }
+}
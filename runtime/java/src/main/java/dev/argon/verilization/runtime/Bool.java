@@ -0,0 +1,28 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class Bool {
+    private Bool() {}
+
+    public static boolean fromBoolean(boolean b) {
+        return b;
+    }
+
+    public static final Codec<Boolean> codec = new Codec<Boolean>() {
+        @Override
+        public Boolean read(FormatReader reader) throws IOException {
+            byte b = reader.readByte();
+            switch(b) {
+                case 0: return false;
+                case 1: return true;
+                default: throw new IOException("Invalid value for bool: " + Byte.toUnsignedInt(b));
+            }
+        }
+
+        @Override
+        public void write(FormatWriter writer, Boolean value) throws IOException {
+            writer.writeByte(value ? (byte)1 : (byte)0);
+        }
+    };
+}
@@ -0,0 +1,26 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+import java.math.BigInteger;
+
+public final class Bytes {
+    private Bytes() {}
+
+    public static byte[] fromSequence(byte... values) {
+        return values.clone();
+    }
+
+    public static final Codec<byte[]> codec = new Codec<byte[]>() {
+        @Override
+        public byte[] read(FormatReader reader) throws IOException {
+            BigInteger length = Nat.codec.read(reader);
+            return reader.readBytes(length.intValueExact());
+        }
+
+        @Override
+        public void write(FormatWriter writer, byte[] value) throws IOException {
+            Nat.codec.write(writer, BigInteger.valueOf(value.length));
+            writer.writeBytes(value);
+        }
+    };
+}
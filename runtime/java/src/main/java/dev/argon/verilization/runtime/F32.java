@@ -0,0 +1,23 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class F32 {
+    private F32() {}
+
+    public static float fromFloat(double d) {
+        return (float)d;
+    }
+
+    public static final Codec<Float> codec = new Codec<Float>() {
+        @Override
+        public Float read(FormatReader reader) throws IOException {
+            return Float.intBitsToFloat(reader.readInt());
+        }
+
+        @Override
+        public void write(FormatWriter writer, Float value) throws IOException {
+            writer.writeInt(Float.floatToRawIntBits(value));
+        }
+    };
+}
@@ -0,0 +1,23 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class F64 {
+    private F64() {}
+
+    public static double fromFloat(double d) {
+        return d;
+    }
+
+    public static final Codec<Double> codec = new Codec<Double>() {
+        @Override
+        public Double read(FormatReader reader) throws IOException {
+            return Double.longBitsToDouble(reader.readLong());
+        }
+
+        @Override
+        public void write(FormatWriter writer, Double value) throws IOException {
+            writer.writeLong(Double.doubleToRawLongBits(value));
+        }
+    };
+}
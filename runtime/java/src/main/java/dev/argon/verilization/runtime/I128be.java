@@ -0,0 +1,32 @@
+package dev.argon.verilization.runtime;
+
+import java.math.BigInteger;
+import java.io.IOException;
+
+public final class I128be {
+    private I128be() {}
+
+    public static BigInteger fromInteger(int i) {
+        return BigInteger.valueOf(i);
+    }
+
+    public static BigInteger fromInteger(long l) {
+        return BigInteger.valueOf(l);
+    }
+
+    public static BigInteger fromInteger(BigInteger i) {
+        return i;
+    }
+
+    public static final Codec<BigInteger> codec = new Codec<BigInteger>() {
+        @Override
+        public BigInteger read(FormatReader reader) throws IOException {
+            return U128.readU128(reader, true, true);
+        }
+
+        @Override
+        public void write(FormatWriter writer, BigInteger value) throws IOException {
+            U128.writeU128(writer, value, true);
+        }
+    };
+}
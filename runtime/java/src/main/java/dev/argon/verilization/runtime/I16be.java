@@ -0,0 +1,23 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class I16be {
+    private I16be() {}
+
+    public static short fromInteger(int i) {
+        return (short)i;
+    }
+
+    public static final Codec<Short> codec = new Codec<Short>() {
+        @Override
+        public Short read(FormatReader reader) throws IOException {
+            return Short.reverseBytes(reader.readShort());
+        }
+
+        @Override
+        public void write(FormatWriter writer, Short value) throws IOException {
+            writer.writeShort(Short.reverseBytes(value));
+        }
+    };
+}
@@ -0,0 +1,23 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class I32be {
+    private I32be() {}
+
+    public static int fromInteger(int i) {
+        return i;
+    }
+
+    public static final Codec<Integer> codec = new Codec<Integer>() {
+        @Override
+        public Integer read(FormatReader reader) throws IOException {
+            return Integer.reverseBytes(reader.readInt());
+        }
+
+        @Override
+        public void write(FormatWriter writer, Integer value) throws IOException {
+            writer.writeInt(Integer.reverseBytes(value));
+        }
+    };
+}
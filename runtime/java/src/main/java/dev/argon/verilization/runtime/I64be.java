@@ -0,0 +1,27 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class I64be {
+    private I64be() {}
+
+    public static long fromInteger(int i) {
+        return i;
+    }
+
+    public static long fromInteger(long l) {
+        return l;
+    }
+
+    public static final Codec<Long> codec = new Codec<Long>() {
+        @Override
+        public Long read(FormatReader reader) throws IOException {
+            return Long.reverseBytes(reader.readLong());
+        }
+
+        @Override
+        public void write(FormatWriter writer, Long value) throws IOException {
+            writer.writeLong(Long.reverseBytes(value));
+        }
+    };
+}
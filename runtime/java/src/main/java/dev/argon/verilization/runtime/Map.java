@@ -0,0 +1,63 @@
+package dev.argon.verilization.runtime;
+
+import java.math.BigInteger;
+import java.io.IOException;
+import java.util.Collections;
+import java.util.LinkedHashMap;
+
+public final class Map {
+    private Map() {}
+
+    @SafeVarargs
+    public static <K, V> java.util.Map<K, V> fromMap(java.util.Map.Entry<K, V>... entries) {
+        java.util.Map<K, V> map = new LinkedHashMap<>();
+        for(java.util.Map.Entry<K, V> entry : entries) {
+            map.put(entry.getKey(), entry.getValue());
+        }
+        return Collections.unmodifiableMap(map);
+    }
+
+    public static <K1, K2, V1, V2> Converter<java.util.Map<K1, V1>, java.util.Map<K2, V2>> converter(Converter<K1, K2> keyConverter, Converter<V1, V2> valueConverter) {
+        if(keyConverter instanceof IdentityConverter<?> && valueConverter instanceof IdentityConverter<?>) {
+            @SuppressWarnings("unchecked")
+            var converter = (Converter<java.util.Map<K1, V1>, java.util.Map<K2, V2>>)(Object)new IdentityConverter<java.util.Map<K1, V1>>();
+            return converter;
+        }
+
+        return prev -> {
+            java.util.Map<K2, V2> map = new LinkedHashMap<>();
+            for(java.util.Map.Entry<K1, V1> entry : prev.entrySet()) {
+                map.put(keyConverter.convert(entry.getKey()), valueConverter.convert(entry.getValue()));
+            }
+            return Collections.unmodifiableMap(map);
+        };
+    }
+
+    public static <K, V> Codec<java.util.Map<K, V>> codec(Codec<K> keyCodec, Codec<V> valueCodec) {
+        return new Codec<java.util.Map<K, V>>() {
+            @Override
+            public java.util.Map<K, V> read(FormatReader reader) throws IOException {
+                int length = Nat.codec.read(reader).intValueExact();
+                java.util.Map<K, V> map = new LinkedHashMap<>();
+                for(int i = 0; i < length; ++i) {
+                    K key = keyCodec.read(reader);
+                    V value = valueCodec.read(reader);
+                    if(map.containsKey(key)) {
+                        throw new IOException("Duplicate key in map");
+                    }
+                    map.put(key, value);
+                }
+                return Collections.unmodifiableMap(map);
+            }
+
+            @Override
+            public void write(FormatWriter writer, java.util.Map<K, V> value) throws IOException {
+                Nat.codec.write(writer, BigInteger.valueOf(value.size()));
+                for(java.util.Map.Entry<K, V> entry : value.entrySet()) {
+                    keyCodec.write(writer, entry.getKey());
+                    valueCodec.write(writer, entry.getValue());
+                }
+            }
+        };
+    }
+}
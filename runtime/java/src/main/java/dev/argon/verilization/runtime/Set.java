@@ -0,0 +1,59 @@
+package dev.argon.verilization.runtime;
+
+import java.math.BigInteger;
+import java.io.IOException;
+import java.util.Collections;
+import java.util.LinkedHashSet;
+
+public final class Set {
+    private Set() {}
+
+    @SafeVarargs
+    public static <A> java.util.Set<A> fromSequence(A... values) {
+        java.util.Set<A> set = new LinkedHashSet<>();
+        for(A value : values) {
+            set.add(value);
+        }
+        return Collections.unmodifiableSet(set);
+    }
+
+    public static <A, B> Converter<java.util.Set<A>, java.util.Set<B>> converter(Converter<A, B> elementConverter) {
+        if(elementConverter instanceof IdentityConverter<?>) {
+            @SuppressWarnings("unchecked")
+            var converter = (Converter<java.util.Set<A>, java.util.Set<B>>)(Object)new IdentityConverter<java.util.Set<A>>();
+            return converter;
+        }
+
+        return prev -> {
+            java.util.Set<B> set = new LinkedHashSet<>();
+            for(A value : prev) {
+                set.add(elementConverter.convert(value));
+            }
+            return Collections.unmodifiableSet(set);
+        };
+    }
+
+    public static <A> Codec<java.util.Set<A>> codec(Codec<A> elementCodec) {
+        return new Codec<java.util.Set<A>>() {
+            @Override
+            public java.util.Set<A> read(FormatReader reader) throws IOException {
+                int length = Nat.codec.read(reader).intValueExact();
+                java.util.Set<A> set = new LinkedHashSet<>();
+                for(int i = 0; i < length; ++i) {
+                    if(!set.add(elementCodec.read(reader))) {
+                        throw new IOException("Duplicate element in set");
+                    }
+                }
+                return Collections.unmodifiableSet(set);
+            }
+
+            @Override
+            public void write(FormatWriter writer, java.util.Set<A> value) throws IOException {
+                Nat.codec.write(writer, BigInteger.valueOf(value.size()));
+                for(A element : value) {
+                    elementCodec.write(writer, element);
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,65 @@
+package dev.argon.verilization.runtime;
+
+import java.math.BigInteger;
+import java.io.IOException;
+import java.util.Arrays;
+
+public final class U128 {
+    private U128() {}
+
+    public static BigInteger fromInteger(int i) {
+        return BigInteger.valueOf(i);
+    }
+
+    public static BigInteger fromInteger(long l) {
+        return BigInteger.valueOf(l);
+    }
+
+    public static BigInteger fromInteger(BigInteger i) {
+        return i;
+    }
+
+    static BigInteger readU128(FormatReader reader, boolean signed, boolean bigEndian) throws IOException {
+        byte[] data = reader.readBytes(16);
+        if(!bigEndian) {
+            reverse(data);
+        }
+        return signed ? new BigInteger(data) : new BigInteger(1, data);
+    }
+
+    static void writeU128(FormatWriter writer, BigInteger value, boolean bigEndian) throws IOException {
+        byte[] bytes = value.toByteArray();
+        byte[] data = new byte[16];
+        if(value.signum() < 0) {
+            Arrays.fill(data, (byte)0xFF);
+        }
+
+        int length = Math.min(bytes.length, data.length);
+        System.arraycopy(bytes, bytes.length - length, data, data.length - length, length);
+
+        if(!bigEndian) {
+            reverse(data);
+        }
+        writer.writeBytes(data);
+    }
+
+    private static void reverse(byte[] data) {
+        for(int i = 0; i < data.length / 2; ++i) {
+            byte b = data[i];
+            data[i] = data[data.length - 1 - i];
+            data[data.length - 1 - i] = b;
+        }
+    }
+
+    public static final Codec<BigInteger> codec = new Codec<BigInteger>() {
+        @Override
+        public BigInteger read(FormatReader reader) throws IOException {
+            return readU128(reader, false, false);
+        }
+
+        @Override
+        public void write(FormatWriter writer, BigInteger value) throws IOException {
+            writeU128(writer, value, false);
+        }
+    };
+}
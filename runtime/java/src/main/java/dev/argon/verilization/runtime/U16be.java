@@ -0,0 +1,11 @@
+package dev.argon.verilization.runtime;
+
+public final class U16be {
+    private U16be() {}
+
+    public static short fromInteger(int i) {
+        return (short)i;
+    }
+
+    public static final Codec<Short> codec = I16be.codec;
+}
@@ -0,0 +1,47 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class U24 {
+    private U24() {}
+
+    public static int fromInteger(int i) {
+        return i;
+    }
+
+    static int readU24(FormatReader reader, boolean bigEndian) throws IOException {
+        if(bigEndian) {
+            int high = reader.readByte() & 0xFF;
+            int low = Short.reverseBytes(reader.readShort()) & 0xFFFF;
+            return (high << 16) | low;
+        }
+        else {
+            int low = reader.readShort() & 0xFFFF;
+            int high = reader.readByte() & 0xFF;
+            return (high << 16) | low;
+        }
+    }
+
+    static void writeU24(FormatWriter writer, int value, boolean bigEndian) throws IOException {
+        if(bigEndian) {
+            writer.writeByte((byte)(value >>> 16));
+            writer.writeShort(Short.reverseBytes((short)value));
+        }
+        else {
+            writer.writeShort((short)value);
+            writer.writeByte((byte)(value >>> 16));
+        }
+    }
+
+    public static final Codec<Integer> codec = new Codec<Integer>() {
+        @Override
+        public Integer read(FormatReader reader) throws IOException {
+            return readU24(reader, false);
+        }
+
+        @Override
+        public void write(FormatWriter writer, Integer value) throws IOException {
+            writeU24(writer, value, false);
+        }
+    };
+}
@@ -0,0 +1,23 @@
+package dev.argon.verilization.runtime;
+
+import java.io.IOException;
+
+public final class U24be {
+    private U24be() {}
+
+    public static int fromInteger(int i) {
+        return i;
+    }
+
+    public static final Codec<Integer> codec = new Codec<Integer>() {
+        @Override
+        public Integer read(FormatReader reader) throws IOException {
+            return U24.readU24(reader, true);
+        }
+
+        @Override
+        public void write(FormatWriter writer, Integer value) throws IOException {
+            U24.writeU24(writer, value, true);
+        }
+    };
+}
@@ -0,0 +1,15 @@
+package dev.argon.verilization.runtime;
+
+public final class U32be {
+    private U32be() {}
+
+    public static int fromInteger(int i) {
+        return i;
+    }
+
+    public static int fromInteger(long l) {
+        return (int)l;
+    }
+
+    public static final Codec<Integer> codec = I32be.codec;
+}
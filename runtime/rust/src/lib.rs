@@ -1,16 +1,55 @@
 mod vlq;
+pub mod types;
 
 use num_bigint::{ BigUint, BigInt };
 use num_traits::ToPrimitive;
 
 
+// Lengths are read from untrusted data, so collections only preallocate up to this many elements.
+const MAX_PREALLOCATED_LENGTH: usize = 1024;
+
+fn read_length<R : FormatReader>(reader: &mut R) -> Result<usize, R::Error> {
+    BigUint::read_verilization(reader)?.to_usize().ok_or_else(|| R::invalid_data("Length is too large"))
+}
+
+fn preallocated_length(len: usize) -> usize {
+    len.min(MAX_PREALLOCATED_LENGTH)
+}
+
+
+/// Errors that can report data that does not encode a valid value.
+///
+/// Every error that can be converted from `std::io::Error` implements this trait.
+pub trait InvalidDataError {
+    fn invalid_data(message: &str) -> Self;
+}
+
+impl<E: From<std::io::Error>> InvalidDataError for E {
+    fn invalid_data(message: &str) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, message).into()
+    }
+}
+
 pub trait FormatReader {
-    type Error;
+    type Error: InvalidDataError;
     fn read_u8(&mut self) -> Result<u8, Self::Error>;
     fn read_u16(&mut self) -> Result<u16, Self::Error>;
     fn read_u32(&mut self) -> Result<u32, Self::Error>;
     fn read_u64(&mut self) -> Result<u64, Self::Error>;
     fn read_bytes(&mut self, count: usize) -> Result<Vec<u8>, Self::Error>;
+
+    /// Creates the error that is returned when the data does not encode a valid value.
+    fn invalid_data(message: &str) -> Self::Error {
+        Self::Error::invalid_data(message)
+    }
+
+    fn read_f32(&mut self) -> Result<f32, Self::Error> {
+        Ok(f32::from_bits(self.read_u32()?))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Self::Error> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
 }
 
 pub trait FormatWriter {
@@ -20,6 +59,14 @@ pub trait FormatWriter {
     fn write_u32(&mut self, value: u32) -> Result<(), Self::Error>;
     fn write_u64(&mut self, value: u64) -> Result<(), Self::Error>;
     fn write_bytes(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    fn write_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.write_u32(value.to_bits())
+    }
+
+    fn write_f64(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.write_u64(value.to_bits())
+    }
 }
 
 pub trait VerilizationCodec where Self : Sized {
@@ -27,6 +74,53 @@ pub trait VerilizationCodec where Self : Sized {
     fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error>;
 }
 
+/// Reads and writes the values of a verilization type.
+///
+/// Generated code selects codecs by the verilization type rather than the Rust type,
+/// so that types such as `u32` and `u32be` can share a Rust type while having different encodings.
+/// The codecs of the runtime types are defined in the modules of [`types`].
+pub trait Codec {
+    type Value;
+
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error>;
+    fn write_verilization<W : FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error>;
+
+    /// Reads a sequence of values without a length.
+    ///
+    /// Codecs may override this when the values can be read together.
+    fn read_verilization_vec<R : FormatReader>(reader: &mut R, len: usize) -> Result<Vec<Self::Value>, R::Error> {
+        let mut vec = Vec::with_capacity(preallocated_length(len));
+        for _ in 0..len {
+            vec.push(Self::read_verilization(reader)?);
+        }
+
+        Ok(vec)
+    }
+
+    /// Writes a sequence of values without a length.
+    ///
+    /// Codecs may override this when the values can be written together.
+    fn write_verilization_slice<W : FormatWriter>(values: &[Self::Value], writer: &mut W) -> Result<(), W::Error> {
+        for value in values {
+            Self::write_verilization(value, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the tag of an enum value.
+///
+/// Returns None if the tag is too large to refer to any case.
+pub fn read_discriminator<R : FormatReader>(reader: &mut R) -> Result<Option<u64>, R::Error> {
+    Ok(BigUint::read_verilization(reader)?.to_u64())
+}
+
+/// Writes the tag of an enum value.
+pub fn write_discriminator<W : FormatWriter>(writer: &mut W, tag: u64) -> Result<(), W::Error> {
+    BigUint::from(tag).write_verilization(writer)
+}
+
 
 impl VerilizationCodec for BigUint {
     fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
@@ -128,9 +222,66 @@ impl VerilizationCodec for i64 {
     }
 }
 
+impl VerilizationCodec for u128 {
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
+        let low = reader.read_u64()?;
+        let high = reader.read_u64()?;
+        Ok(((high as u128) << 64) | low as u128)
+    }
+
+    fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write_u64(*self as u64)?;
+        writer.write_u64((*self >> 64) as u64)
+    }
+}
+
+impl VerilizationCodec for i128 {
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
+        Ok(u128::read_verilization(reader)? as i128)
+    }
+
+    fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        (*self as u128).write_verilization(writer)
+    }
+}
+
+impl VerilizationCodec for f32 {
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
+        reader.read_f32()
+    }
+
+    fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write_f32(*self)
+    }
+}
+
+impl VerilizationCodec for f64 {
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
+        reader.read_f64()
+    }
+
+    fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write_f64(*self)
+    }
+}
+
+impl VerilizationCodec for bool {
+    fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
+        match reader.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(R::invalid_data(&format!("Invalid value for bool: {}", b))),
+        }
+    }
+
+    fn write_verilization<W : FormatWriter>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write_u8(u8::from(*self))
+    }
+}
+
 impl VerilizationCodec for String {
     fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
-        let len = BigUint::read_verilization(reader)?.to_usize().unwrap();
+        let len = read_length(reader)?;
         let vec = reader.read_bytes(len)?;
         let s = String::from_utf8(vec).unwrap();
 
@@ -145,8 +296,8 @@ impl VerilizationCodec for String {
 
 impl <T: VerilizationCodec> VerilizationCodec for Vec<T> {
     fn read_verilization<R : FormatReader>(reader: &mut R) -> Result<Self, R::Error> {
-        let len = BigUint::read_verilization(reader)?.to_usize().unwrap();
-        let mut vec = Vec::with_capacity(len);
+        let len = read_length(reader)?;
+        let mut vec = Vec::with_capacity(preallocated_length(len));
         for _ in 0..len {
             vec.push(T::read_verilization(reader)?);
         }
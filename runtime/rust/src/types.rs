@@ -0,0 +1,468 @@
+//! The extern types defined by the runtime library.
+//!
+//! Each module follows the layout that generated Rust code expects for an `extern` type.
+//! Types that share a representation, such as `u32` and `u32be`, share a Rust type and differ only in their `Codec`.
+
+use crate::{FormatReader, FormatWriter, VerilizationCodec};
+use num_bigint::BigUint;
+
+fn write_length<W : FormatWriter>(writer: &mut W, len: usize) -> Result<(), W::Error> {
+    BigUint::from(len).write_verilization(writer)
+}
+
+fn read_u24<R : FormatReader>(reader: &mut R) -> Result<u32, R::Error> {
+    let low = reader.read_u16()?;
+    let high = reader.read_u8()?;
+    Ok(((high as u32) << 16) | low as u32)
+}
+
+fn write_u24<W : FormatWriter>(value: u32, writer: &mut W) -> Result<(), W::Error> {
+    writer.write_u16(value as u16)?;
+    writer.write_u8((value >> 16) as u8)
+}
+
+fn read_u24_be<R : FormatReader>(reader: &mut R) -> Result<u32, R::Error> {
+    let high = reader.read_u8()?;
+    let low = reader.read_u16()?.swap_bytes();
+    Ok(((high as u32) << 16) | low as u32)
+}
+
+fn write_u24_be<W : FormatWriter>(value: u32, writer: &mut W) -> Result<(), W::Error> {
+    writer.write_u8((value >> 16) as u8)?;
+    writer.write_u16((value as u16).swap_bytes())
+}
+
+fn sign_extend_24(value: u32) -> i32 {
+    ((value << 8) as i32) >> 8
+}
+
+fn read_u128_be<R : FormatReader>(reader: &mut R) -> Result<u128, R::Error> {
+    let high = reader.read_u64()?.swap_bytes();
+    let low = reader.read_u64()?.swap_bytes();
+    Ok(((high as u128) << 64) | low as u128)
+}
+
+fn write_u128_be<W : FormatWriter>(value: u128, writer: &mut W) -> Result<(), W::Error> {
+    writer.write_u64(((value >> 64) as u64).swap_bytes())?;
+    writer.write_u64((value as u64).swap_bytes())
+}
+
+// Defines the codec of a type that is encoded by its VerilizationCodec impl.
+macro_rules! native_codec {
+    ($t: ty) => {
+        pub struct Codec;
+
+        impl crate::Codec for Codec {
+            type Value = $t;
+
+            fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+                crate::VerilizationCodec::read_verilization(reader)
+            }
+
+            fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+                crate::VerilizationCodec::write_verilization(value, writer)
+            }
+        }
+    };
+}
+
+pub mod nat {
+    use num_bigint::{BigInt, BigUint};
+
+    pub type Nat = BigUint;
+
+    pub fn from_integer(n: BigInt) -> Nat {
+        n.to_biguint().expect("Integer is out of range for nat.")
+    }
+
+    native_codec!(Nat);
+}
+
+pub mod int {
+    use num_bigint::BigInt;
+
+    pub type Int = BigInt;
+
+    pub fn from_integer(n: BigInt) -> Int {
+        n
+    }
+
+    native_codec!(Int);
+}
+
+// The 24-bit and big endian integers use the native integer of the same signedness that can hold them.
+macro_rules! int_type {
+    ($module: ident, $type_name: ident, $t: ty, $to_prim: ident, $in_range: expr, |$reader: ident| $read: expr, |$value: ident, $writer: ident| $write: expr) => {
+        pub mod $module {
+            use num_bigint::BigInt;
+            use num_traits::ToPrimitive;
+
+            pub type $type_name = $t;
+
+            pub fn from_integer(n: BigInt) -> $type_name {
+                n.$to_prim()
+                    .filter($in_range)
+                    .expect(concat!("Integer is out of range for ", stringify!($module), "."))
+            }
+
+            pub struct Codec;
+
+            impl crate::Codec for Codec {
+                type Value = $type_name;
+
+                fn read_verilization<R : crate::FormatReader>($reader: &mut R) -> Result<Self::Value, R::Error> {
+                    $read
+                }
+
+                fn write_verilization<W : crate::FormatWriter>($value: &Self::Value, $writer: &mut W) -> Result<(), W::Error> {
+                    $write
+                }
+            }
+        }
+    };
+    ($module: ident, $type_name: ident, $t: ty, $to_prim: ident) => {
+        int_type!($module, $type_name, $t, $to_prim, |_| true,
+            |reader| crate::VerilizationCodec::read_verilization(reader),
+            |value, writer| crate::VerilizationCodec::write_verilization(value, writer));
+    };
+}
+
+// Sequences of u8 are read and written as raw bytes.
+pub mod u8 {
+    use num_bigint::BigInt;
+    use num_traits::ToPrimitive;
+
+    pub type U8 = u8;
+
+    pub fn from_integer(n: BigInt) -> U8 {
+        n.to_u8().expect("Integer is out of range for u8.")
+    }
+
+    pub struct Codec;
+
+    impl crate::Codec for Codec {
+        type Value = U8;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            reader.read_u8()
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            writer.write_u8(*value)
+        }
+
+        fn read_verilization_vec<R : crate::FormatReader>(reader: &mut R, len: usize) -> Result<Vec<Self::Value>, R::Error> {
+            reader.read_bytes(len)
+        }
+
+        fn write_verilization_slice<W : crate::FormatWriter>(values: &[Self::Value], writer: &mut W) -> Result<(), W::Error> {
+            writer.write_bytes(values)
+        }
+    }
+}
+
+int_type!(i8, I8, i8, to_i8);
+int_type!(u16, U16, u16, to_u16);
+int_type!(i16, I16, i16, to_i16);
+int_type!(u32, U32, u32, to_u32);
+int_type!(i32, I32, i32, to_i32);
+int_type!(u64, U64, u64, to_u64);
+int_type!(i64, I64, i64, to_i64);
+int_type!(u128, U128, u128, to_u128);
+int_type!(i128, I128, i128, to_i128);
+
+int_type!(u24, U24, u32, to_u32, |n| *n < (1 << 24),
+    |reader| super::read_u24(reader),
+    |value, writer| super::write_u24(*value, writer));
+int_type!(i24, I24, i32, to_i32, |n| (-(1 << 23)..(1 << 23)).contains(n),
+    |reader| Ok(super::sign_extend_24(super::read_u24(reader)?)),
+    |value, writer| super::write_u24(*value as u32, writer));
+
+int_type!(u16be, U16be, u16, to_u16, |_| true,
+    |reader| Ok(reader.read_u16()?.swap_bytes()),
+    |value, writer| writer.write_u16(value.swap_bytes()));
+int_type!(i16be, I16be, i16, to_i16, |_| true,
+    |reader| Ok(reader.read_u16()?.swap_bytes() as i16),
+    |value, writer| writer.write_u16((*value as u16).swap_bytes()));
+int_type!(u24be, U24be, u32, to_u32, |n| *n < (1 << 24),
+    |reader| super::read_u24_be(reader),
+    |value, writer| super::write_u24_be(*value, writer));
+int_type!(i24be, I24be, i32, to_i32, |n| (-(1 << 23)..(1 << 23)).contains(n),
+    |reader| Ok(super::sign_extend_24(super::read_u24_be(reader)?)),
+    |value, writer| super::write_u24_be(*value as u32, writer));
+int_type!(u32be, U32be, u32, to_u32, |_| true,
+    |reader| Ok(reader.read_u32()?.swap_bytes()),
+    |value, writer| writer.write_u32(value.swap_bytes()));
+int_type!(i32be, I32be, i32, to_i32, |_| true,
+    |reader| Ok(reader.read_u32()?.swap_bytes() as i32),
+    |value, writer| writer.write_u32((*value as u32).swap_bytes()));
+int_type!(u64be, U64be, u64, to_u64, |_| true,
+    |reader| Ok(reader.read_u64()?.swap_bytes()),
+    |value, writer| writer.write_u64(value.swap_bytes()));
+int_type!(i64be, I64be, i64, to_i64, |_| true,
+    |reader| Ok(reader.read_u64()?.swap_bytes() as i64),
+    |value, writer| writer.write_u64((*value as u64).swap_bytes()));
+int_type!(u128be, U128be, u128, to_u128, |_| true,
+    |reader| super::read_u128_be(reader),
+    |value, writer| super::write_u128_be(*value, writer));
+int_type!(i128be, I128be, i128, to_i128, |_| true,
+    |reader| Ok(super::read_u128_be(reader)? as i128),
+    |value, writer| super::write_u128_be(*value as u128, writer));
+
+pub mod f32 {
+    pub type F32 = f32;
+
+    pub fn from_float(f: f64) -> F32 {
+        f as f32
+    }
+
+    native_codec!(F32);
+}
+
+pub mod f64 {
+    pub type F64 = f64;
+
+    pub fn from_float(f: f64) -> F64 {
+        f
+    }
+
+    native_codec!(F64);
+}
+
+pub mod bool {
+    pub type Bool = bool;
+
+    pub fn from_boolean(b: bool) -> Bool {
+        b
+    }
+
+    native_codec!(Bool);
+}
+
+pub mod bytes {
+    pub type Bytes = Vec<u8>;
+
+    pub fn from_sequence(seq: Vec<u8>) -> Bytes {
+        seq
+    }
+
+    pub struct Codec;
+
+    impl crate::Codec for Codec {
+        type Value = Bytes;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            let len = crate::read_length(reader)?;
+            reader.read_bytes(len)
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            super::write_length(writer, value.len())?;
+            writer.write_bytes(value)
+        }
+    }
+}
+
+pub mod string {
+    pub type String = std::string::String;
+
+    pub fn from_string(s: &str) -> String {
+        String::from(s)
+    }
+
+    native_codec!(String);
+}
+
+pub mod list {
+    use std::marker::PhantomData;
+
+    pub type List<A> = Vec<A>;
+
+    pub fn from_sequence<A>(seq: Vec<A>) -> List<A> {
+        seq
+    }
+
+    pub fn converter<A, B>(a_conv: impl Fn(A) -> B + Copy) -> impl Fn(List<A>) -> List<B> + Copy {
+        move |prev| prev.into_iter().map(a_conv).collect()
+    }
+
+    pub struct Codec<A>(PhantomData<A>);
+
+    impl <A: crate::Codec> crate::Codec for Codec<A> {
+        type Value = List<A::Value>;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            let len = crate::read_length(reader)?;
+            A::read_verilization_vec(reader, len)
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            super::write_length(writer, value.len())?;
+            A::write_verilization_slice(value, writer)
+        }
+    }
+}
+
+pub mod array {
+    use std::convert::TryInto;
+    use std::marker::PhantomData;
+
+    pub type Array<A, const N: usize> = [A; N];
+
+    pub fn from_sequence<A, const N: usize>(seq: Vec<A>) -> Array<A, N> {
+        match seq.try_into() {
+            Ok(values) => values,
+            Err(_) => panic!("Sequence has the wrong length for array."),
+        }
+    }
+
+    pub fn converter<A, B, const N: usize>(a_conv: impl Fn(A) -> B + Copy) -> impl Fn(Array<A, N>) -> Array<B, N> + Copy {
+        move |prev| prev.map(a_conv)
+    }
+
+    pub struct Codec<A, const N: usize>(PhantomData<A>);
+
+    // The length is part of the type, so it is not written.
+    impl <A: crate::Codec, const N: usize> crate::Codec for Codec<A, N> {
+        type Value = Array<A::Value, N>;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            match A::read_verilization_vec(reader, N)?.try_into() {
+                Ok(values) => Ok(values),
+                Err(_) => unreachable!("Read the wrong number of elements for an array"),
+            }
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            A::write_verilization_slice(value, writer)
+        }
+    }
+}
+
+pub mod option {
+    use std::marker::PhantomData;
+
+    pub type Option<A> = std::option::Option<A>;
+
+    pub fn from_case_some<A>(value: A) -> Option<A> {
+        Some(value)
+    }
+
+    pub fn from_case_none<A>() -> Option<A> {
+        None
+    }
+
+    pub fn converter<A, B>(a_conv: impl Fn(A) -> B + Copy) -> impl Fn(Option<A>) -> Option<B> + Copy {
+        move |prev| prev.map(a_conv)
+    }
+
+    pub struct Codec<A>(PhantomData<A>);
+
+    impl <A: crate::Codec> crate::Codec for Codec<A> {
+        type Value = Option<A::Value>;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            let b = reader.read_u8()?;
+            if b != 0 {
+                Ok(Some(A::read_verilization(reader)?))
+            }
+            else {
+                Ok(None)
+            }
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            if let Some(value) = value {
+                writer.write_u8(1)?;
+                A::write_verilization(value, writer)?;
+            }
+            else {
+                writer.write_u8(0)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod map {
+    use std::hash::Hash;
+    use std::marker::PhantomData;
+
+    pub type Map<K, V> = std::collections::HashMap<K, V>;
+
+    pub fn from_map<K: Hash + Eq, V>(entries: Vec<(K, V)>) -> Map<K, V> {
+        entries.into_iter().collect()
+    }
+
+    pub fn converter<K1, K2: Hash + Eq, V1, V2>(k_conv: impl Fn(K1) -> K2 + Copy, v_conv: impl Fn(V1) -> V2 + Copy) -> impl Fn(Map<K1, V1>) -> Map<K2, V2> + Copy {
+        move |prev| prev.into_iter().map(|(k, v)| (k_conv(k), v_conv(v))).collect()
+    }
+
+    pub struct Codec<K, V>(PhantomData<(K, V)>);
+
+    impl <K: crate::Codec, V: crate::Codec> crate::Codec for Codec<K, V> where K::Value : Hash + Eq {
+        type Value = Map<K::Value, V::Value>;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            let len = crate::read_length(reader)?;
+            let mut map = Map::with_capacity(crate::preallocated_length(len));
+            for _ in 0..len {
+                let key = K::read_verilization(reader)?;
+                let value = V::read_verilization(reader)?;
+                if map.insert(key, value).is_some() {
+                    return Err(R::invalid_data("Duplicate key in map"));
+                }
+            }
+            Ok(map)
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            super::write_length(writer, value.len())?;
+            for (key, value) in value {
+                K::write_verilization(key, writer)?;
+                V::write_verilization(value, writer)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod set {
+    use std::hash::Hash;
+    use std::marker::PhantomData;
+
+    pub type Set<A> = std::collections::HashSet<A>;
+
+    pub fn from_sequence<A: Hash + Eq>(seq: Vec<A>) -> Set<A> {
+        seq.into_iter().collect()
+    }
+
+    pub fn converter<A, B: Hash + Eq>(a_conv: impl Fn(A) -> B + Copy) -> impl Fn(Set<A>) -> Set<B> + Copy {
+        move |prev| prev.into_iter().map(a_conv).collect()
+    }
+
+    pub struct Codec<A>(PhantomData<A>);
+
+    impl <A: crate::Codec> crate::Codec for Codec<A> where A::Value : Hash + Eq {
+        type Value = Set<A::Value>;
+
+        fn read_verilization<R : crate::FormatReader>(reader: &mut R) -> Result<Self::Value, R::Error> {
+            let len = crate::read_length(reader)?;
+            let mut set = Set::with_capacity(crate::preallocated_length(len));
+            for _ in 0..len {
+                if !set.insert(A::read_verilization(reader)?) {
+                    return Err(R::invalid_data("Duplicate element in set"));
+                }
+            }
+            Ok(set)
+        }
+
+        fn write_verilization<W : crate::FormatWriter>(value: &Self::Value, writer: &mut W) -> Result<(), W::Error> {
+            super::write_length(writer, value.len())?;
+            for value in value {
+                A::write_verilization(value, writer)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -82,7 +82,7 @@ pub fn decoede_vlq_signed<R : FormatReader>(reader: &mut R) -> Result<BigInt, R:
     let sign = if signbit { Sign::Minus } else { Sign::Plus };
 
     if signbit {
-        n -= BigUint::one();
+        n += BigUint::one();
     }
 
     Ok(BigInt::from_biguint(sign, n))
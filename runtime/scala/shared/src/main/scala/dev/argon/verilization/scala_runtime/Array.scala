@@ -0,0 +1,30 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.{ZIO, IO, Chunk, ChunkBuilder}
+
+object Array {
+    def fromSequence[A](seq: A*): Chunk[A] = Chunk.fromIterable(seq)
+
+    def converter[A, B](elementConverter: Converter[A, B]): Converter[Chunk[A], Chunk[B]] = elementConverter match {
+        case _: IdentityConverter[_] => new IdentityConverter[Chunk[A]]
+        case _ => new Converter[Chunk[A], Chunk[B]] {
+            override def convert(prev: Chunk[A]): Chunk[B] = prev.map(elementConverter.convert)
+        }
+    }
+
+    // The length is part of the type, so it is not written.
+    def codec[A](elementCodec: Codec[A], length: scala.Int): Codec[Chunk[A]] = new Codec[Chunk[A]] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Chunk[A]] =
+            IO.succeed { ChunkBuilder.make[A](length) }.flatMap { chunkBuilder =>
+                ZIO.foreachDiscard(0 until length) { _ =>
+                    elementCodec.read(reader).flatMap { a => IO.succeed { chunkBuilder += a } }
+                }.flatMap { _ =>
+                    IO.succeed { chunkBuilder.result() }
+                }
+            }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Chunk[A]): ZIO[R, E, Unit] =
+            if(value.size != length) ZIO.dieMessage("Array has the wrong length")
+            else ZIO.foreachDiscard(value) { elem => elementCodec.write(writer, elem) }
+    }
+}
@@ -0,0 +1,19 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.ZIO
+
+object Bool {
+    def fromBoolean(b: Boolean): Bool = b
+
+    val codec: Codec[Boolean] = new Codec[Boolean] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Boolean] =
+            reader.readByte().flatMap { b =>
+                if(b == 0) ZIO.succeed(false)
+                else if(b == 1) ZIO.succeed(true)
+                else ZIO.dieMessage(s"Invalid value for bool: ${b & 0xFF}")
+            }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Boolean): ZIO[R, E, Unit] =
+            writer.writeByte(if(value) 1 else 0)
+    }
+}
@@ -0,0 +1,17 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.{ZIO, Chunk}
+
+object Bytes {
+    def fromSequence(seq: Byte*): Bytes = Chunk.fromIterable(seq)
+
+    val codec: Codec[Chunk[Byte]] = new Codec[Chunk[Byte]] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Chunk[Byte]] =
+            Nat.codec.read(reader).flatMap { length =>
+                reader.readBytes(length.bigInteger.intValueExact)
+            }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Chunk[Byte]): ZIO[R, E, Unit] =
+            Nat.codec.write(writer, value.size) *> writer.writeBytes(value)
+    }
+}
@@ -0,0 +1,27 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.ZIO
+
+object F32 {
+    def fromFloat(d: Double): F32 = d.toFloat
+
+    val codec: Codec[Float] = new Codec[Float] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Float] =
+            reader.readInt().map(java.lang.Float.intBitsToFloat)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Float): ZIO[R, E, Unit] =
+            writer.writeInt(java.lang.Float.floatToRawIntBits(value))
+    }
+}
+
+object F64 {
+    def fromFloat(d: Double): F64 = d
+
+    val codec: Codec[Double] = new Codec[Double] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Double] =
+            reader.readLong().map(java.lang.Double.longBitsToDouble)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Double): ZIO[R, E, Unit] =
+            writer.writeLong(java.lang.Double.doubleToRawLongBits(value))
+    }
+}
@@ -68,6 +68,48 @@ object U16 {
     val codec: Codec[Short] = I16.codec
 }
 
+object U24 {
+    def fromInteger(i: SInt): U24 = i
+
+    private[scala_runtime] def readU24[R, E](reader: FormatReader[R, E], bigEndian: Boolean): ZIO[R, E, SInt] =
+        if(bigEndian)
+            for {
+                high <- reader.readByte()
+                low <- reader.readShort()
+            } yield ((high & 0xFF) << 16) | (java.lang.Short.reverseBytes(low) & 0xFFFF)
+        else
+            for {
+                low <- reader.readShort()
+                high <- reader.readByte()
+            } yield ((high & 0xFF) << 16) | (low & 0xFFFF)
+
+    private[scala_runtime] def writeU24[R, E](writer: FormatWriter[R, E], value: SInt, bigEndian: Boolean): ZIO[R, E, Unit] =
+        if(bigEndian)
+            writer.writeByte((value >>> 16).toByte) *> writer.writeShort(java.lang.Short.reverseBytes(value.toShort))
+        else
+            writer.writeShort(value.toShort) *> writer.writeByte((value >>> 16).toByte)
+
+    val codec: Codec[SInt] = new Codec[SInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SInt] =
+            readU24(reader, false)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SInt): ZIO[R, E, Unit] =
+            writeU24(writer, value, false)
+    }
+}
+
+object I24 {
+    def fromInteger(i: SInt): I24 = i
+
+    val codec: Codec[SInt] = new Codec[SInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SInt] =
+            U24.readU24(reader, false).map { value => (value << 8) >> 8 }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SInt): ZIO[R, E, Unit] =
+            U24.writeU24(writer, value, false)
+    }
+}
+
 object I32 {
     def fromInteger(i: SInt): I32 = i
 
@@ -108,3 +150,153 @@ object U64 {
     val codec: Codec[Long] = I64.codec
 }
 
+object U128 {
+    def fromInteger(i: SInt): U128 = i
+    def fromInteger(l: Long): U128 = l
+    def fromInteger(i: BigInt): U128 = i
+
+    private[scala_runtime] def readU128[R, E](reader: FormatReader[R, E], signed: Boolean, bigEndian: Boolean): ZIO[R, E, BigInt] =
+        reader.readBytes(16).map { data =>
+            val bytes = (if(bigEndian) data else data.reverse).toArray
+            if(signed) BigInt(bytes) else BigInt(1, bytes)
+        }
+
+    private[scala_runtime] def writeU128[R, E](writer: FormatWriter[R, E], value: BigInt, bigEndian: Boolean): ZIO[R, E, Unit] = {
+        val bytes = value.toByteArray.takeRight(16)
+        val padding = Chunk.fill(16 - bytes.length)(if(value < 0) 0xFF.toByte else 0.toByte)
+        val data = padding ++ Chunk.fromArray(bytes)
+        writer.writeBytes(if(bigEndian) data else data.reverse)
+    }
+
+    val codec: Codec[BigInt] = new Codec[BigInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, BigInt] =
+            readU128(reader, false, false)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: BigInt): ZIO[R, E, Unit] =
+            writeU128(writer, value, false)
+    }
+}
+
+object I128 {
+    def fromInteger(i: SInt): I128 = i
+    def fromInteger(l: Long): I128 = l
+    def fromInteger(i: BigInt): I128 = i
+
+    val codec: Codec[BigInt] = new Codec[BigInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, BigInt] =
+            U128.readU128(reader, true, false)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: BigInt): ZIO[R, E, Unit] =
+            U128.writeU128(writer, value, false)
+    }
+}
+
+object I16be {
+    def fromInteger(i: SInt): I16be = i.toShort
+
+    val codec: Codec[Short] = new Codec[Short] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Short] =
+            reader.readShort().map(java.lang.Short.reverseBytes)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Short): ZIO[R, E, Unit] =
+            writer.writeShort(java.lang.Short.reverseBytes(value))
+    }
+}
+
+object U16be {
+    def fromInteger(i: SInt): U16be = i.toShort
+
+    val codec: Codec[Short] = I16be.codec
+}
+
+object U24be {
+    def fromInteger(i: SInt): U24be = i
+
+    val codec: Codec[SInt] = new Codec[SInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SInt] =
+            U24.readU24(reader, true)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SInt): ZIO[R, E, Unit] =
+            U24.writeU24(writer, value, true)
+    }
+}
+
+object I24be {
+    def fromInteger(i: SInt): I24be = i
+
+    val codec: Codec[SInt] = new Codec[SInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SInt] =
+            U24.readU24(reader, true).map { value => (value << 8) >> 8 }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SInt): ZIO[R, E, Unit] =
+            U24.writeU24(writer, value, true)
+    }
+}
+
+object I32be {
+    def fromInteger(i: SInt): I32be = i
+
+    val codec: Codec[SInt] = new Codec[SInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SInt] =
+            reader.readInt().map(java.lang.Integer.reverseBytes)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SInt): ZIO[R, E, Unit] =
+            writer.writeInt(java.lang.Integer.reverseBytes(value))
+    }
+}
+
+object U32be {
+    def fromInteger(i: SInt): U32be = i
+    def fromInteger(l: Long): U32be = l.toInt
+
+    val codec: Codec[SInt] = I32be.codec
+}
+
+object I64be {
+    def fromInteger(i: SInt): I64be = i
+    def fromInteger(l: Long): I64be = l
+
+    val codec: Codec[Long] = new Codec[Long] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, Long] =
+            reader.readLong().map(java.lang.Long.reverseBytes)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: Long): ZIO[R, E, Unit] =
+            writer.writeLong(java.lang.Long.reverseBytes(value))
+    }
+}
+
+object U64be {
+    def fromInteger(i: SInt): U64be = i
+    def fromInteger(l: Long): U64be = l
+    def fromInteger(i: BigInt): U64be = i.toLong
+
+    val codec: Codec[Long] = I64be.codec
+}
+
+object I128be {
+    def fromInteger(i: SInt): I128be = i
+    def fromInteger(l: Long): I128be = l
+    def fromInteger(i: BigInt): I128be = i
+
+    val codec: Codec[BigInt] = new Codec[BigInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, BigInt] =
+            U128.readU128(reader, true, true)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: BigInt): ZIO[R, E, Unit] =
+            U128.writeU128(writer, value, true)
+    }
+}
+
+object U128be {
+    def fromInteger(i: SInt): U128be = i
+    def fromInteger(l: Long): U128be = l
+    def fromInteger(i: BigInt): U128be = i
+
+    val codec: Codec[BigInt] = new Codec[BigInt] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, BigInt] =
+            U128.readU128(reader, false, true)
+
+        override def write[R, E](writer: FormatWriter[R, E], value: BigInt): ZIO[R, E, Unit] =
+            U128.writeU128(writer, value, true)
+    }
+}
@@ -0,0 +1,31 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.ZIO
+import scala.collection.immutable.{Map => SMap}
+
+object Map {
+    def fromMap[K, V](entries: (K, V)*): Map[K, V] = SMap(entries: _*)
+
+    def converter[K1, K2, V1, V2](keyConverter: Converter[K1, K2], valueConverter: Converter[V1, V2]): Converter[SMap[K1, V1], SMap[K2, V2]] = (keyConverter, valueConverter) match {
+        case (_: IdentityConverter[_], _: IdentityConverter[_]) => new IdentityConverter[SMap[K1, V1]].asInstanceOf[Converter[SMap[K1, V1], SMap[K2, V2]]]
+        case _ => new Converter[SMap[K1, V1], SMap[K2, V2]] {
+            override def convert(prev: SMap[K1, V1]): SMap[K2, V2] =
+                prev.map { case (k, v) => keyConverter.convert(k) -> valueConverter.convert(v) }
+        }
+    }
+
+    def codec[K, V](keyCodec: Codec[K], valueCodec: Codec[V]): Codec[SMap[K, V]] = new Codec[SMap[K, V]] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SMap[K, V]] =
+            Nat.codec.read(reader).flatMap { length =>
+                ZIO.foldLeft(0 until length.bigInteger.intValueExact)(SMap.empty[K, V]) { (map, _) =>
+                    keyCodec.read(reader).zip(valueCodec.read(reader)).flatMap { case (key, value) =>
+                        if(map.contains(key)) ZIO.dieMessage("Duplicate key in map")
+                        else ZIO.succeed(map.updated(key, value))
+                    }
+                }
+            }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SMap[K, V]): ZIO[R, E, Unit] =
+            Nat.codec.write(writer, value.size) *> ZIO.foreachDiscard(value) { case (k, v) => keyCodec.write(writer, k) *> valueCodec.write(writer, v) }
+    }
+}
@@ -0,0 +1,30 @@
+package dev.argon.verilization.scala_runtime
+
+import zio.ZIO
+import scala.collection.immutable.{Set => SSet}
+
+object Set {
+    def fromSequence[A](seq: A*): Set[A] = SSet(seq: _*)
+
+    def converter[A, B](elementConverter: Converter[A, B]): Converter[SSet[A], SSet[B]] = elementConverter match {
+        case _: IdentityConverter[_] => new IdentityConverter[SSet[A]].asInstanceOf[Converter[SSet[A], SSet[B]]]
+        case _ => new Converter[SSet[A], SSet[B]] {
+            override def convert(prev: SSet[A]): SSet[B] = prev.map(elementConverter.convert)
+        }
+    }
+
+    def codec[A](elementCodec: Codec[A]): Codec[SSet[A]] = new Codec[SSet[A]] {
+        override def read[R, E](reader: FormatReader[R, E]): ZIO[R, E, SSet[A]] =
+            Nat.codec.read(reader).flatMap { length =>
+                ZIO.foldLeft(0 until length.bigInteger.intValueExact)(SSet.empty[A]) { (set, _) =>
+                    elementCodec.read(reader).flatMap { value =>
+                        if(set.contains(value)) ZIO.dieMessage("Duplicate element in set")
+                        else ZIO.succeed(set + value)
+                    }
+                }
+            }
+
+        override def write[R, E](writer: FormatWriter[R, E], value: SSet[A]): ZIO[R, E, Unit] =
+            Nat.codec.write(writer, value.size) *> ZIO.foreachDiscard(value) { elem => elementCodec.write(writer, elem) }
+    }
+}
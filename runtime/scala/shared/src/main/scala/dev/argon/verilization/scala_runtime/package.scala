@@ -7,11 +7,32 @@ package object scala_runtime {
     type I8 = scala.Byte
     type U16 = scala.Short
     type I16 = scala.Short
+    type U24 = scala.Int
+    type I24 = scala.Int
     type U32 = scala.Int
     type I32 = scala.Int
     type U64 = scala.Long
     type I64 = scala.Long
+    type U128 = scala.math.BigInt
+    type I128 = scala.math.BigInt
+    type U16be = scala.Short
+    type I16be = scala.Short
+    type U24be = scala.Int
+    type I24be = scala.Int
+    type U32be = scala.Int
+    type I32be = scala.Int
+    type U64be = scala.Long
+    type I64be = scala.Long
+    type U128be = scala.math.BigInt
+    type I128be = scala.math.BigInt
+    type F32 = scala.Float
+    type F64 = scala.Double
+    type Bool = scala.Boolean
+    type Bytes = zio.Chunk[scala.Byte]
     type String = scala.Predef.String
     type List[A] = zio.Chunk[A]
     type Option[A] = scala.Option[A]
+    type Map[K, V] = scala.collection.immutable.Map[K, V]
+    type Set[A] = scala.collection.immutable.Set[A]
+    type Array[A] = zio.Chunk[A]
 }
\ No newline at end of file
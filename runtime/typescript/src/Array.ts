@@ -0,0 +1,44 @@
+import { Converter, IdentityConverter } from "./Converter.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { Codec } from "./Codec.js";
+
+export type Array<A> = readonly A[];
+
+export function fromSequence<A>(...values: A[]): Array<A> {
+    return values;
+}
+
+export function converter<A, B>(elemConv: Converter<A, B>): Converter<Array<A>, Array<B>> {
+    if(elemConv instanceof IdentityConverter) {
+        return Converter.identity<Array<A>>() as unknown as Converter<Array<A>, Array<B>>;
+    }
+
+    return {
+        convert(prev: Array<A>): Array<B> {
+            return prev.map(a => elemConv.convert(a));
+        },
+    };
+}
+
+// The length is part of the type, so it is not written.
+export function codec<A>(elemCodec: Codec<A>, length: bigint): Codec<Array<A>> {
+    return {
+        async read(reader: FormatReader): Promise<Array<A>> {
+            const result: A[] = [];
+            for(let i = 0n; i < length; ++i) {
+                result.push(await elemCodec.read(reader));
+            }
+            return result;
+        },
+
+        async write(writer: FormatWriter, value: Array<A>): Promise<void> {
+            if(BigInt(value.length) !== length) {
+                throw new Error("Array has the wrong length");
+            }
+
+            for(const a of value) {
+                await elemCodec.write(writer, a);
+            }
+        },
+    };
+}
@@ -0,0 +1,23 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type Bool = boolean;
+
+export const codec: Codec<Bool> = {
+    async read(reader: FormatReader): Promise<Bool> {
+        const b = await reader.readU8();
+        switch(b) {
+            case 0: return false;
+            case 1: return true;
+            default: throw new Error("Invalid value for bool: " + b);
+        }
+    },
+
+    write(writer: FormatWriter, value: Bool): Promise<void> {
+        return writer.writeU8(value ? 1 : 0);
+    },
+};
+
+export function fromBoolean(b: boolean): Bool {
+    return b;
+}
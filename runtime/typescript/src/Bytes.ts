@@ -0,0 +1,25 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { codec as natCodec } from "./Nat.js";
+
+export type Bytes = Uint8Array;
+
+export const codec: Codec<Bytes> = {
+    async read(reader: FormatReader): Promise<Bytes> {
+        const length = await natCodec.read(reader);
+        if(length > BigInt(Number.MAX_SAFE_INTEGER)) {
+            throw new Error("Length of array too large");
+        }
+
+        return await reader.readBytes(Number(length));
+    },
+
+    async write(writer: FormatWriter, value: Bytes): Promise<void> {
+        await natCodec.write(writer, BigInt(value.length));
+        await writer.writeBytes(value);
+    },
+};
+
+export function fromSequence(...values: number[]): Bytes {
+    return new Uint8Array(values);
+}
@@ -0,0 +1,22 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type F32 = number;
+
+export const codec: Codec<F32> = {
+    async read(reader: FormatReader): Promise<F32> {
+        const view = new DataView(new ArrayBuffer(4));
+        view.setUint32(0, await reader.readU32(), true);
+        return view.getFloat32(0, true);
+    },
+
+    write(writer: FormatWriter, value: F32): Promise<void> {
+        const view = new DataView(new ArrayBuffer(4));
+        view.setFloat32(0, value, true);
+        return writer.writeU32(view.getUint32(0, true));
+    },
+};
+
+export function fromFloat(f: number): F32 {
+    return Math.fround(f);
+}
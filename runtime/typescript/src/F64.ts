@@ -0,0 +1,22 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type F64 = number;
+
+export const codec: Codec<F64> = {
+    async read(reader: FormatReader): Promise<F64> {
+        const view = new DataView(new ArrayBuffer(8));
+        view.setBigUint64(0, await reader.readU64(), true);
+        return view.getFloat64(0, true);
+    },
+
+    write(writer: FormatWriter, value: F64): Promise<void> {
+        const view = new DataView(new ArrayBuffer(8));
+        view.setFloat64(0, value, true);
+        return writer.writeU64(view.getBigUint64(0, true));
+    },
+};
+
+export function fromFloat(f: number): F64 {
+    return f;
+}
@@ -0,0 +1,19 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { codec as u128Codec } from "./U128.js";
+
+export type I128 = bigint;
+
+export const codec: Codec<I128> = {
+    async read(reader: FormatReader): Promise<I128> {
+        return BigInt.asIntN(128, await u128Codec.read(reader));
+    },
+
+    write(writer: FormatWriter, value: I128): Promise<void> {
+        return u128Codec.write(writer, BigInt.asUintN(128, value));
+    },
+};
+
+export function fromInteger(n: bigint): I128 {
+    return BigInt.asIntN(128, n);
+}
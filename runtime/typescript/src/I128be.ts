@@ -0,0 +1,19 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { codec as u128beCodec } from "./U128be.js";
+
+export type I128be = bigint;
+
+export const codec: Codec<I128be> = {
+    async read(reader: FormatReader): Promise<I128be> {
+        return BigInt.asIntN(128, await u128beCodec.read(reader));
+    },
+
+    write(writer: FormatWriter, value: I128be): Promise<void> {
+        return u128beCodec.write(writer, BigInt.asUintN(128, value));
+    },
+};
+
+export function fromInteger(n: bigint): I128be {
+    return BigInt.asIntN(128, n);
+}
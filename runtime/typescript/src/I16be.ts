@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type I16be = number;
+
+export const codec: Codec<I16be> = {
+    async read(reader: FormatReader): Promise<I16be> {
+        const data = await reader.readBytes(2);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getInt16(0, false);
+    },
+
+    write(writer: FormatWriter, value: I16be): Promise<void> {
+        const data = new Uint8Array(2);
+        new DataView(data.buffer).setInt16(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): I16be {
+    return (Number(n) << 16) >> 16;
+}
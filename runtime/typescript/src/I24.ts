@@ -0,0 +1,19 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { codec as u24Codec } from "./U24.js";
+
+export type I24 = number;
+
+export const codec: Codec<I24> = {
+    async read(reader: FormatReader): Promise<I24> {
+        return (await u24Codec.read(reader) << 8) >> 8;
+    },
+
+    write(writer: FormatWriter, value: I24): Promise<void> {
+        return u24Codec.write(writer, value & 0xFFFFFF);
+    },
+};
+
+export function fromInteger(n: bigint): I24 {
+    return (Number(n) << 8) >> 8;
+}
@@ -0,0 +1,19 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { codec as u24beCodec } from "./U24be.js";
+
+export type I24be = number;
+
+export const codec: Codec<I24be> = {
+    async read(reader: FormatReader): Promise<I24be> {
+        return (await u24beCodec.read(reader) << 8) >> 8;
+    },
+
+    write(writer: FormatWriter, value: I24be): Promise<void> {
+        return u24beCodec.write(writer, value & 0xFFFFFF);
+    },
+};
+
+export function fromInteger(n: bigint): I24be {
+    return (Number(n) << 8) >> 8;
+}
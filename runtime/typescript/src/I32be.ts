@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type I32be = number;
+
+export const codec: Codec<I32be> = {
+    async read(reader: FormatReader): Promise<I32be> {
+        const data = await reader.readBytes(4);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getInt32(0, false);
+    },
+
+    write(writer: FormatWriter, value: I32be): Promise<void> {
+        const data = new Uint8Array(4);
+        new DataView(data.buffer).setInt32(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): I32be {
+    return Number(n) | 0;
+}
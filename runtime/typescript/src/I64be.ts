@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type I64be = bigint;
+
+export const codec: Codec<I64be> = {
+    async read(reader: FormatReader): Promise<I64be> {
+        const data = await reader.readBytes(8);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getBigInt64(0, false);
+    },
+
+    write(writer: FormatWriter, value: I64be): Promise<void> {
+        const data = new Uint8Array(8);
+        new DataView(data.buffer).setBigInt64(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): I64be {
+    return BigInt.asIntN(64, n);
+}
@@ -0,0 +1,52 @@
+import { Converter, IdentityConverter } from "./Converter.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { Codec } from "./Codec.js";
+import { codec as natCodec } from "./Nat.js";
+
+export type Map<K, V> = ReadonlyMap<K, V>;
+
+export function fromMap<K, V>(...entries: [K, V][]): Map<K, V> {
+    return new globalThis.Map(entries);
+}
+
+export function converter<K1, K2, V1, V2>(keyConv: Converter<K1, K2>, valueConv: Converter<V1, V2>): Converter<Map<K1, V1>, Map<K2, V2>> {
+    if(keyConv instanceof IdentityConverter && valueConv instanceof IdentityConverter) {
+        return Converter.identity<Map<K1, V1>>() as unknown as Converter<Map<K1, V1>, Map<K2, V2>>;
+    }
+
+    return {
+        convert(prev: Map<K1, V1>): Map<K2, V2> {
+            const result = new globalThis.Map<K2, V2>();
+            for(const [key, value] of prev) {
+                result.set(keyConv.convert(key), valueConv.convert(value));
+            }
+            return result;
+        },
+    };
+}
+
+export function codec<K, V>(keyCodec: Codec<K>, valueCodec: Codec<V>): Codec<Map<K, V>> {
+    return {
+        async read(reader: FormatReader): Promise<Map<K, V>> {
+            const length = await natCodec.read(reader);
+            const result = new globalThis.Map<K, V>();
+            for(let i = 0n; i < length; ++i) {
+                const key = await keyCodec.read(reader);
+                const value = await valueCodec.read(reader);
+                if(result.has(key)) {
+                    throw new Error("Duplicate key in map");
+                }
+                result.set(key, value);
+            }
+            return result;
+        },
+
+        async write(writer: FormatWriter, value: Map<K, V>): Promise<void> {
+            await natCodec.write(writer, BigInt(value.size));
+            for(const [k, v] of value) {
+                await keyCodec.write(writer, k);
+                await valueCodec.write(writer, v);
+            }
+        },
+    };
+}
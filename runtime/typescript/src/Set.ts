@@ -0,0 +1,50 @@
+import { Converter, IdentityConverter } from "./Converter.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+import { Codec } from "./Codec.js";
+import { codec as natCodec } from "./Nat.js";
+
+export type Set<A> = ReadonlySet<A>;
+
+export function fromSequence<A>(...values: A[]): Set<A> {
+    return new globalThis.Set(values);
+}
+
+export function converter<A, B>(elemConv: Converter<A, B>): Converter<Set<A>, Set<B>> {
+    if(elemConv instanceof IdentityConverter) {
+        return Converter.identity<Set<A>>() as unknown as Converter<Set<A>, Set<B>>;
+    }
+
+    return {
+        convert(prev: Set<A>): Set<B> {
+            const result = new globalThis.Set<B>();
+            for(const a of prev) {
+                result.add(elemConv.convert(a));
+            }
+            return result;
+        },
+    };
+}
+
+export function codec<A>(elemCodec: Codec<A>): Codec<Set<A>> {
+    return {
+        async read(reader: FormatReader): Promise<Set<A>> {
+            const length = await natCodec.read(reader);
+            const result = new globalThis.Set<A>();
+            for(let i = 0n; i < length; ++i) {
+                const value = await elemCodec.read(reader);
+                if(result.has(value)) {
+                    throw new Error("Duplicate element in set");
+                }
+                result.add(value);
+            }
+            return result;
+        },
+
+        async write(writer: FormatWriter, value: Set<A>): Promise<void> {
+            await natCodec.write(writer, BigInt(value.size));
+            for(const a of value) {
+                await elemCodec.write(writer, a);
+            }
+        },
+    };
+}
@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U128 = bigint;
+
+export const codec: Codec<U128> = {
+    async read(reader: FormatReader): Promise<U128> {
+        const low = await reader.readU64();
+        const high = await reader.readU64();
+        return (high << 64n) | low;
+    },
+
+    async write(writer: FormatWriter, value: U128): Promise<void> {
+        await writer.writeU64(BigInt.asUintN(64, value));
+        await writer.writeU64(BigInt.asUintN(64, value >> 64n));
+    },
+};
+
+export function fromInteger(n: bigint): U128 {
+    return BigInt.asUintN(128, n);
+}
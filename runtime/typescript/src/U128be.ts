@@ -0,0 +1,24 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U128be = bigint;
+
+export const codec: Codec<U128be> = {
+    async read(reader: FormatReader): Promise<U128be> {
+        const data = await reader.readBytes(16);
+        const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+        return (view.getBigUint64(0, false) << 64n) | view.getBigUint64(8, false);
+    },
+
+    write(writer: FormatWriter, value: U128be): Promise<void> {
+        const data = new Uint8Array(16);
+        const view = new DataView(data.buffer);
+        view.setBigUint64(0, BigInt.asUintN(64, value >> 64n), false);
+        view.setBigUint64(8, BigInt.asUintN(64, value), false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): U128be {
+    return BigInt.asUintN(128, n);
+}
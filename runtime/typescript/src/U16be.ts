@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U16be = number;
+
+export const codec: Codec<U16be> = {
+    async read(reader: FormatReader): Promise<U16be> {
+        const data = await reader.readBytes(2);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getUint16(0, false);
+    },
+
+    write(writer: FormatWriter, value: U16be): Promise<void> {
+        const data = new Uint8Array(2);
+        new DataView(data.buffer).setUint16(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): U16be {
+    return Number(n) & 0xFFFF;
+}
@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U24 = number;
+
+export const codec: Codec<U24> = {
+    async read(reader: FormatReader): Promise<U24> {
+        const low = await reader.readU16();
+        const high = await reader.readU8();
+        return (high << 16) | low;
+    },
+
+    async write(writer: FormatWriter, value: U24): Promise<void> {
+        await writer.writeU16(value & 0xFFFF);
+        await writer.writeU8((value >>> 16) & 0xFF);
+    },
+};
+
+export function fromInteger(n: bigint): U24 {
+    return Number(n) & 0xFFFFFF;
+}
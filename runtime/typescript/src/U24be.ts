@@ -0,0 +1,19 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U24be = number;
+
+export const codec: Codec<U24be> = {
+    async read(reader: FormatReader): Promise<U24be> {
+        const data = await reader.readBytes(3);
+        return (data[0] << 16) | (data[1] << 8) | data[2];
+    },
+
+    write(writer: FormatWriter, value: U24be): Promise<void> {
+        return writer.writeBytes(new Uint8Array([ (value >>> 16) & 0xFF, (value >>> 8) & 0xFF, value & 0xFF ]));
+    },
+};
+
+export function fromInteger(n: bigint): U24be {
+    return Number(n) & 0xFFFFFF;
+}
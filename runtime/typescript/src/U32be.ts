@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U32be = number;
+
+export const codec: Codec<U32be> = {
+    async read(reader: FormatReader): Promise<U32be> {
+        const data = await reader.readBytes(4);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getUint32(0, false);
+    },
+
+    write(writer: FormatWriter, value: U32be): Promise<void> {
+        const data = new Uint8Array(4);
+        new DataView(data.buffer).setUint32(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): U32be {
+    return Number(n) >>> 0;
+}
@@ -0,0 +1,21 @@
+import { Codec } from "./Codec.js";
+import { FormatReader, FormatWriter } from "./FormatIO.js";
+
+export type U64be = bigint;
+
+export const codec: Codec<U64be> = {
+    async read(reader: FormatReader): Promise<U64be> {
+        const data = await reader.readBytes(8);
+        return new DataView(data.buffer, data.byteOffset, data.byteLength).getBigUint64(0, false);
+    },
+
+    write(writer: FormatWriter, value: U64be): Promise<void> {
+        const data = new Uint8Array(8);
+        new DataView(data.buffer).setBigUint64(0, value, false);
+        return writer.writeBytes(data);
+    },
+};
+
+export function fromInteger(n: bigint): U64be {
+    return BigInt.asUintN(64, n);
+}
@@ -14,6 +14,15 @@ final class Referenced_Conversions {
             };
         }
     };
+
+    static final Converter<Referenced.V2, Referenced.V1> v2ToV1 = new Converter<Referenced.V2, Referenced.V1>() {
+        @Override
+        public Referenced.V1 convert(Referenced.V2 prev) {
+            return switch(prev) {
+                case Referenced.V2.X prev2 -> new Referenced.V1.X((int)prev2.x());
+            };
+        }
+    };
     
 
 }
@@ -13,5 +13,12 @@ public class Upgrade_Conversions {
             return new Upgrade.V4(prev.n());
         }
     };
+
+    static final Converter<Upgrade.V4, Upgrade.V3> v4ToV3 = new Converter<Upgrade.V4, Upgrade.V3>() {
+        @Override
+        public Upgrade.V3 convert(Upgrade.V4 prev) {
+            return new Upgrade.V3((int)prev.n());
+        }
+    };
     
 }
@@ -13,4 +13,13 @@ public final class Pair_Conversions {
             }
         };
     }
+
+    static <A_1, A_2, B_1, B_2> Converter<Pair.V4<A_1, B_1>, Pair.V3<A_2, B_2>> v4ToV3(Converter<A_1, A_2> A_conv, Converter<B_1, B_2> B_conv) {
+        return new Converter<Pair.V4<A_1, B_1>, Pair.V3<A_2, B_2>>() {
+            @Override
+            public Pair.V3<A_2, B_2> convert(Pair.V4<A_1, B_1> prev) {
+                return new Pair.V3<A_2, B_2>(A_conv.convert(prev.left()), B_conv.convert(prev.right()));
+            }
+        };
+    }
 }
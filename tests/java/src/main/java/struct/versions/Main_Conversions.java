@@ -4,18 +4,12 @@ import dev.argon.verilization.runtime.Converter;
 
 final class Main_Conversions {
     private Main_Conversions() {}
-    
-    static final Converter<Main.V3, Main.V4> v3ToV4 = new Converter<Main.V3, Main.V4>() {
+
+
+    static final Converter<Main.V4, Main.V3> v4ToV3 = new Converter<Main.V4, Main.V3>() {
         @Override
-        public Main.V4 convert(Main.V3 prev) {
-            return new Main.V4(
-                prev.n(),
-                prev.m(),
-                Referenced.V4.fromV3.convert(prev.r()),
-                new Addition.V4(
-                    5
-                )
-            );
+        public Main.V3 convert(Main.V4 prev) {
+            return new Main.V3(prev.n(), prev.m(), Referenced.V4.toV3.convert(prev.r()));
         }
     };
 
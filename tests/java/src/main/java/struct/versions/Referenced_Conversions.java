@@ -13,4 +13,11 @@ final class Referenced_Conversions {
         }
     };
 
+    static final Converter<Referenced.V2, Referenced.V1> v2ToV1 = new Converter<Referenced.V2, Referenced.V1>() {
+        @Override
+        public Referenced.V1 convert(Referenced.V2 prev) {
+            return new Referenced.V1((int)prev.x());
+        }
+    };
+
 }
@@ -0,0 +1,16 @@
+package struct.versions;
+
+import dev.argon.verilization.runtime.Converter;
+
+final class Renamed_Conversions {
+    private Renamed_Conversions() {}
+
+
+    static final Converter<Renamed.V3, Renamed.V2> v3ToV2 = new Converter<Renamed.V3, Renamed.V2>() {
+        @Override
+        public Renamed.V2 convert(Renamed.V3 prev) {
+            return new Renamed.V2(prev.newName(), 0, Referenced.V3.toV2.convert(prev.kept()));
+        }
+    };
+
+}
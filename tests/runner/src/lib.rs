@@ -3,6 +3,7 @@ pub mod test_lang;
 mod ts_test_gen;
 mod java_test_gen;
 mod scala_test_gen;
+mod rust_test_gen;
 pub mod test_cases;
 pub mod output_comparison;
 pub mod value_generator;
@@ -0,0 +1,136 @@
+use verilization_compiler::{lang, model, VError};
+use verilization_lang_rust::{RustGenerator, RustOptions};
+use model::{Verilization, Named};
+use lang::generator::*;
+
+use crate::memory_format::MemoryFormatWriter;
+use crate::test_lang::{TestLanguage, TestGenerator};
+
+use num_bigint::BigUint;
+use std::fs::File;
+use std::io::Write;
+use rand::Rng;
+use crate::value_generator::{generate_random_value, write_constant_value};
+
+struct RustTestCaseGen<'a, F, R> {
+    file: &'a mut F,
+    options: &'a RustOptions,
+    random: &'a mut R,
+    model: &'a Verilization,
+	type_def: Named<'a, model::VersionedTypeDefinitionData>,
+	scope: model::Scope<'a>,
+}
+
+
+impl <'a, F: Write, R> GeneratorWithFile for RustTestCaseGen<'a, F, R> {
+	type GeneratorFile = F;
+	fn file(&mut self) -> &mut Self::GeneratorFile {
+		self.file
+	}
+}
+
+impl <'a, F: Write, R> Generator<'a> for RustTestCaseGen<'a, F, R> {
+    type Lang = verilization_lang_rust::RustLanguage;
+	fn model(&self) -> &'a model::Verilization {
+		self.model
+	}
+
+	fn scope(&self) -> &model::Scope<'a> {
+		&self.scope
+	}
+}
+
+impl <'a, F: Write, R> RustGenerator<'a> for RustTestCaseGen<'a, F, R> {
+	fn options(&self) -> &RustOptions {
+		self.options
+	}
+
+	fn add_user_converter(&mut self) {}
+}
+
+impl <'a, F: Write, R: Rng> RustTestCaseGen<'a, F, R> {
+    fn generate(&mut self) -> Result<(), VError> {
+        for ver in self.type_def.versions() {
+            self.versioned_type(&ver.version)?;
+        }
+
+        Ok(())
+    }
+
+    fn versioned_type(&mut self, version: &BigUint) -> Result<(), VError> {
+        write!(self.file, "    crate::check::check::<")?;
+
+        let type_args: Vec<_> = self.type_def.type_params().iter().map(|_| model::TypeArgument::Type(model::Type { name: model::QualifiedName { package: model::PackageName::new(), name: String::from("u32") }, args: Vec::new() })).collect();
+        let current_type = model::Type { name: self.type_def.name().clone(), args: type_args };
+        let current_lang_type = self.build_type(version, &current_type)?;
+
+        self.write_codec_type(&self.build_codec(current_lang_type.clone())?)?;
+        write!(self.file, ">(")?;
+        
+        let value = generate_random_value(self.random, current_lang_type.clone())?;
+
+        self.write_expr(&self.build_value(version, current_lang_type.clone(), value.clone())?)?;
+        
+        let mut writer = MemoryFormatWriter::new();
+        write_constant_value(&mut writer, value, current_lang_type)?;
+        
+        write!(self.file, ", &[")?;
+        for b in writer.data() {
+            write!(self.file, "{},", b)?;
+        }
+        writeln!(self.file, "]);")?;
+
+
+        Ok(())
+    }
+}
+
+
+pub struct RustTestGenerator {
+    file: File,
+}
+
+impl TestGenerator for RustTestGenerator {
+    fn start() -> Result<RustTestGenerator, VError> {
+        let mut file = File::create("../rust/src/gen/tests.rs")?;
+
+        writeln!(file, "#[test]")?;
+        writeln!(file, "fn test() {{")?;
+        
+
+        Ok(RustTestGenerator {
+            file,
+        })
+    }
+
+    fn generate_tests<'a, R: Rng>(&'a mut self, model: &'a Verilization, random: &'a mut R) -> Result<(), VError> {
+        let options = verilization_lang_rust::RustLanguage::test_options();
+
+        for t in model.types() {
+            let t = match t {
+                model::NamedTypeDefinition::StructType(t) => t,
+                model::NamedTypeDefinition::EnumType(t) => t,
+                model::NamedTypeDefinition::ExternType(_) => continue,
+                model::NamedTypeDefinition::InterfaceType(_) => continue,
+            };
+
+            let mut gen = RustTestCaseGen {
+                file: &mut self.file,
+                options: &options,
+                random,
+                model,
+                type_def: t,
+                scope: model::Scope::empty(model),
+            };
+    
+            gen.generate()?;
+        }
+
+        Ok(())
+    }
+    
+    fn end(mut self) -> Result<(), VError> {
+        writeln!(self.file, "}}")?;
+        Ok(())
+    }
+}
@@ -60,7 +60,7 @@ impl <'a, F: Write, R: Rng> ScalaTestCaseGen<'a, F, R> {
 
     fn versioned_type(&mut self, version: &BigUint) -> Result<(), VError> {
 
-        let type_args: Vec<_> = self.type_def.type_params().iter().map(|_| model::Type { name: model::QualifiedName { package: model::PackageName::new(), name: String::from("u32") }, args: Vec::new() }).collect();
+        let type_args: Vec<_> = self.type_def.type_params().iter().map(|_| model::TypeArgument::Type(model::Type { name: model::QualifiedName { package: model::PackageName::new(), name: String::from("u32") }, args: Vec::new() })).collect();
         let current_type = model::Type { name: self.type_def.name().clone(), args: type_args };
         let current_lang_type = self.build_type(version, &current_type)?;
 
@@ -5,9 +5,15 @@ pub const RUNTIME_DIR: &str = "../../runtime/verilization/";
 
 pub const RUNTIME_FILES: &[&str] = &[
     "integral",
+    "float",
+    "bool",
+    "bytes",
     "string",
     "list",
     "option",
+    "map",
+    "set",
+    "array",
 ];
 
 
@@ -46,6 +46,7 @@ impl TestLanguage for verilization_lang_typescript::TypeScriptLanguage {
             library_mapping: HashMap::<_, _>::from_iter(IntoIter::new([
                 ( PackageName::from_parts(&[]), OsString::from("@verilization/runtime") ),
             ])),
+            downgrade: true,
         }
     }
     
@@ -99,13 +100,35 @@ impl TestLanguage for verilization_lang_java::JavaLanguage {
                 ( QualifiedName::from_parts(&[], "i8"), QualifiedName::from_parts(&[], "byte") ),
                 ( QualifiedName::from_parts(&[], "u16"), QualifiedName::from_parts(&[], "short") ),
                 ( QualifiedName::from_parts(&[], "i16"), QualifiedName::from_parts(&[], "short") ),
+                ( QualifiedName::from_parts(&[], "u24"), QualifiedName::from_parts(&[], "int") ),
+                ( QualifiedName::from_parts(&[], "i24"), QualifiedName::from_parts(&[], "int") ),
                 ( QualifiedName::from_parts(&[], "u32"), QualifiedName::from_parts(&[], "int") ),
                 ( QualifiedName::from_parts(&[], "i32"), QualifiedName::from_parts(&[], "int") ),
                 ( QualifiedName::from_parts(&[], "u64"), QualifiedName::from_parts(&[], "long") ),
                 ( QualifiedName::from_parts(&[], "i64"), QualifiedName::from_parts(&[], "long") ),
+                ( QualifiedName::from_parts(&[], "u128"), QualifiedName::from_parts(&["java", "math"], "BigInteger") ),
+                ( QualifiedName::from_parts(&[], "i128"), QualifiedName::from_parts(&["java", "math"], "BigInteger") ),
+                ( QualifiedName::from_parts(&[], "u16be"), QualifiedName::from_parts(&[], "short") ),
+                ( QualifiedName::from_parts(&[], "i16be"), QualifiedName::from_parts(&[], "short") ),
+                ( QualifiedName::from_parts(&[], "u24be"), QualifiedName::from_parts(&[], "int") ),
+                ( QualifiedName::from_parts(&[], "i24be"), QualifiedName::from_parts(&[], "int") ),
+                ( QualifiedName::from_parts(&[], "u32be"), QualifiedName::from_parts(&[], "int") ),
+                ( QualifiedName::from_parts(&[], "i32be"), QualifiedName::from_parts(&[], "int") ),
+                ( QualifiedName::from_parts(&[], "u64be"), QualifiedName::from_parts(&[], "long") ),
+                ( QualifiedName::from_parts(&[], "i64be"), QualifiedName::from_parts(&[], "long") ),
+                ( QualifiedName::from_parts(&[], "u128be"), QualifiedName::from_parts(&["java", "math"], "BigInteger") ),
+                ( QualifiedName::from_parts(&[], "i128be"), QualifiedName::from_parts(&["java", "math"], "BigInteger") ),
+                ( QualifiedName::from_parts(&[], "f32"), QualifiedName::from_parts(&[], "float") ),
+                ( QualifiedName::from_parts(&[], "f64"), QualifiedName::from_parts(&[], "double") ),
+                ( QualifiedName::from_parts(&[], "bool"), QualifiedName::from_parts(&[], "boolean") ),
+                ( QualifiedName::from_parts(&[], "bytes"), QualifiedName::from_parts(&[], "byte[]") ),
                 ( QualifiedName::from_parts(&[], "string"), QualifiedName::from_parts(&["java", "lang"], "String") ),
                 ( QualifiedName::from_parts(&[], "option"), QualifiedName::from_parts(&["java", "util"], "Optional") ),
+                ( QualifiedName::from_parts(&[], "map"), QualifiedName::from_parts(&["java", "util"], "Map") ),
+                ( QualifiedName::from_parts(&[], "set"), QualifiedName::from_parts(&["java", "util"], "Set") ),
+                ( QualifiedName::from_parts(&[], "array"), QualifiedName::from_parts(&["java", "util"], "List") ),
             ])),
+            downgrade: true,
         }
     }
     
@@ -155,6 +178,7 @@ impl TestLanguage for verilization_lang_scala::ScalaLanguage {
             library_mapping: HashMap::<_, _>::from_iter(IntoIter::new([
                 ( PackageName::from_parts(&[]), PackageName::from_parts(&["dev", "argon", "verilization", "scala_runtime"]) ),
             ])),
+            downgrade: true,
         }
     }
     
@@ -179,3 +203,46 @@ impl TestLanguage for verilization_lang_scala::ScalaLanguage {
     }
 }
 
+impl TestLanguage for verilization_lang_rust::RustLanguage {
+    type TestGen = crate::rust_test_gen::RustTestGenerator;
+
+    fn test_options() -> Self::Options {
+        Self::test_options_dir(OsString::from("../rust/src/"))
+    }
+
+    fn test_options_dir(dir: OsString) -> Self::Options {
+        verilization_lang_rust::RustOptions {
+            output_dir: dir,
+            package_mapping: HashMap::<_, _>::from_iter(IntoIterator::into_iter([
+                ( PackageName::from_parts(&["struct", "versions"]), String::from("gen::struct_versions") ),
+                ( PackageName::from_parts(&["enum", "versions"]), String::from("gen::enum_versions") ),
+                ( PackageName::from_parts(&["genericsTest"]), String::from("gen::generics_test") ),
+                ( PackageName::from_parts(&["finalTest"]), String::from("gen::final_test") ),
+                ( PackageName::from_parts(&["interfaceExample"]), String::from("gen::interface_example") ),
+            ])),
+            library_mapping: HashMap::<_, _>::from_iter(IntoIterator::into_iter([
+                ( PackageName::from_parts(&[]), String::from("verilization_runtime::types") ),
+            ])),
+        }
+    }
+
+    fn append_options(command: &mut Command, options: &Self::Options) {
+        command.arg("-o:out_dir");
+        command.arg(&options.output_dir);
+        for (pkg, rust_module) in &options.package_mapping {
+            command.arg(format!("-o:pkg:{}", pkg));
+            command.arg(rust_module);
+        }
+        for (pkg, rust_module) in &options.library_mapping {
+            command.arg(format!("-o:lib:{}", pkg));
+            command.arg(rust_module);
+        }
+    }
+
+    fn test_command() -> Command {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("test");
+        cmd.current_dir("../rust");
+        cmd
+    }
+}
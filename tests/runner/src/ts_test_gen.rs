@@ -1,6 +1,6 @@
 use verilization_compiler::{lang, model, VError};
 use lang::GeneratorError;
-use verilization_lang_typescript::{TSGenerator, TSOptions};
+use verilization_lang_typescript::{TSGenerator, TSImports, TSOptions};
 use lang::generator::*;
 use model::{Verilization, Named};
 use crate::value_generator::{generate_random_value, write_constant_value};
@@ -24,6 +24,7 @@ struct TSTestCaseGen<'model, 'opt, 'state, 'output, F, R> {
     model: &'model Verilization,
 	type_def: Named<'model, model::VersionedTypeDefinitionData>,
 	scope: model::Scope<'model>,
+	imports: TSImports,
 }
 
 impl <'model, 'opt, 'state, 'output, F: Write, R> GeneratorWithFile for TSTestCaseGen<'model, 'opt, 'state, 'output, F, R> {
@@ -65,6 +66,10 @@ impl <'model, 'opt, 'state, 'output, F: Write, R> TSGenerator<'model> for TSTest
 	}
 
 	fn add_user_converter(&mut self, _name: String) {}
+
+	fn imports(&mut self) -> &mut TSImports {
+		&mut self.imports
+	}
 }
 
 impl <'model, 'opt, 'state, 'output, F: Write, R: Rng> TSTestCaseGen<'model, 'opt, 'state, 'output, F, R> {
@@ -101,7 +106,7 @@ impl <'model, 'opt, 'state, 'output, F: Write, R: Rng> TSTestCaseGen<'model, 'op
     fn versioned_type(&mut self, version: &BigUint) -> Result<(), VError> {
         write!(self.file, "await check(")?;
 
-        let type_args: Vec<_> = self.type_def.type_params().iter().map(|_| model::Type { name: model::QualifiedName { package: model::PackageName::new(), name: String::from("u32") }, args: Vec::new() }).collect();
+        let type_args: Vec<_> = self.type_def.type_params().iter().map(|_| model::TypeArgument::Type(model::Type { name: model::QualifiedName { package: model::PackageName::new(), name: String::from("u32") }, args: Vec::new() })).collect();
         let current_type = model::Type { name: self.type_def.name().clone(), args: type_args };
         let current_lang_type = self.build_type(version, &current_type)?;
 
@@ -165,6 +170,7 @@ impl TestGenerator for TSTestGenerator {
                 model: model,
                 type_def: t,
                 scope: model::Scope::empty(model),
+                imports: TSImports::default(),
             };
 
             gen.generate()?;
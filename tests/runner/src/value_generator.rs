@@ -2,7 +2,7 @@ use verilization_compiler::*;
 use lang::generator::*;
 use lang::GeneratorError;
 use model::{ConstantValue, ConstantValueRecordBuilder};
-use verilization_runtime::{FormatWriter, VerilizationCodec};
+use verilization_runtime::{types, Codec, FormatWriter, VerilizationCodec};
 use rand::Rng;
 use num_bigint::{BigInt, BigUint, RandBigInt};
 use num_traits::{One, ToPrimitive};
@@ -17,7 +17,7 @@ pub fn generate_random_value<R: Rng>(random: &mut R, t: LangType) -> Result<Cons
 
             for field in fields.build()? {
                 let value = generate_random_value(random, field.field_type)?;
-                record.add_field(field.name.clone(), value)?;
+                record.add_field(field.name.clone(), value, None)?;
             }
 
             ConstantValue::Record(record.build())
@@ -33,6 +33,9 @@ pub fn generate_random_value<R: Rng>(random: &mut R, t: LangType) -> Result<Cons
             ConstantValue::Case(field.name.clone(), vec!(value))
         },
 
+        // Entry order is not part of the encoding, so only empty sets and maps can be compared byte for byte.
+        LangType::Extern(name, _, _) if name.package.package.is_empty() && name.name == "set" => ConstantValue::Sequence(Vec::new()),
+
         LangType::Extern(_, _, literals) => {
             let mut literals = literals.build()?;
             let literal = literals.remove(random.gen_range(0..literals.len()));
@@ -59,14 +62,21 @@ pub fn generate_random_value<R: Rng>(random: &mut R, t: LangType) -> Result<Cons
                     let s = random_string::generate(len, &charset).to_string();
                     ConstantValue::String(s)
                 },
-                LangLiteral::Sequence(element_type) => {
-                    let len: u32 = random.gen_range(0..200);
+                LangLiteral::Boolean => ConstantValue::Boolean(random.gen()),
+                // Values are representable as f32 so that they are exact for both f32 and f64.
+                LangLiteral::Float => ConstantValue::Float(f64::from(random.gen_range(-1000000.0f32..1000000.0f32))),
+                LangLiteral::Sequence(element_type, length) => {
+                    let len = match length {
+                        Some(length) => length.to_u32().unwrap(),
+                        None => random.gen_range(0..200),
+                    };
                     let mut values = Vec::new();
                     for _ in 0..len {
                         values.push(generate_random_value(random, element_type.clone())?);
                     }
                     ConstantValue::Sequence(values)
                 },
+                LangLiteral::Map(_, _) => ConstantValue::Map(Vec::new()),
                 LangLiteral::Case(case_name, arg_types) => {
                     let mut values = Vec::new();
                     for arg_type in arg_types {
@@ -77,14 +87,14 @@ pub fn generate_random_value<R: Rng>(random: &mut R, t: LangType) -> Result<Cons
                 LangLiteral::Record(fields) => {
                     let mut record = ConstantValueRecordBuilder::new();
                     for field in fields {
-                        record.add_field(field.name.clone(), generate_random_value(random, field.field_type)?)?;
+                        record.add_field(field.name.clone(), generate_random_value(random, field.field_type)?, None)?;
                     }
                     ConstantValue::Record(record.build())
                 },
             }
         },
         
-        LangType::Interface(..) | LangType::TypeParameter(_) |
+        LangType::Interface(..) | LangType::TypeParameter(_) | LangType::Integer(_) |
         LangType::Codec(_) | LangType::Converter(_, _) |
         LangType::RemoteObjectId | LangType::RemoteConnection => Err(GeneratorError::InvalidTypeForConstant)?,
     })
@@ -104,12 +114,35 @@ pub fn write_constant_value<W: FormatWriter<Error = GeneratorError>>(writer: &mu
             ("i32", []) => n.to_i32().unwrap().write_verilization(writer)?,
             ("u64", []) => n.to_u64().unwrap().write_verilization(writer)?,
             ("i64", []) => n.to_i64().unwrap().write_verilization(writer)?,
+            ("u24", []) => types::u24::Codec::write_verilization(&n.to_u32().unwrap(), writer)?,
+            ("i24", []) => types::i24::Codec::write_verilization(&n.to_i32().unwrap(), writer)?,
+            ("u128", []) => n.to_u128().unwrap().write_verilization(writer)?,
+            ("i128", []) => n.to_i128().unwrap().write_verilization(writer)?,
+            ("u16be", []) => types::u16be::Codec::write_verilization(&n.to_u16().unwrap(), writer)?,
+            ("i16be", []) => types::i16be::Codec::write_verilization(&n.to_i16().unwrap(), writer)?,
+            ("u24be", []) => types::u24be::Codec::write_verilization(&n.to_u32().unwrap(), writer)?,
+            ("i24be", []) => types::i24be::Codec::write_verilization(&n.to_i32().unwrap(), writer)?,
+            ("u32be", []) => types::u32be::Codec::write_verilization(&n.to_u32().unwrap(), writer)?,
+            ("i32be", []) => types::i32be::Codec::write_verilization(&n.to_i32().unwrap(), writer)?,
+            ("u64be", []) => types::u64be::Codec::write_verilization(&n.to_u64().unwrap(), writer)?,
+            ("i64be", []) => types::i64be::Codec::write_verilization(&n.to_i64().unwrap(), writer)?,
+            ("u128be", []) => types::u128be::Codec::write_verilization(&n.to_u128().unwrap(), writer)?,
+            ("i128be", []) => types::i128be::Codec::write_verilization(&n.to_i128().unwrap(), writer)?,
             _ => return Err(GeneratorError::InvalidTypeForConstant),
         },
 
         (ConstantValue::String(s), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "string" && type_args.is_empty() =>
             s.write_verilization(writer)?,
 
+        (ConstantValue::Boolean(b), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "bool" && type_args.is_empty() =>
+            b.write_verilization(writer)?,
+
+        (ConstantValue::Float(x), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && type_args.is_empty() => match name.name.as_ref() {
+            "f32" => (x as f32).write_verilization(writer)?,
+            "f64" => x.write_verilization(writer)?,
+            _ => return Err(GeneratorError::InvalidTypeForConstant),
+        },
+
             (ConstantValue::Sequence(values), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "list" => match &type_args[..] {
                 [element_type] => {
                     BigUint::from(values.len()).write_verilization(writer)?;
@@ -120,6 +153,44 @@ pub fn write_constant_value<W: FormatWriter<Error = GeneratorError>>(writer: &mu
                 _ => return Err(GeneratorError::InvalidTypeForConstant),
             },
 
+        (ConstantValue::Sequence(values), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "bytes" && type_args.is_empty() => {
+            let data = values.into_iter().map(|value| match value {
+                ConstantValue::Integer(n) => n.to_u8().ok_or(GeneratorError::InvalidTypeForConstant),
+                _ => Err(GeneratorError::InvalidTypeForConstant),
+            }).collect::<Result<Vec<_>, _>>()?;
+            data.write_verilization(writer)?;
+        },
+
+        (ConstantValue::Sequence(values), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "array" => match &type_args[..] {
+            [element_type, LangType::Integer(_)] => {
+                for value in values {
+                    write_constant_value(writer, value, element_type.clone())?;
+                }
+            },
+            _ => return Err(GeneratorError::InvalidTypeForConstant),
+        },
+
+        (ConstantValue::Sequence(values), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "set" => match &type_args[..] {
+            [element_type] => {
+                BigUint::from(values.len()).write_verilization(writer)?;
+                for value in values {
+                    write_constant_value(writer, value, element_type.clone())?;
+                }
+            },
+            _ => return Err(GeneratorError::InvalidTypeForConstant),
+        },
+
+        (ConstantValue::Map(entries), LangType::Extern(name, type_args, _)) if name.package.package.is_empty() && name.name == "map" => match &type_args[..] {
+            [key_type, value_type] => {
+                BigUint::from(entries.len()).write_verilization(writer)?;
+                for (key, value) in entries {
+                    write_constant_value(writer, key, key_type.clone())?;
+                    write_constant_value(writer, value, value_type.clone())?;
+                }
+            },
+            _ => return Err(GeneratorError::InvalidTypeForConstant),
+        },
+
         (ConstantValue::Case(case_name, mut values), LangType::Extern(name, mut type_args, _))
                 if name.package.package.is_empty() &&
                     name.name == "option" &&
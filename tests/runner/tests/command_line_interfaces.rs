@@ -143,3 +143,8 @@ fn run_cli_java(file: &str) {
 fn run_cli_scala(file: &str) {
     run_test_case::<verilization_lang_scala::ScalaLanguage>(file).unwrap()
 }
+
+#[test_resources("tests/verilization/*.verilization")]
+fn run_cli_rust(file: &str) {
+    run_test_case::<verilization_lang_rust::RustLanguage>(file).unwrap()
+}
@@ -0,0 +1,191 @@
+use verilization_test_runner::*;
+
+use verilization_compiler::{model, parser, MemoryOutputHandler, VError};
+use verilization_compiler::source::SourceFile;
+
+use test_lang::TestLanguage;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+
+
+fn generate_test_case<Lang: TestLanguage>(file: &str) -> Result<HashMap<String, String>, VError> {
+    let mut input_files = vec!(OsString::from(format!("../verilization/{}.verilization", file)));
+    for rt_file in test_cases::RUNTIME_FILES {
+        input_files.push(OsString::from(format!("{}/{}.verilization", test_cases::RUNTIME_DIR, rt_file)));
+    }
+
+    let model = verilization_compiler::load_files(input_files)?;
+
+    let mut output = MemoryOutputHandler { files: HashMap::new() };
+    Lang::generate(&model, Lang::test_options_dir(OsString::from("gen")), &mut output)?;
+
+    Ok(output.files.into_iter()
+        .map(|(name, data)| (name.replace('\\', "/"), String::from_utf8(data).unwrap()))
+        .collect())
+}
+
+fn find_file<'a>(files: &'a HashMap<String, String>, suffix: &str) -> &'a str {
+    files.iter()
+        .find(|(name, _)| name.ends_with(suffix))
+        .map(|(_, content)| content.as_str())
+        .unwrap_or_else(|| panic!("Could not find generated file {}", suffix))
+}
+
+// Converters of non-generic types are values, so they must not be called with empty arguments.
+fn assert_converters_not_called<Lang: TestLanguage>() -> Result<(), VError> {
+    for file in test_cases::TEST_CASE_FILES {
+        for (name, content) in generate_test_case::<Lang>(file)? {
+            for version in 1..10 {
+                assert!(!content.contains(&format!("fromV{}()", version)), "{} calls fromV{} with no arguments", name, version);
+                assert!(!content.contains(&format!("toV{}()", version)), "{} calls toV{} with no arguments", name, version);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn typescript_converters_are_values() -> Result<(), VError> {
+    assert_converters_not_called::<verilization_lang_typescript::TypeScriptLanguage>()?;
+
+    let files = generate_test_case::<verilization_lang_typescript::TypeScriptLanguage>("struct_versions")?;
+    assert!(find_file(&files, "struct/versions/Renamed.ts").contains("Referenced.V2.fromV1.convert(prev.kept)"));
+    Ok(())
+}
+
+#[test]
+fn java_converters_are_values() -> Result<(), VError> {
+    assert_converters_not_called::<verilization_lang_java::JavaLanguage>()?;
+
+    let files = generate_test_case::<verilization_lang_java::JavaLanguage>("struct_versions")?;
+    assert!(find_file(&files, "struct/versions/Renamed.java").contains("Referenced.V2.fromV1.convert("));
+    Ok(())
+}
+
+#[test]
+fn scala_converters_are_values() -> Result<(), VError> {
+    assert_converters_not_called::<verilization_lang_scala::ScalaLanguage>()
+}
+
+const LITERALS_SCHEMA: &str = "
+version 1;
+package literals;
+
+struct Holder {
+    version 1 {
+        values: list(u8);
+        maybe: option(u8);
+        counts: map(string, u8);
+    }
+}
+
+const empty: Holder {
+    version 1 = { values = []; maybe = none(); counts = []; };
+}
+";
+
+fn generate_literals<Lang: TestLanguage>(options: Lang::Options, file_name: &str) -> Result<String, VError> {
+    let mut input_files = Vec::new();
+    for rt_file in test_cases::RUNTIME_FILES {
+        input_files.push(OsString::from(format!("{}/{}.verilization", test_cases::RUNTIME_DIR, rt_file)));
+    }
+
+    let runtime = verilization_compiler::load_files(input_files)?;
+    let literals = parser::parse_model(&SourceFile::new(String::from("literals.verilization"), String::from(LITERALS_SCHEMA)));
+    let model = verilization_compiler::load_all_models(vec!(Ok(runtime), literals).into_iter())?;
+
+    let mut output = MemoryOutputHandler { files: HashMap::new() };
+    Lang::generate(&model, options, &mut output)?;
+
+    let files = output.files.into_iter()
+        .map(|(name, data)| (name.replace('\\', "/"), String::from_utf8(data).unwrap()))
+        .collect();
+    Ok(String::from(find_file(&files, file_name)))
+}
+
+// Skips any type arguments between the operation name and its arguments.
+fn is_called_without_args(content: &str, op: &str) -> bool {
+    content.match_indices(op).any(|(index, _)| {
+        let rest = &content[index + op.len()..];
+        let rest = match rest.chars().next() {
+            Some(open @ '<') | Some(open @ '[') => {
+                let close = if open == '<' { '>' } else { ']' };
+                let mut depth = 0;
+                let end = rest.char_indices().find(|(_, c)| {
+                    if *c == open { depth += 1; }
+                    else if *c == close { depth -= 1; }
+                    depth == 0
+                });
+                match end {
+                    Some((end, _)) => &rest[end + 1..],
+                    None => return false,
+                }
+            },
+            _ => rest,
+        };
+        rest.starts_with("()")
+    })
+}
+
+// Literal constructors are functions, so they are still called when there are no elements.
+#[test]
+fn literal_constructors_are_called() -> Result<(), VError> {
+    let mut ts_options = verilization_lang_typescript::TypeScriptLanguage::test_options_dir(OsString::from("gen"));
+    ts_options.package_mapping.insert(model::PackageName::from_parts(&["literals"]), OsString::from("literals"));
+    let ts = generate_literals::<verilization_lang_typescript::TypeScriptLanguage>(ts_options, "literals/empty.ts")?;
+
+    let mut java_options = verilization_lang_java::JavaLanguage::test_options_dir(OsString::from("gen"));
+    java_options.package_mapping.insert(model::PackageName::from_parts(&["literals"]), model::PackageName::from_parts(&["literals"]));
+    let java = generate_literals::<verilization_lang_java::JavaLanguage>(java_options, "literals/Empty.java")?;
+
+    let mut scala_options = verilization_lang_scala::ScalaLanguage::test_options_dir(OsString::from("gen"));
+    scala_options.package_mapping.insert(model::PackageName::from_parts(&["literals"]), model::PackageName::from_parts(&["literals"]));
+    let scala = generate_literals::<verilization_lang_scala::ScalaLanguage>(scala_options, "literals/empty.scala")?;
+
+    for content in &[ts, java, scala] {
+        for op in &["fromSequence", "fromCaseNone", "fromMap"] {
+            assert!(is_called_without_args(content, op), "{} is not called in {}", op, content);
+        }
+    }
+    Ok(())
+}
+
+const STRUCT_KEY_SCHEMA: &str = "
+version 1;
+package keys;
+
+struct Point {
+    version 1 {
+        x: u32;
+    }
+}
+
+struct Index {
+    version 1 {
+        points: map(Point, string);
+        unique: set(Point);
+    }
+}
+";
+
+// The runtimes compare keys by value only for primitive types, so struct keys are rejected before generating code.
+#[test]
+fn struct_keys_are_rejected() -> Result<(), VError> {
+    let mut input_files = Vec::new();
+    for rt_file in test_cases::RUNTIME_FILES {
+        input_files.push(OsString::from(format!("{}/{}.verilization", test_cases::RUNTIME_DIR, rt_file)));
+    }
+
+    let runtime = verilization_compiler::load_files(input_files)?;
+    let keys = parser::parse_model(&SourceFile::new(String::from("keys.verilization"), String::from(STRUCT_KEY_SCHEMA)));
+    let err = match verilization_compiler::load_all_models(vec!(Ok(runtime), keys).into_iter()) {
+        Ok(_) => panic!("Struct keys were not rejected"),
+        Err(err) => err,
+    };
+
+    let codes: Vec<_> = err.errors().iter().map(|err| err.code()).collect();
+    assert_eq!(codes, vec!(Some("T0024"), Some("T0024")));
+    Ok(())
+}
@@ -82,4 +82,7 @@ fn run_scala_tests() -> Result<(), VError> {
     run_tests_for_lang::<verilization_lang_scala::ScalaLanguage>()
 }
 
-
+#[test]
+fn run_rust_tests() -> Result<(), VError> {
+    run_tests_for_lang::<verilization_lang_rust::RustLanguage>()
+}
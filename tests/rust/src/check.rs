@@ -0,0 +1,14 @@
+use crate::memory_format::{MemoryFormatReader, MemoryFormatWriter};
+use std::fmt::Debug;
+use verilization_runtime::Codec;
+
+pub fn check<C: Codec>(value: C::Value, encoded: &[u8]) where C::Value : PartialEq + Debug {
+    let mut writer = MemoryFormatWriter::new();
+    C::write_verilization(&value, &mut writer).unwrap();
+    assert_eq!(writer.data(), encoded, "Encode failed");
+
+    let mut reader = MemoryFormatReader::new(encoded);
+    let decoded = C::read_verilization(&mut reader).unwrap();
+    assert!(reader.is_eof(), "Decode failed: Did not consume all input");
+    assert_eq!(decoded, value, "Decode failed");
+}
@@ -0,0 +1,31 @@
+use crate::gen::{enum_versions, final_test};
+use crate::memory_format::MemoryFormatReader;
+use std::io;
+use verilization_runtime::{types, Codec};
+
+#[test]
+fn unknown_tag() {
+    let mut reader = MemoryFormatReader::new(&[0x05, 0x00, 0x00, 0x00, 0x00]);
+    let err = enum_versions::referenced::V1Codec::read_verilization(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_any_version() {
+    let mut reader = MemoryFormatReader::new(&[0x05, 0x00, 0x00, 0x00]);
+    let value = final_test::upgrade::read_any_version(3, &mut reader).unwrap();
+    assert_eq!(value, final_test::upgrade::V4 { n: 5 });
+    assert!(reader.is_eof());
+
+    let mut reader = MemoryFormatReader::new(&[0x05, 0x00, 0x00, 0x00]);
+    let err = final_test::upgrade::read_any_version(1, &mut reader).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn big_endian_and_24_bit_codecs() {
+    crate::check::check::<types::list::Codec<types::u32be::Codec>>(vec![1], &[0x01, 0x00, 0x00, 0x00, 0x01]);
+    crate::check::check::<types::list::Codec<types::u32::Codec>>(vec![1], &[0x01, 0x01, 0x00, 0x00, 0x00]);
+    crate::check::check::<types::i24::Codec>(-2, &[0xFE, 0xFF, 0xFF]);
+    crate::check::check::<types::u24be::Codec>(0x010203, &[0x01, 0x02, 0x03]);
+}
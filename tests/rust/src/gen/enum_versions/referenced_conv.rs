@@ -0,0 +1,5 @@
+pub fn v1_to_v2(prev: super::V1) -> super::V2 {
+    match prev {
+        super::V1::X(x) => super::V2::X(i64::from(x)),
+    }
+}
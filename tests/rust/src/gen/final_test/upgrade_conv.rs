@@ -0,0 +1,5 @@
+pub fn v3_to_v4(prev: super::V3) -> super::V4 {
+    super::V4 {
+        n: i64::from(prev.n),
+    }
+}
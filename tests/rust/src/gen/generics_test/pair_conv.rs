@@ -0,0 +1,7 @@
+pub fn v3_to_v4<A_1, A_2, B_1, B_2>(a_conv: impl Fn(A_1) -> A_2 + Copy, b_conv: impl Fn(B_1) -> B_2 + Copy) -> impl Fn(super::V3<A_1, B_1>) -> super::V4<A_2, B_2> + Copy {
+    move |prev| super::V4 {
+        left: a_conv(prev.left),
+        right: b_conv(prev.right),
+        other: String::from("dummy"),
+    }
+}
@@ -0,0 +1,5 @@
+pub fn v1_to_v2(prev: super::V1) -> super::V2 {
+    super::V2 {
+        x: i64::from(prev.x),
+    }
+}
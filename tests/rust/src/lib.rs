@@ -0,0 +1,16 @@
+mod memory_format;
+mod check;
+
+pub mod gen {
+    pub mod struct_versions;
+    pub mod enum_versions;
+    pub mod generics_test;
+    pub mod final_test;
+    pub mod interface_example;
+
+    #[cfg(test)]
+    mod tests;
+}
+
+#[cfg(test)]
+mod decoding;
@@ -0,0 +1,98 @@
+use std::convert::TryInto;
+use std::io;
+use verilization_runtime::{FormatReader, FormatWriter};
+
+pub struct MemoryFormatReader<'a> {
+    data: &'a [u8],
+}
+
+impl <'a> MemoryFormatReader<'a> {
+    pub fn new(data: &'a [u8]) -> MemoryFormatReader<'a> {
+        MemoryFormatReader {
+            data,
+        }
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn take(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        if count > self.data.len() {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        let (bytes, rest) = self.data.split_at(count);
+        self.data = rest;
+        Ok(bytes)
+    }
+}
+
+impl <'a> FormatReader for MemoryFormatReader<'a> {
+    type Error = io::Error;
+
+    fn read_u8(&mut self) -> Result<u8, Self::Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Self::Error> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Self::Error> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Self::Error> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<Vec<u8>, Self::Error> {
+        Ok(self.take(count)?.to_vec())
+    }
+}
+
+pub struct MemoryFormatWriter {
+    data: Vec<u8>,
+}
+
+impl MemoryFormatWriter {
+    pub fn new() -> MemoryFormatWriter {
+        MemoryFormatWriter {
+            data: Vec::new(),
+        }
+    }
+
+    pub fn data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl FormatWriter for MemoryFormatWriter {
+    type Error = io::Error;
+
+    fn write_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.data.push(value);
+        Ok(())
+    }
+
+    fn write_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_u32(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.data.extend_from_slice(data);
+        Ok(())
+    }
+}
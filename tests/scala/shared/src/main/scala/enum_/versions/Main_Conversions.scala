@@ -3,9 +3,10 @@ package enum_.versions
 import dev.argon.verilization.scala_runtime.Converter
 
 object Main_Conversions {
-	val v3ToV4: Converter[Main.V3, Main.V4] = {
-		case Main.V3.N(n) => Main.V4.N(n)
-		case Main.V3.M(m) => Main.V4.M(m)
-		case Main.V3.R(r) => Main.V4.R(Referenced.V4.fromV3.convert(r))
+	val v4ToV3: Converter[Main.V4, Main.V3] = {
+		case Main.V4.N(n) => Main.V3.N(n)
+		case Main.V4.M(m) => Main.V3.M(m)
+		case Main.V4.R(r) => Main.V3.R(Referenced.V4.toV3.convert(r))
+		case Main.V4.Addition(_) => throw new IllegalArgumentException("Version 3 does not have the addition case")
 	}
 }
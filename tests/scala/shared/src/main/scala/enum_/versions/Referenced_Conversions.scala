@@ -6,4 +6,8 @@ object Referenced_Conversions {
 	val v1ToV2: Converter[Referenced.V1, Referenced.V2] = {
 		case Referenced.V1.X(x) => Referenced.V2.X(x)
 	}
+
+	val v2ToV1: Converter[Referenced.V2, Referenced.V1] = {
+		case Referenced.V2.X(x) => Referenced.V1.X(x.toInt)
+	}
 }
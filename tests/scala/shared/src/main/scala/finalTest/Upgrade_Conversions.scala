@@ -2,4 +2,5 @@ package finalTest
 
 object Upgrade_Conversions {
     def v3ToV4(prev: Upgrade.V3): Upgrade.V4 = Upgrade.V4(prev.n)
+    def v4ToV3(prev: Upgrade.V4): Upgrade.V3 = Upgrade.V3(prev.n.toInt)
 }
@@ -3,11 +3,6 @@ package struct.versions
 import dev.argon.verilization.scala_runtime.Converter
 
 object Main_Conversions {
-    val v3ToV4: Converter[Main.V3, Main.V4] = prev =>
-        Main.V4(
-            n = prev.n,
-            m = prev.m,
-            r = Referenced.V4.fromV3.convert(prev.r),
-            addition = Addition.V4(5),
-        )
-}
\ No newline at end of file
+    val v4ToV3: Converter[Main.V4, Main.V3] = prev =>
+        Main.V3(n = prev.n, m = prev.m, r = Referenced.V4.toV3.convert(prev.r))
+}
@@ -5,4 +5,7 @@ import dev.argon.verilization.scala_runtime.Converter
 object Referenced_Conversions {
     val v1ToV2: Converter[Referenced.V1, Referenced.V2] = prev =>
         Referenced.V2(x = prev.x)
+
+    val v2ToV1: Converter[Referenced.V2, Referenced.V1] = prev =>
+        Referenced.V1(x = prev.x.toInt)
 }
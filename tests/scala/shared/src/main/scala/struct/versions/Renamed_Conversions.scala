@@ -0,0 +1,8 @@
+package struct.versions
+
+import dev.argon.verilization.scala_runtime.Converter
+
+object Renamed_Conversions {
+    val v3ToV2: Converter[Renamed.V3, Renamed.V2] = prev =>
+        Renamed.V2(oldName = prev.newName, unused = 0L, kept = Referenced.V3.toV2.convert(prev.kept))
+}
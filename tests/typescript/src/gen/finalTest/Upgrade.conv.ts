@@ -9,3 +9,11 @@ export const v3_to_v4: Converter<V3, V4> = {
     },
 };
 
+export const v4_to_v3: Converter<V4, V3> = {
+    convert(prev: V4): V3 {
+        return {
+            n: Number(prev.n),
+        };
+    },
+};
+
@@ -2,15 +2,12 @@ import {V3, V4} from "./Main.js";
 import * as Referenced from "./Referenced.js";
 import {Converter} from "@verilization/runtime";
 
-export const v3_to_v4: Converter<V3, V4> = {
-    convert(v3: V3): V4 {
+export const v4_to_v3: Converter<V4, V3> = {
+    convert(prev: V4): V3 {
         return {
-            n: v3.n,
-            m: v3.m,
-            r: Referenced.V4.fromV3.convert(v3.r),
-            addition: {
-                stuff: 5,
-            },
+            n: prev.n,
+            m: prev.m,
+            r: Referenced.V4.toV3.convert(prev.r),
         };
     },
 };
@@ -7,4 +7,12 @@ export const v1_to_v2: Converter<V1, V2> = {
             x: BigInt(v1.x),
         }
     },
-}; 
+};
+
+export const v2_to_v1: Converter<V2, V1> = {
+    convert(v2: V2): V1 {
+        return {
+            x: Number(v2.x),
+        }
+    },
+};
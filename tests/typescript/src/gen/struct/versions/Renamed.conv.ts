@@ -0,0 +1,13 @@
+import {V2, V3} from "./Renamed.js";
+import * as Referenced from "./Referenced.js";
+import {Converter} from "@verilization/runtime";
+
+export const v3_to_v2: Converter<V3, V2> = {
+    convert(prev: V3): V2 {
+        return {
+            oldName: prev.newName,
+            unused: BigInt(0),
+            kept: Referenced.V3.toV2.convert(prev.kept),
+        };
+    },
+};